@@ -17,6 +17,7 @@
 use crate::env::{
     backend::{
         Env,
+        RentStatus,
         ReturnFlags,
         TypedEnv,
     },
@@ -29,6 +30,7 @@ use crate::env::{
         EnvInstance,
         OnInstance,
     },
+    Clear,
     EnvTypes,
     Result,
     Topics,
@@ -47,6 +49,40 @@ where
     <EnvInstance as OnInstance>::on_instance(|instance| TypedEnv::caller::<T>(instance))
 }
 
+/// Returns `true` if the caller of the executed contract is the origin.
+///
+/// # Note
+///
+/// The caller is the origin if the contract has been called directly by a
+/// user and not by another contract.
+///
+/// # Errors
+///
+/// If the environment does not keep track of the call stack.
+pub fn caller_is_origin<T>() -> Result<bool>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::caller_is_origin::<T>(instance)
+    })
+}
+
+/// Returns the balance of the caller of the executed contract.
+///
+/// # Errors
+///
+/// - If the environment does not expose the balances of other accounts.
+/// - If the returned value cannot be properly decoded.
+pub fn caller_balance<T>() -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::caller_balance::<T>(instance)
+    })
+}
+
 /// Returns the transferred balance for the contract execution.
 ///
 /// # Errors
@@ -75,6 +111,27 @@ where
     })
 }
 
+/// Returns the price for the specified two-dimensional weight.
+///
+/// # Note
+///
+/// The weight consists of the computation time (`ref_time`) and the size of
+/// the storage proof (`proof_size`) which are priced independently. Use
+/// [`weight_to_fee`] for scalar weights.
+///
+/// # Errors
+///
+/// - If the returned value cannot be properly decoded.
+/// - If the chain does not support two-dimensional weights.
+pub fn weight_to_fee_v2<T>(ref_time: u64, proof_size: u64) -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::weight_to_fee_v2::<T>(instance, ref_time, proof_size)
+    })
+}
+
 /// Returns the amount of gas left for the contract execution.
 ///
 /// # Errors
@@ -87,6 +144,27 @@ where
     <EnvInstance as OnInstance>::on_instance(|instance| TypedEnv::gas_left::<T>(instance))
 }
 
+/// Returns the nonce of the executed contract.
+///
+/// # Note
+///
+/// The nonce is incremented once for every contract instantiation and every
+/// transfer originating from the account. Contracts may use it for example
+/// in order to implement their own replay protection.
+///
+/// # Errors
+///
+/// - If the environment does not keep track of account nonces.
+/// - If the returned value cannot be properly decoded.
+pub fn account_nonce<T>() -> Result<u64>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::account_nonce::<T>(instance)
+    })
+}
+
 /// Returns the current block timestamp.
 ///
 /// # Errors
@@ -119,6 +197,27 @@ where
     })
 }
 
+/// Returns the code hash of the executed contract.
+///
+/// # Note
+///
+/// This is the hash of the code the executed contract has been instantiated
+/// from which is useful for upgradable and self-referential contracts.
+///
+/// # Errors
+///
+/// - If the executed contract is not a contract account.
+/// - If the environment does not support querying the own code hash.
+/// - If the returned value cannot be properly decoded.
+pub fn own_code_hash<T>() -> Result<T::Hash>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::own_code_hash::<T>(instance)
+    })
+}
+
 /// Returns the balance of the executed contract.
 ///
 /// # Errors
@@ -187,6 +286,26 @@ where
     })
 }
 
+/// Returns the rent status of the executed contract.
+///
+/// # Note
+///
+/// The current rent is the size of the contract storage in bytes times
+/// the rent byte price of the chain.
+///
+/// # Errors
+///
+/// - If the returned value cannot be properly decoded.
+/// - If the rent status is not supported by the environment.
+pub fn rent_status<T>() -> Result<RentStatus<T>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::rent_status::<T>(instance)
+    })
+}
+
 /// Emits an event with the given event data.
 pub fn emit_event<T, Event>(event: Event)
 where
@@ -198,6 +317,25 @@ where
     })
 }
 
+/// Returns the topic hash of the given value of an `#[ink(topic)]` event field.
+///
+/// # Note
+///
+/// The topic hash is the BLAKE2 256-bit hash of the SCALE encoded value.
+/// It is truncated or padded with zeros to the length of the hash type.
+pub fn topic_hash<T, V>(value: &V) -> T::Hash
+where
+    T: EnvTypes,
+    V: scale::Encode,
+{
+    let mut output = <hash::Blake2x256 as hash::HashOutput>::Type::default();
+    hash::blake2_256(&scale::Encode::encode(value), &mut output);
+    let mut topic = <T::Hash as Clear>::clear();
+    let len = core::cmp::min(topic.as_ref().len(), output.len());
+    topic.as_mut()[..len].copy_from_slice(&output[..len]);
+    topic
+}
+
 /// Sets the rent allowance of the executed contract to the new value.
 pub fn set_rent_allowance<T>(new_value: T::Balance)
 where
@@ -210,10 +348,19 @@ where
 
 /// Writes the value to the contract storage under the given key.
 ///
+/// Returns the size of the previously stored value in bytes or `None`
+/// if there was no value stored under the key.
+///
+/// # Note
+///
+/// On-chain this always returns `None` since the host functions do not
+/// report the size of the previous value, yet. Use
+/// [`contains_contract_storage`] before the write if the size is required.
+///
 /// # Panics
 ///
 /// - If the encode length of value exceeds the configured maximum value length of a storage entry.
-pub fn set_contract_storage<V>(key: &Key, value: &V)
+pub fn set_contract_storage<V>(key: &Key, value: &V) -> Option<u32>
 where
     V: scale::Encode,
 {
@@ -222,6 +369,30 @@ where
     })
 }
 
+/// Writes the value to the contract storage under the given key unless the
+/// contract storage is out of space.
+///
+/// Returns the size of the previously stored value in bytes or `None`
+/// if there was no value stored under the key.
+///
+/// # Note
+///
+/// Contracts can use this to handle a full storage gracefully. On-chain the
+/// execution traps instead if the host cannot accept the write and the size
+/// of the previous value is always reported as `None`.
+///
+/// # Errors
+///
+/// - If the contract storage cannot accept the value (`OutOfStorage`).
+pub fn try_set_contract_storage<V>(key: &Key, value: &V) -> Result<Option<u32>>
+where
+    V: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::try_set_contract_storage::<V>(instance, key, value)
+    })
+}
+
 /// Returns the value stored under the given key in the contract's storage if any.
 ///
 /// # Errors
@@ -236,8 +407,29 @@ where
     })
 }
 
+/// Returns the size of the value stored under the given key in the contract's
+/// storage in bytes if any.
+///
+/// # Note
+///
+/// Unlike [`get_contract_storage`] this does not decode the stored value.
+pub fn contains_contract_storage(key: &Key) -> Option<u32> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::contains_contract_storage(instance, key)
+    })
+}
+
 /// Clears the contract's storage key entry.
-pub fn clear_contract_storage(key: &Key) {
+///
+/// Returns the size of the previously stored value in bytes or `None`
+/// if there was no value stored under the key.
+///
+/// # Note
+///
+/// On-chain this always returns `None` since the host functions do not
+/// report the size of the previous value, yet. Use
+/// [`contains_contract_storage`] before the clear if the size is required.
+pub fn clear_contract_storage(key: &Key) -> Option<u32> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         Env::clear_contract_storage(instance, key)
     })
@@ -385,6 +577,29 @@ pub fn restore_contract<T>(
     })
 }
 
+/// Replaces the code of the executed contract with the code of the given
+/// code hash.
+///
+/// # Note
+///
+/// This upgrades the executed contract in place: its account ID, balance and
+/// storage are kept while all subsequent calls to it execute the new code.
+/// No constructor of the new code is run.
+///
+/// # Errors
+///
+/// - If there is no code uploaded under the given code hash.
+/// - If the executed contract is not a contract account.
+/// - If the environment does not support replacing the code of contracts.
+pub fn set_code_hash<T>(code_hash: T::Hash) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::set_code_hash::<T>(instance, code_hash)
+    })
+}
+
 /// Terminates the existence of the currently executed smart contract.
 ///
 /// This removes the calling account and transfers all remaining balance
@@ -424,6 +639,72 @@ where
     })
 }
 
+/// Transfers value from the contract to the destination account ID unless
+/// this would leave the destination with dust.
+///
+/// # Note
+///
+/// Unlike [`transfer`] this refuses transfers that would leave the
+/// destination with a non-zero balance below the minimum balance since
+/// such dust would be reaped and thus lost.
+///
+/// # Errors
+///
+/// - If the contract doesn't have sufficient funds.
+/// - If the destination would end up with a balance below the minimum balance.
+pub fn transfer_keep_alive<T>(destination: T::AccountId, value: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::transfer_keep_alive::<T>(instance, destination, value)
+    })
+}
+
+/// Transfers up to the given value from the executed contract to the given
+/// account ID and returns the actually transferred value.
+///
+/// # Note
+///
+/// Unlike [`transfer`] this transfers only the available balance of the
+/// executed contract if it is lower than `value`. This is useful for
+/// sweeping the balance or for best-effort payouts.
+///
+/// # Errors
+///
+/// If the transfer of the available balance fails.
+pub fn transfer_saturating<T>(
+    destination: T::AccountId,
+    value: T::Balance,
+) -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::transfer_saturating::<T>(instance, destination, value)
+    })
+}
+
+/// Dispatches the runtime call on behalf of the executed contract.
+///
+/// # Note
+///
+/// The runtime call is SCALE encoded and dispatched to the runtime with the
+/// executed contract as its origin.
+///
+/// # Errors
+///
+/// - If the runtime call failed.
+/// - If the environment does not support runtime calls.
+pub fn call_runtime<C>(call: &C) -> Result<()>
+where
+    C: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnv::call_runtime(instance, call)
+    })
+}
+
 /// Calls the chain extension with the given ID and inputs.
 ///
 /// Returns the given output type.
@@ -434,7 +715,7 @@ where
 /// - If the given inputs cannot be properly decoded by the runtime.
 /// - If the given output type cannot be properly decoded by the contract.
 /// - If some chain extension specific conditions are not met.
-#[cfg(feature = "ink-unstable-chain-extensions")]
+#[cfg(any(feature = "std", feature = "ink-unstable-chain-extensions"))]
 pub fn call_chain_extension<I, O>(func_id: u32, input: &I) -> Result<O>
 where
     I: scale::Codec + 'static,
@@ -445,6 +726,35 @@ where
     })
 }
 
+/// Calls the chain extension with the given ID and inputs.
+///
+/// Returns the status code of the chain extension alongside the given
+/// output type.
+///
+/// # Note
+///
+/// Unlike [`call_chain_extension`] non-zero status codes are not treated as
+/// errors so that contracts can interpret them on their own.
+///
+/// # Errors
+///
+/// - If the given function ID does not exist in the runtime.
+/// - If the given inputs cannot be properly decoded by the runtime.
+/// - If the given output type cannot be properly decoded by the contract.
+#[cfg(any(feature = "std", feature = "ink-unstable-chain-extensions"))]
+pub fn call_chain_extension_with_status<I, O>(
+    func_id: u32,
+    input: &I,
+) -> Result<(u32, O)>
+where
+    I: scale::Codec + 'static,
+    O: scale::Codec + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::call_chain_extension_with_status(instance, func_id, input)
+    })
+}
+
 /// Returns the execution input to the executed contract and decodes it as `T`.
 ///
 /// # Note
@@ -475,6 +785,21 @@ where
     <EnvInstance as OnInstance>::on_instance(|instance| Env::decode_input::<T>(instance))
 }
 
+/// Calls `f` with the raw input of the executed contract and returns its result.
+///
+/// # Note
+///
+/// Unlike [`decode_input`] this lends the input buffer to `f` so that
+/// values can be decoded from it without copying, e.g. via
+/// `ink_lang::decode_cow_bytes`. The input buffer is only alive while `f`
+/// is executed and `f` must not call into the environment itself.
+pub fn with_input<F, R>(f: F) -> R
+where
+    F: FnOnce(&[u8]) -> R,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| Env::with_input(instance, f))
+}
+
 /// Returns the value back to the caller of the executed contract.
 ///
 /// # Note
@@ -489,6 +814,19 @@ where
     })
 }
 
+/// Returns the already encoded value back to the caller of the executed contract.
+///
+/// # Note
+///
+/// This function  stops the execution of the contract immediately.
+/// The given bytes are returned verbatim which is useful for contracts
+/// forwarding the output of another contract to their caller.
+pub fn return_raw(return_flags: ReturnFlags, return_value: &[u8]) -> ! {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::return_raw(instance, return_flags, return_value)
+    })
+}
+
 /// Returns a random hash seed.
 ///
 /// # Note
@@ -508,45 +846,165 @@ where
     })
 }
 
+/// Returns `true` if the contract is executed in read-only mode.
+///
+/// # Note
+///
+/// In read-only mode the contract must not mutate its state.
+/// Currently only the off-chain environment supports read-only mode.
+pub fn is_read_only() -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::is_read_only(instance)
+    })
+}
+
 /// Prints the given contents to the environmental log.
 pub fn println(content: &str) {
     <EnvInstance as OnInstance>::on_instance(|instance| Env::println(instance, content))
 }
 
+/// Records the given debug message.
+///
+/// # Note
+///
+/// Unlike [`println`] the off-chain environment records debug messages
+/// together with the current block number and caller instead of printing
+/// them so that tests can inspect them.
+pub fn debug_message(message: &str) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        Env::debug_message(instance, message)
+    })
+}
+
 /// Built-in efficient cryptographic hash functions.
 pub mod hash {
     use super::*;
 
+    /// The output of a built-in cryptographic hash function.
+    pub trait HashOutput: private::Sealed {
+        /// The output buffer of the hash function, e.g. `[u8; 32]`.
+        type Type: Default + AsRef<[u8]> + AsMut<[u8]>;
+    }
+
+    /// A built-in cryptographic hash function.
+    ///
+    /// # Note
+    ///
+    /// Implemented by the environmental backends for all hash function
+    /// marker types so that [`hash_bytes`] can select the hash function
+    /// by type parameter.
+    pub trait CryptoHash: HashOutput {
+        /// Conducts the hash of the input and puts the result into the output buffer.
+        fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type);
+    }
+
+    mod private {
+        /// Seals the implementation of [`HashOutput`](`super::HashOutput`).
+        pub trait Sealed {}
+    }
+
+    /// Conducts the hash of the given bytes using the hash function `H` and
+    /// puts the result into the output buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ink_core::env::hash::{hash_bytes, Blake2x256, HashOutput};
+    /// let mut output = <Blake2x256 as HashOutput>::Type::default();
+    /// hash_bytes::<Blake2x256>(b"DEAD_BEEF", &mut output);
+    /// ```
+    pub fn hash_bytes<H>(input: &[u8], output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash,
+    {
+        // No need to actually access the environmental instance
+        // if we only call one of its inherent methods.
+        <EnvInstance as Env>::hash_bytes::<H>(input, output)
+    }
+
+    macro_rules! impl_hash_output {
+        ( $(#[$doc:meta])* enum $marker:ident($output_len:literal) ) => {
+            $( #[$doc] )*
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub enum $marker {}
+
+            impl private::Sealed for $marker {}
+
+            impl HashOutput for $marker {
+                type Type = [u8; $output_len];
+            }
+        };
+    }
+    impl_hash_output!(
+        /// The SHA2 256-bit hash function.
+        enum Sha2x256(32)
+    );
+    impl_hash_output!(
+        /// The KECCAK 256-bit hash function.
+        enum Keccak256(32)
+    );
+    impl_hash_output!(
+        /// The BLAKE2 256-bit hash function.
+        enum Blake2x256(32)
+    );
+    impl_hash_output!(
+        /// The BLAKE2 128-bit hash function.
+        enum Blake2x128(16)
+    );
+    impl_hash_output!(
+        /// The TWOX 64-bit hash function.
+        enum Twox64(8)
+    );
+    impl_hash_output!(
+        /// The TWOX 128-bit hash function.
+        enum Twox128(16)
+    );
+    impl_hash_output!(
+        /// The TWOX 256-bit hash function.
+        enum Twox256(32)
+    );
+
     macro_rules! impl_hash_fn {
-        ( $(#[$doc:meta])* fn $name:ident($output_len:literal) ) => {
-            paste::item! {
-                $( #[$doc] )*
-                pub fn $name(input: &[u8], output: &mut [u8; $output_len]) {
-                    // No need to actually access the environmental instance
-                    // if we only call one of its inherent methods.
-                    <EnvInstance as Env>::[<hash_ $name>](input, output)
-                }
+        ( $(#[$doc:meta])* fn $name:ident($marker:ident) ) => {
+            $( #[$doc] )*
+            pub fn $name(input: &[u8], output: &mut <$marker as HashOutput>::Type) {
+                hash_bytes::<$marker>(input, output)
             }
         };
     }
     impl_hash_fn!(
         /// Conducts the SHA2 256-bit hash of the given bytes and
         /// puts the result into the output buffer.
-        fn sha2_256(32)
+        fn sha2_256(Sha2x256)
     );
     impl_hash_fn!(
         /// Conducts the KECCAK 256-bit hash of the given bytes and
         /// puts the result into the output buffer.
-        fn keccak_256(32)
+        fn keccak_256(Keccak256)
     );
     impl_hash_fn!(
         /// Conducts the BLAKE2 256-bit hash of the given bytes and
         /// puts the result into the output buffer.
-        fn blake2_256(32)
+        fn blake2_256(Blake2x256)
     );
     impl_hash_fn!(
         /// Conducts the BLAKE2 128-bit hash of the given bytes and
         /// puts the result into the output buffer.
-        fn blake2_128(16)
+        fn blake2_128(Blake2x128)
+    );
+    impl_hash_fn!(
+        /// Conducts the TWOX 64-bit hash of the given bytes and
+        /// puts the result into the output buffer.
+        fn twox_64(Twox64)
+    );
+    impl_hash_fn!(
+        /// Conducts the TWOX 128-bit hash of the given bytes and
+        /// puts the result into the output buffer.
+        fn twox_128(Twox128)
+    );
+    impl_hash_fn!(
+        /// Conducts the TWOX 256-bit hash of the given bytes and
+        /// puts the result into the output buffer.
+        fn twox_256(Twox256)
     );
 }
@@ -18,6 +18,10 @@ use crate::env::{
         CallParams,
         CreateParams,
     },
+    hash::{
+        CryptoHash,
+        HashOutput,
+    },
     EnvTypes,
     Result,
     Topics,
@@ -25,6 +29,7 @@ use crate::env::{
 use ink_primitives::Key;
 
 /// The flags to indicate further information about the end of a contract execution.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ReturnFlags {
     value: u32,
 }
@@ -45,16 +50,50 @@ impl ReturnFlags {
         self
     }
 
+    /// Returns `true` if the execution is going to be reverted.
+    pub fn is_reverted(self) -> bool {
+        self.value & 1 != 0
+    }
+
     /// Returns the underlying `u32` representation.
     pub(crate) fn into_u32(self) -> u32 {
         self.value
     }
 }
 
+/// The rent status of a contract based on its storage footprint.
+pub struct RentStatus<T>
+where
+    T: EnvTypes,
+{
+    /// The maximum rent the contract is allowed to pay, i.e. its rent allowance.
+    pub max_rent: T::Balance,
+    /// The rent the contract currently pays per block for its storage.
+    pub current_rent: T::Balance,
+    /// The total size of the contract storage in bytes.
+    pub storage_size: u32,
+    /// `true` if the balance of the contract does not cover the current rent
+    /// on top of the tombstone deposit.
+    pub below_deposit: bool,
+}
+
 /// Environmental contract functionality that does not require `EnvTypes`.
 pub trait Env {
     /// Writes the value to the contract storage under the given key.
-    fn set_contract_storage<V>(&mut self, key: &Key, value: &V)
+    ///
+    /// Returns the size of the previously stored value in bytes or `None`
+    /// if there was no value stored under the key.
+    fn set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Option<u32>
+    where
+        V: scale::Encode;
+
+    /// Writes the value to the contract storage under the given key unless
+    /// the contract storage is out of space.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::try_set_contract_storage`]
+    fn try_set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Result<Option<u32>>
     where
         V: scale::Encode;
 
@@ -67,8 +106,14 @@ pub trait Env {
     where
         R: scale::Decode;
 
+    /// Returns the size of the value stored under the given key in bytes if any.
+    fn contains_contract_storage(&mut self, key: &Key) -> Option<u32>;
+
     /// Clears the contract's storage key entry.
-    fn clear_contract_storage(&mut self, key: &Key);
+    ///
+    /// Returns the size of the previously stored value in bytes or `None`
+    /// if there was no value stored under the key.
+    fn clear_contract_storage(&mut self, key: &Key) -> Option<u32>;
 
     /// Returns the execution input to the executed contract and decodes it as `T`.
     ///
@@ -97,6 +142,15 @@ pub trait Env {
     where
         T: scale::Decode;
 
+    /// Calls `f` with the raw input of the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::with_input`]
+    fn with_input<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R;
+
     /// Returns the value back to the caller of the executed contract.
     ///
     /// # Note
@@ -110,24 +164,41 @@ pub trait Env {
     where
         R: scale::Encode;
 
-    /// Prints the given contents to the console log.
-    fn println(&mut self, content: &str);
+    /// Returns the already encoded value back to the caller of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// Calling this method will end contract execution immediately.
+    /// Unlike [`Env::return_value`] the given bytes are returned verbatim
+    /// without being encoded again.
+    fn return_raw(&mut self, flags: ReturnFlags, return_value: &[u8]) -> !;
 
-    /// Conducts the SHA2 256-bit hash of the input
-    /// puts the result into the output buffer.
-    fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]);
+    /// Returns `true` if the contract is executed in read-only mode.
+    ///
+    /// # Note
+    ///
+    /// In read-only mode the contract must not mutate its state.
+    fn is_read_only(&mut self) -> bool;
 
-    /// Conducts the KECCAK 256-bit hash of the input
-    /// puts the result into the output buffer.
-    fn hash_keccak_256(input: &[u8], output: &mut [u8; 32]);
+    /// Prints the given contents to the console log.
+    fn println(&mut self, content: &str);
 
-    /// Conducts the BLAKE2 256-bit hash of the input
-    /// puts the result into the output buffer.
-    fn hash_blake2_256(input: &[u8], output: &mut [u8; 32]);
+    /// Records the given debug message.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::debug_message`]
+    fn debug_message(&mut self, message: &str);
 
-    /// Conducts the BLAKE2 128-bit hash of the input
-    /// puts the result into the output buffer.
-    fn hash_blake2_128(input: &[u8], output: &mut [u8; 16]);
+    /// Conducts the crytographic hash of the input using the hash function `H`
+    /// and puts the result into the output buffer.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::hash::hash_bytes`]
+    fn hash_bytes<H>(input: &[u8], output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash;
 
     /// Calls the chain extension with the given ID and inputs.
     ///
@@ -139,11 +210,31 @@ pub trait Env {
     /// - If the inputs had an unexpected encoding.
     /// - If the output could not be properly decoded.
     /// - If some extension specific condition has not been met.
-    #[cfg(feature = "ink-unstable-chain-extensions")]
+    #[cfg(any(feature = "std", feature = "ink-unstable-chain-extensions"))]
     fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
     where
         I: scale::Codec + 'static,
         O: scale::Codec + 'static;
+
+    /// Calls the chain extension with the given ID and inputs.
+    ///
+    /// Returns the status code of the chain extension alongside the output
+    /// of the specified type.
+    ///
+    /// # Errors
+    ///
+    /// - If the chain extension with the given ID does not exist.
+    /// - If the inputs had an unexpected encoding.
+    /// - If the output could not be properly decoded.
+    #[cfg(any(feature = "std", feature = "ink-unstable-chain-extensions"))]
+    fn call_chain_extension_with_status<I, O>(
+        &mut self,
+        func_id: u32,
+        input: &I,
+    ) -> Result<(u32, O)>
+    where
+        I: scale::Codec + 'static,
+        O: scale::Codec + 'static;
 }
 
 /// Environmental contract functionality.
@@ -155,6 +246,20 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::caller`]
     fn caller<T: EnvTypes>(&mut self) -> Result<T::AccountId>;
 
+    /// Returns `true` if the caller of the executed contract is the origin.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::caller_is_origin`]
+    fn caller_is_origin<T: EnvTypes>(&mut self) -> Result<bool>;
+
+    /// Returns the balance of the caller of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::caller_balance`]
+    fn caller_balance<T: EnvTypes>(&mut self) -> Result<T::Balance>;
+
     /// Returns the transferred balance for the contract execution.
     ///
     /// # Note
@@ -169,6 +274,17 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::gas_price`]
     fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance>;
 
+    /// Returns the price for the specified two-dimensional weight.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::weight_to_fee_v2`]
+    fn weight_to_fee_v2<T: EnvTypes>(
+        &mut self,
+        ref_time: u64,
+        proof_size: u64,
+    ) -> Result<T::Balance>;
+
     /// Returns the amount of gas left for the contract execution.
     ///
     /// # Note
@@ -176,6 +292,13 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::gas_left`]
     fn gas_left<T: EnvTypes>(&mut self) -> Result<T::Balance>;
 
+    /// Returns the nonce of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::account_nonce`]
+    fn account_nonce<T: EnvTypes>(&mut self) -> Result<u64>;
+
     /// Returns the timestamp of the current block.
     ///
     /// # Note
@@ -190,6 +313,13 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::account_id`]
     fn account_id<T: EnvTypes>(&mut self) -> Result<T::AccountId>;
 
+    /// Returns the code hash of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::own_code_hash`]
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash>;
+
     /// Returns the balance of the executed contract.
     ///
     /// # Note
@@ -225,6 +355,13 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::tombstone_deposit`]
     fn tombstone_deposit<T: EnvTypes>(&mut self) -> Result<T::Balance>;
 
+    /// Returns the rent status of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::rent_status`]
+    fn rent_status<T: EnvTypes>(&mut self) -> Result<RentStatus<T>>;
+
     /// Emits an event with the given event data.
     ///
     /// # Note
@@ -298,6 +435,15 @@ pub trait TypedEnv: Env {
     ) where
         T: EnvTypes;
 
+    /// Replaces the code of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::set_code_hash`]
+    fn set_code_hash<T>(&mut self, code_hash: T::Hash) -> Result<()>
+    where
+        T: EnvTypes;
+
     /// Terminates a smart contract.
     ///
     /// # Note
@@ -316,6 +462,43 @@ pub trait TypedEnv: Env {
     where
         T: EnvTypes;
 
+    /// Transfers value from the contract to the destination account ID
+    /// unless this would leave the destination with dust.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::transfer_keep_alive`]
+    fn transfer_keep_alive<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes;
+
+    /// Transfers up to the given value from the contract to the destination
+    /// account ID and returns the actually transferred value.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::transfer_saturating`]
+    fn transfer_saturating<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<T::Balance>
+    where
+        T: EnvTypes;
+
+    /// Dispatches the runtime call on behalf of the contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_runtime`]
+    fn call_runtime<C>(&mut self, call: &C) -> Result<()>
+    where
+        C: scale::Encode;
+
     /// Returns a random hash seed.
     ///
     /// # Note
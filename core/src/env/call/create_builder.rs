@@ -30,6 +30,7 @@ use crate::{
     },
 };
 use core::marker::PhantomData;
+use ink_prelude::vec::Vec;
 
 /// Contracts that can be contructed from an `AccountId`.
 ///
@@ -59,16 +60,12 @@ where
     endowment: E::Balance,
     /// The input data for the instantation.
     exec_input: ExecutionInput<Args>,
+    /// The salt for deriving the account ID of the instantiated contract.
+    salt: Vec<u8>,
     /// The type of the instantiated contract.
     return_type: ReturnType<R>,
 }
 
-#[cfg(
-    // We do not currently support cross-contract instantiation in the off-chain
-    // environment so we do not have to provide these getters in case of
-    // off-chain environment compilation.
-    all(not(feature = "std"), target_arch = "wasm32")
-)]
 impl<E, Args, R> CreateParams<E, Args, R>
 where
     E: EnvTypes,
@@ -81,6 +78,8 @@ where
 
     /// The gas limit for the contract instantiation.
     #[inline]
+    // The off-chain environment does not meter the instantiation.
+    #[cfg_attr(feature = "std", allow(dead_code))]
     pub(crate) fn gas_limit(&self) -> u64 {
         self.gas_limit
     }
@@ -96,6 +95,14 @@ where
     pub(crate) fn exec_input(&self) -> &ExecutionInput<Args> {
         &self.exec_input
     }
+
+    /// The salt for deriving the account ID of the instantiated contract.
+    #[inline]
+    // The on-chain environment does not yet accept a salt.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn salt_bytes(&self) -> &[u8] {
+        &self.salt
+    }
 }
 
 impl<E, Args, R> CreateParams<E, Args, R>
@@ -121,6 +128,7 @@ where
     gas_limit: GasLimit,
     endowment: Endowment,
     exec_input: Args,
+    salt: Vec<u8>,
     return_type: ReturnType<R>,
 }
 
@@ -167,8 +175,8 @@ where
 ///     .unwrap();
 /// ```
 ///
-/// **Note:** The shown example panics because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
+/// **Note:** The shown example panics because the used code hash has not been
+///           uploaded to the off-chain testing environment. However, this code
 ///           should work fine in on-chain environments.
 #[allow(clippy::type_complexity)]
 pub fn build_create<E, R>() -> CreateBuilder<
@@ -189,10 +197,31 @@ where
         gas_limit: Default::default(),
         endowment: Default::default(),
         exec_input: Default::default(),
+        salt: Vec::new(),
         return_type: Default::default(),
     }
 }
 
+impl<E, CodeHash, GasLimit, Endowment, Args, R>
+    CreateBuilder<E, CodeHash, GasLimit, Endowment, Args, R>
+where
+    E: EnvTypes,
+{
+    /// Sets the salt used to derive the account ID of the instantiated contract.
+    ///
+    /// # Note
+    ///
+    /// Instantiating the same code with the same salt by the same creator
+    /// always yields the same account ID. Without a salt the account ID
+    /// is derived from the nonce of the creator instead.
+    #[inline]
+    pub fn salt_bytes(mut self, salt: &[u8]) -> Self {
+        self.salt.clear();
+        self.salt.extend_from_slice(salt);
+        self
+    }
+}
+
 impl<E, GasLimit, Endowment, Args, R>
     CreateBuilder<E, Unset<E::Hash>, GasLimit, Endowment, Args, R>
 where
@@ -210,6 +239,7 @@ where
             gas_limit: self.gas_limit,
             endowment: self.endowment,
             exec_input: self.exec_input,
+            salt: self.salt,
             return_type: self.return_type,
         }
     }
@@ -232,6 +262,7 @@ where
             gas_limit: Set(gas_limit),
             endowment: self.endowment,
             exec_input: self.exec_input,
+            salt: self.salt,
             return_type: self.return_type,
         }
     }
@@ -254,6 +285,7 @@ where
             gas_limit: self.gas_limit,
             endowment: Set(endowment),
             exec_input: self.exec_input,
+            salt: self.salt,
             return_type: self.return_type,
         }
     }
@@ -284,6 +316,7 @@ where
             gas_limit: self.gas_limit,
             endowment: self.endowment,
             exec_input: Set(exec_input),
+            salt: self.salt,
             return_type: self.return_type,
         }
     }
@@ -310,6 +343,7 @@ where
             gas_limit: self.gas_limit.unwrap_or_else(|| 0),
             endowment: self.endowment.value(),
             exec_input: self.exec_input.value(),
+            salt: self.salt,
             return_type: self.return_type,
         }
     }
@@ -23,6 +23,15 @@ pub struct ExecutionInput<Args> {
     args: Args,
 }
 
+impl<Args> ExecutionInput<Args> {
+    /// Returns the selector of the smart contract execution.
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn selector(&self) -> Selector {
+        self.selector
+    }
+}
+
 impl ExecutionInput<EmptyArgumentList> {
     /// Creates a new execution input with the given selector.
     #[inline]
@@ -55,6 +55,22 @@ impl CallData {
     }
 }
 
+/// Encodes the call data for the given selector and arguments.
+///
+/// # Note
+///
+/// Multiple arguments are passed as a tuple. The resulting bytes are the
+/// selector followed by the SCALE encoded arguments which is exactly the
+/// layout that the generated ink! dispatch expects.
+pub fn encode_call<A>(selector: Selector, args: &A) -> Vec<u8>
+where
+    A: scale::Encode,
+{
+    let mut call_data = CallData::new(selector);
+    call_data.push_arg(args);
+    call_data.bytes
+}
+
 impl scale::Encode for CallData {
     fn size_hint(&self) -> usize {
         self.bytes.len()
@@ -13,41 +13,22 @@
 // limitations under the License.
 
 use super::OffChainError;
-use crate::env::Result;
+use crate::env::{
+    EnvError,
+    Result,
+};
 use std::collections::HashMap;
 
 type FuncId = u32;
 
-/// Types implementing this trait can be used as chain extensions.
-///
-/// This trait is only useful for testing contract via the off-chain environment.
-pub trait ChainExtension {
-    /// The expected input type.
-    ///
-    /// # Note
-    ///
-    /// This can be a tuple to expect multiple input types.
-    type Input: scale::Codec;
-    /// The expected output type.
-    type Output: scale::Codec;
-
-    /// The static function ID of the chain extension.
-    ///
-    /// # Note
-    ///
-    /// This is expected to return a constant value.
-    fn func_id(&self) -> u32;
-
-    /// Calls the chain extension with the given input.
-    fn call(&mut self, input: &Self::Input) -> Result<Self::Output>;
-}
-
 /// A raw chain extension function.
 ///
 /// This is mostly a wrapper closure around the real chain extension function
 /// that handles marshalling of types between their encoded and decoded
 /// representations.
-type ChainExtensionFn = Box<dyn FnMut(Vec<u8>) -> Result<Vec<u8>>>;
+///
+/// Returns the status code of the chain extension alongside its output.
+type ChainExtensionFn = Box<dyn FnMut(Vec<u8>) -> Result<(u32, Vec<u8>)>>;
 
 /// Runtime call handler.
 ///
@@ -72,21 +53,38 @@ impl ChainExtensionHandler {
         self.registered.clear()
     }
 
-    /// Register a new chain extension.
-    pub fn register<I, O>(
-        &mut self,
-        mut extension: Box<dyn ChainExtension<Input = I, Output = O>>,
-    ) where
+    /// Register a new chain extension for the given function ID.
+    ///
+    /// The chain extension always returns the success status code `0`.
+    ///
+    /// Replaces any chain extension previously registered for the same ID.
+    pub fn register<I, O, F>(&mut self, func_id: FuncId, mut extension: F)
+    where
         I: scale::Codec + 'static,
         O: scale::Codec + 'static,
+        F: FnMut(I) -> Result<O> + 'static,
+    {
+        self.register_with_status(func_id, move |input: I| {
+            extension(input).map(|output| (0, output))
+        })
+    }
+
+    /// Register a new chain extension returning a status code alongside its
+    /// output for the given function ID.
+    ///
+    /// Replaces any chain extension previously registered for the same ID.
+    pub fn register_with_status<I, O, F>(&mut self, func_id: FuncId, mut extension: F)
+    where
+        I: scale::Codec + 'static,
+        O: scale::Codec + 'static,
+        F: FnMut(I) -> Result<(u32, O)> + 'static,
     {
-        let func_id = extension.func_id();
         self.registered.insert(
             func_id,
             Box::new(move |encoded_input: Vec<u8>| {
                 let decoded_input = scale::Decode::decode(&mut &encoded_input[..])?;
-                let decoded_output = extension.call(&decoded_input)?;
-                Ok(scale::Encode::encode(&decoded_output))
+                let (status, decoded_output) = extension(decoded_input)?;
+                Ok((status, scale::Encode::encode(&decoded_output)))
             }),
         );
     }
@@ -94,7 +92,30 @@ impl ChainExtensionHandler {
     /// Evaluates the chain extension with the given parameters.
     ///
     /// Upon success returns the values returned by the evaluated chain extension.
+    ///
+    /// # Errors
+    ///
+    /// If the chain extension returned a non-zero status code.
     pub fn eval<I, O>(&mut self, func_id: FuncId, input: &I) -> Result<O>
+    where
+        I: scale::Codec + 'static,
+        O: scale::Codec + 'static,
+    {
+        match self.eval_with_status(func_id, input)? {
+            (0, output) => Ok(output),
+            _ => Err(EnvError::UnknownError),
+        }
+    }
+
+    /// Evaluates the chain extension with the given parameters.
+    ///
+    /// Upon success returns the status code and the values returned by the
+    /// evaluated chain extension.
+    pub fn eval_with_status<I, O>(
+        &mut self,
+        func_id: FuncId,
+        input: &I,
+    ) -> Result<(u32, O)>
     where
         I: scale::Codec + 'static,
         O: scale::Codec + 'static,
@@ -103,11 +124,12 @@ impl ChainExtensionHandler {
         match self.registered.entry(func_id) {
             Entry::Occupied(mut occupied) => {
                 let encoded_input = scale::Encode::encode(input);
-                let encoded_output = occupied.get_mut()(encoded_input)?;
-                scale::Decode::decode(&mut &encoded_output[..]).map_err(Into::into)
+                let (status, encoded_output) = occupied.get_mut()(encoded_input)?;
+                let output = scale::Decode::decode(&mut &encoded_output[..])?;
+                Ok((status, output))
             }
             Entry::Vacant(_vacant) => {
-                Err(OffChainError::UnregisteredChainExtension.into())
+                Err(OffChainError::UnregisteredChainExtension(func_id).into())
             }
         }
     }
@@ -19,14 +19,21 @@ use super::{
     },
     OffAccountId,
     OffBalance,
+    OffHash,
 };
 use crate::env::{
     EnvError,
     EnvTypes,
 };
-use core::cell::Cell;
+use core::cell::{
+    Cell,
+    RefCell,
+};
 use derive_more::From;
-use ink_prelude::collections::BTreeMap;
+use ink_prelude::collections::{
+    btree_map,
+    BTreeMap,
+};
 use ink_primitives::Key;
 
 /// Errors encountered upon interacting with the accounts database.
@@ -36,12 +43,20 @@ pub enum AccountError {
     #[from(ignore)]
     UnexpectedUserAccount,
     #[from(ignore)]
+    UnexpectedTombstone,
+    #[from(ignore)]
+    ExpectedTombstone,
+    #[from(ignore)]
     NoAccountForId(OffAccountId),
 }
 
 impl From<AccountError> for EnvError {
     fn from(account_error: AccountError) -> Self {
-        EnvError::OffChain(OffChainError::Account(account_error))
+        match account_error {
+            AccountError::NoAccountForId(_) => EnvError::AccountNotFound,
+            AccountError::UnexpectedUserAccount => EnvError::NotAContract,
+            account_error => EnvError::OffChain(OffChainError::Account(account_error)),
+        }
     }
 }
 
@@ -65,9 +80,12 @@ impl From<scale::Error> for AccountError {
 pub type Result<T> = core::result::Result<T, AccountError>;
 
 /// The database that stores all accounts.
+#[derive(Clone)]
 pub struct AccountsDb {
     /// The mapping from account ID to an actual account.
     accounts: BTreeMap<OffAccountId, Account>,
+    /// The number of accounts created via [`AccountsDb::create_user_account`].
+    created_accounts: u64,
 }
 
 impl AccountsDb {
@@ -75,12 +93,39 @@ impl AccountsDb {
     pub fn new() -> Self {
         Self {
             accounts: BTreeMap::new(),
+            created_accounts: 0,
         }
     }
 
     /// Resets the account DB to uninitialized state.
     pub fn reset(&mut self) {
-        self.accounts.clear()
+        self.accounts.clear();
+        self.created_accounts = 0;
+    }
+
+    /// Creates a new user account with the initial balance and returns its
+    /// account ID.
+    ///
+    /// # Note
+    ///
+    /// The account IDs are derived from an internal counter so that they
+    /// are deterministic across test runs and distinct from each other.
+    /// Account IDs that are already in use are skipped.
+    pub fn create_user_account<T>(&mut self, initial_balance: T::Balance) -> T::AccountId
+    where
+        T: EnvTypes,
+        <T as EnvTypes>::AccountId: From<[u8; 32]>,
+    {
+        loop {
+            self.created_accounts += 1;
+            let mut account_id = [0x00; 32];
+            account_id[24..].copy_from_slice(&self.created_accounts.to_be_bytes());
+            let account_id = T::AccountId::from(account_id);
+            if self.get_account::<T>(&account_id).is_none() {
+                self.add_user_account::<T>(account_id.clone(), initial_balance);
+                return account_id
+            }
+        }
     }
 
     /// Returns the account at the given account ID or creates it.
@@ -102,6 +147,14 @@ impl AccountsDb {
         }
     }
 
+    /// Removes the account for the given account ID and returns it if any.
+    pub fn remove_account<T>(&mut self, at: &T::AccountId) -> Option<Account>
+    where
+        T: EnvTypes,
+    {
+        self.accounts.remove(&OffAccountId::new(at))
+    }
+
     /// Returns the account for the given account ID if any.
     pub fn get_account<T>(&self, at: &T::AccountId) -> Option<&Account>
     where
@@ -118,6 +171,11 @@ impl AccountsDb {
         self.accounts.get_mut(&OffAccountId::new(at))
     }
 
+    /// Returns an iterator over all accounts ordered by their encoded account IDs.
+    pub fn iter(&self) -> impl Iterator<Item = (&OffAccountId, &Account)> {
+        self.accounts.iter()
+    }
+
     /// Returns the account for the given off-account ID if any.
     pub fn get_account_off<'a>(&'a self, at: &OffAccountId) -> Option<&'a Account> {
         self.accounts.get(at)
@@ -141,6 +199,7 @@ impl AccountsDb {
             Account {
                 balance: OffBalance::new(&initial_balance),
                 kind: AccountKind::User,
+                nonce: 0,
             },
         );
     }
@@ -159,17 +218,22 @@ impl AccountsDb {
             Account {
                 balance: OffBalance::new(&initial_balance),
                 kind: AccountKind::Contract(ContractAccount::new::<T>(rent_allowance)),
+                nonce: 0,
             },
         );
     }
 }
 
 /// An account within the chain.
+#[derive(Clone)]
 pub struct Account {
     /// The balance of the account.
     balance: OffBalance,
     /// The kind of the account.
     kind: AccountKind,
+    /// The number of contract instantiations and transfers originating from
+    /// the account.
+    nonce: u64,
 }
 
 impl Account {
@@ -189,6 +253,25 @@ impl Account {
         self.balance.assign(&new_balance).map_err(Into::into)
     }
 
+    /// Returns `true` if the account is a contract account.
+    ///
+    /// # Note
+    ///
+    /// Evicted contracts leave behind a tombstone which is not a contract.
+    pub fn is_contract(&self) -> bool {
+        matches!(self.kind, AccountKind::Contract(_))
+    }
+
+    /// Returns the current nonce of the account.
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// Increments the nonce of the account by one.
+    pub fn inc_nonce(&mut self) {
+        self.nonce += 1;
+    }
+
     /// Returns the contract account or an error if it is a user account.
     fn contract_or_err(&self) -> Result<&ContractAccount> {
         match &self.kind {
@@ -196,6 +279,9 @@ impl Account {
                 Err(AccountError::UnexpectedUserAccount).map_err(Into::into)
             }
             AccountKind::Contract(contract_account) => Ok(contract_account),
+            AccountKind::Tombstone(_) => {
+                Err(AccountError::UnexpectedTombstone).map_err(Into::into)
+            }
         }
     }
 
@@ -206,9 +292,71 @@ impl Account {
                 Err(AccountError::UnexpectedUserAccount).map_err(Into::into)
             }
             AccountKind::Contract(contract_account) => Ok(contract_account),
+            AccountKind::Tombstone(_) => {
+                Err(AccountError::UnexpectedTombstone).map_err(Into::into)
+            }
         }
     }
 
+    /// Returns the tombstone hash of the evicted contract account or an error.
+    pub fn tombstone_hash(&self) -> Result<[u8; 32]> {
+        match &self.kind {
+            AccountKind::Tombstone(tombstone) => Ok(tombstone.hash),
+            _ => Err(AccountError::ExpectedTombstone),
+        }
+    }
+
+    /// Evicts the contract account leaving behind a tombstone with the given hash.
+    pub fn evict(&mut self, tombstone_hash: [u8; 32]) -> Result<()> {
+        self.contract_or_err()?;
+        self.kind = AccountKind::Tombstone(TombstoneAccount {
+            hash: tombstone_hash,
+        });
+        Ok(())
+    }
+
+    /// Revives the tombstone account as contract account with the given storage.
+    pub fn revive<T>(
+        &mut self,
+        code_hash: T::Hash,
+        rent_allowance: T::Balance,
+        storage: ContractStorage,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.tombstone_hash()?;
+        let mut contract = ContractAccount::new::<T>(rent_allowance);
+        contract.code_hash = OffHash::new(&code_hash);
+        contract.storage = storage;
+        self.kind = AccountKind::Contract(contract);
+        Ok(())
+    }
+
+    /// Returns the code hash of the contract account or an error.
+    pub fn code_hash<T>(&self) -> Result<T::Hash>
+    where
+        T: EnvTypes,
+    {
+        self.contract_or_err()
+            .and_then(|contract| contract.code_hash.decode().map_err(Into::into))
+    }
+
+    /// Sets the code hash of the contract account or returns an error.
+    pub fn set_code_hash<T>(&mut self, code_hash: T::Hash) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.contract_or_err_mut().map(|contract| {
+            contract.code_hash = OffHash::new(&code_hash);
+        })
+    }
+
+    /// Returns the storage of the contract account or an error.
+    pub fn storage(&self) -> Result<&ContractStorage> {
+        self.contract_or_err().map(|contract| &contract.storage)
+    }
+
     /// Returns the rent allowance of the contract account or an error.
     pub fn rent_allowance<T>(&self) -> Result<T::Balance>
     where
@@ -232,7 +380,9 @@ impl Account {
     }
 
     /// Sets the contract storage of key to the new value.
-    pub fn set_storage<T>(&mut self, at: Key, new_value: &T) -> Result<()>
+    ///
+    /// Returns the size of the overwritten value if any.
+    pub fn set_storage<T>(&mut self, at: Key, new_value: &T) -> Result<Option<u32>>
     where
         T: scale::Encode,
     {
@@ -241,11 +391,30 @@ impl Account {
     }
 
     /// Clears the contract storage at key.
-    pub fn clear_storage(&mut self, at: Key) -> Result<()> {
+    ///
+    /// Returns the size of the previously stored value if any.
+    pub fn clear_storage(&mut self, at: Key) -> Result<Option<u32>> {
         self.contract_or_err_mut()
             .map(|contract| contract.storage.clear_storage(at))
     }
 
+    /// Returns the size of the value stored in the contract storage at the given key if any.
+    pub fn contains_storage(&self, at: Key) -> Result<Option<u32>> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.contains_storage(at))
+    }
+
+    /// Returns the size of the value stored in the contract storage at the given key if any.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Account::contains_storage`] this is no access to the contract
+    /// storage and thus neither counted nor logged.
+    pub fn storage_entry_size(&self, at: Key) -> Result<Option<u32>> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.entry_size(at))
+    }
+
     /// Returns the value stored in the contract storage at the given key.
     pub fn get_storage<T>(&self, at: Key) -> Result<Option<T>>
     where
@@ -255,6 +424,40 @@ impl Account {
             .and_then(|contract| contract.storage.get_storage::<T>(at))
     }
 
+    /// Removes all entries from the contract storage.
+    pub fn clear_all_storage(&mut self) -> Result<()> {
+        self.contract_or_err_mut()
+            .map(|contract| contract.storage.clear_all_storage())
+    }
+
+    /// Enables the storage access log of the contract account.
+    pub fn enable_storage_access_log(&mut self) -> Result<()> {
+        self.contract_or_err_mut()
+            .map(|contract| contract.storage.enable_access_log())
+    }
+
+    /// Returns the logged storage accesses of the contract account in order.
+    pub fn storage_access_log(&self) -> Result<Vec<StorageAccess>> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.access_log())
+    }
+
+    /// Drains the logged storage accesses of the contract account in order.
+    pub fn drain_storage_access_log(&mut self) -> Result<Vec<StorageAccess>> {
+        self.contract_or_err_mut()
+            .map(|contract| contract.storage.drain_access_log())
+    }
+
+    /// Returns the keys of all entries of the contract storage in order.
+    pub fn storage_keys(&self) -> Result<Vec<Key>> {
+        self.contract_or_err().map(|contract| contract.storage.keys())
+    }
+
+    /// Returns the total size of the encoded values of the contract storage in bytes.
+    pub fn storage_size(&self) -> Result<usize> {
+        self.contract_or_err().map(|contract| contract.storage.size())
+    }
+
     /// Returns the total number of reads and write from and to the contract's storage.
     pub fn get_storage_rw(&self) -> Result<(usize, usize)> {
         self.contract_or_err().map(|contract| contract.get_rw())
@@ -263,16 +466,34 @@ impl Account {
 
 /// The kind of the account.
 ///
-/// Can be either a user account or a (more complicated) contract account.
+/// Can be either a user account, a (more complicated) contract account
+/// or the tombstone of an evicted contract account.
+#[derive(Clone)]
 pub enum AccountKind {
     User,
     Contract(ContractAccount),
+    Tombstone(TombstoneAccount),
+}
+
+/// The remains of an evicted contract account.
+#[derive(Clone)]
+pub struct TombstoneAccount {
+    /// The hash over the storage and code hash of the evicted contract.
+    hash: [u8; 32],
 }
 
 /// Extraneous fields for contract accounts.
+#[derive(Clone)]
 pub struct ContractAccount {
     /// The contract's rent allowance.
     rent_allowance: OffBalance,
+    /// The contract's code hash.
+    ///
+    /// # Note
+    ///
+    /// Stays uninitialized for contracts that have not been instantiated
+    /// or restored with a known code hash.
+    code_hash: OffHash,
     /// The contract storage.
     pub storage: ContractStorage,
 }
@@ -285,6 +506,7 @@ impl ContractAccount {
     {
         Self {
             rent_allowance: OffBalance::new(&rent_allowance),
+            code_hash: OffHash::uninitialized(),
             storage: ContractStorage::new(),
         }
     }
@@ -295,7 +517,28 @@ impl ContractAccount {
     }
 }
 
+/// The kind of an access to the contract storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StorageAccessKind {
+    /// The storage entry has been read.
+    Read,
+    /// The storage entry has been written.
+    Write,
+    /// The storage entry has been cleared.
+    Clear,
+}
+
+/// A logged access to the contract storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StorageAccess {
+    /// The key of the accessed storage entry.
+    pub key: Key,
+    /// The kind of the access.
+    pub kind: StorageAccessKind,
+}
+
 /// The storage of a contract instance.
+#[derive(Clone)]
 pub struct ContractStorage {
     /// The entries within the contract storage.
     entries: BTreeMap<Key, Vec<u8>>,
@@ -303,6 +546,12 @@ pub struct ContractStorage {
     count_reads: Cell<usize>,
     /// The total number of writes to the storage.
     count_writes: usize,
+    /// The log of all accesses to the storage in order.
+    ///
+    /// # Note
+    ///
+    /// Accesses are only logged if this is `Some`.
+    access_log: RefCell<Option<Vec<StorageAccess>>>,
 }
 
 impl ContractStorage {
@@ -312,6 +561,7 @@ impl ContractStorage {
             entries: BTreeMap::new(),
             count_reads: Cell::new(0),
             count_writes: 0,
+            access_log: RefCell::new(None),
         }
     }
 
@@ -320,12 +570,39 @@ impl ContractStorage {
         (self.count_reads.get(), self.count_writes)
     }
 
+    /// Enables logging of all accesses to the storage.
+    pub fn enable_access_log(&mut self) {
+        self.access_log.get_mut().get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the logged accesses to the storage in order.
+    pub fn access_log(&self) -> Vec<StorageAccess> {
+        self.access_log.borrow().clone().unwrap_or_default()
+    }
+
+    /// Drains the logged accesses to the storage in order.
+    pub fn drain_access_log(&mut self) -> Vec<StorageAccess> {
+        self.access_log
+            .get_mut()
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Logs the access to the storage entry at the key if logging is enabled.
+    fn log_access(&self, key: Key, kind: StorageAccessKind) {
+        if let Some(access_log) = self.access_log.borrow_mut().as_mut() {
+            access_log.push(StorageAccess { key, kind });
+        }
+    }
+
     /// Returns the decoded storage at the key if any.
     pub fn get_storage<T>(&self, at: Key) -> Result<Option<T>>
     where
         T: scale::Decode,
     {
         self.count_reads.set(self.count_reads.get() + 1);
+        self.log_access(at, StorageAccessKind::Read);
         self.entries
             .get(&at)
             .map(|encoded| T::decode(&mut &encoded[..]))
@@ -333,18 +610,72 @@ impl ContractStorage {
             .map_err(Into::into)
     }
 
+    /// Returns the size of the encoded value at the key if any.
+    pub fn contains_storage(&self, at: Key) -> Option<u32> {
+        self.count_reads.set(self.count_reads.get() + 1);
+        self.log_access(at, StorageAccessKind::Read);
+        self.entries.get(&at).map(|encoded| encoded.len() as u32)
+    }
+
+    /// Returns the size of the encoded value at the key if any without
+    /// counting or logging an access.
+    pub fn entry_size(&self, at: Key) -> Option<u32> {
+        self.entries.get(&at).map(|encoded| encoded.len() as u32)
+    }
+
     /// Writes the encoded value into the contract storage at the given key.
-    pub fn set_storage<T>(&mut self, at: Key, new_value: &T)
+    ///
+    /// Returns the size of the overwritten value if any.
+    pub fn set_storage<T>(&mut self, at: Key, new_value: &T) -> Option<u32>
     where
         T: scale::Encode,
     {
         self.count_writes += 1;
-        self.entries.insert(at, new_value.encode());
+        self.log_access(at, StorageAccessKind::Write);
+        self.entries
+            .insert(at, new_value.encode())
+            .map(|value| value.len() as u32)
     }
 
     /// Removes the value from storage entries at the given key.
-    pub fn clear_storage(&mut self, at: Key) {
+    ///
+    /// Returns the size of the removed value if any.
+    pub fn clear_storage(&mut self, at: Key) -> Option<u32> {
         self.count_writes += 1;
-        self.entries.remove(&at);
+        self.log_access(at, StorageAccessKind::Clear);
+        self.entries.remove(&at).map(|value| value.len() as u32)
+    }
+
+    /// Removes all entries from the contract storage.
+    pub fn clear_all_storage(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the keys of all entries of the contract storage in order.
+    pub fn keys(&self) -> Vec<Key> {
+        self.entries.keys().copied().collect()
+    }
+
+    /// Returns the total size of the encoded values of the contract storage in bytes.
+    pub fn size(&self) -> usize {
+        self.entries.values().map(Vec::len).sum()
+    }
+
+    /// Returns an iterator over the encoded entries of the contract storage.
+    pub fn iter(&self) -> btree_map::Iter<Key, Vec<u8>> {
+        self.entries.iter()
+    }
+
+    /// Returns a copy of the contract storage without the filtered keys.
+    pub fn filtered(&self, filtered_keys: &[Key]) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .filter(|(key, _)| !filtered_keys.contains(key))
+                .map(|(key, value)| (*key, value.clone()))
+                .collect(),
+            ..Self::new()
+        }
     }
 }
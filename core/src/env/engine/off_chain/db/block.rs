@@ -14,8 +14,10 @@
 
 use super::{
     super::{
+        hashing,
         Result,
         TypedEncoded,
+        TypedEncodedError,
     },
     OffBlockNumber,
     OffHash,
@@ -24,6 +26,7 @@ use super::{
 use crate::env::EnvTypes;
 
 /// An emulated block in the chain.
+#[derive(Clone)]
 pub struct Block {
     /// The current block number.
     number: OffBlockNumber,
@@ -36,6 +39,13 @@ pub struct Block {
     /// - Can optionally be set for more control via
     ///   [`crate::env::set_block_randomization_hash`].
     entropy: OffHash,
+    /// The randomness seed of the block if any.
+    ///
+    /// # Note
+    ///
+    /// If set the randomness of the block is derived from the seed
+    /// instead of the entropy.
+    seed: Option<Vec<u8>>,
 }
 
 impl Block {
@@ -52,9 +62,15 @@ impl Block {
             number: TypedEncoded::new(&number),
             timestamp: TypedEncoded::new(&timestamp),
             entropy: TypedEncoded::new(&entropy),
+            seed: None,
         }
     }
 
+    /// Returns the encoded block number.
+    pub fn encoded_number(&self) -> &OffBlockNumber {
+        &self.number
+    }
+
     /// Returns the block number.
     pub fn number<T>(&self) -> Result<T::BlockNumber>
     where
@@ -84,6 +100,21 @@ impl Block {
         self.entropy.assign(&new_entropy).map_err(Into::into)
     }
 
+    /// Sets the randomness seed of this block.
+    ///
+    /// # Note
+    ///
+    /// This makes [`crate::env::random`] return the BLAKE2 256-bit hash of
+    /// the concatenation of the seed and the subject.
+    pub fn set_seed(&mut self, seed: &[u8]) {
+        self.seed = Some(seed.to_vec());
+    }
+
+    /// Returns the randomness seed of this block if any.
+    pub fn seed(&self) -> Option<&[u8]> {
+        self.seed.as_deref()
+    }
+
     /// Returns a randomized hash.
     ///
     /// # Note
@@ -97,10 +128,20 @@ impl Block {
     /// testability purposes the actual implementation is quite simple and
     /// computes those "random" hashes by wrapping XOR of the internal entry hash
     /// with the eventually repeated sequence of the subject buffer.
+    ///
+    /// - If a seed has been set the returned hash is the BLAKE2 256-bit hash
+    /// of the concatenation of the seed and the subject instead.
     pub fn random<T>(&self, subject: &[u8]) -> Result<T::Hash>
     where
         T: EnvTypes,
     {
+        if let Some(seed) = &self.seed {
+            let mut output = [0x00; 32];
+            hashing::blake2b_256(&[&seed[..], subject].concat(), &mut output);
+            return <T::Hash as scale::Decode>::decode(&mut &output[..])
+                .map_err(TypedEncodedError::from)
+                .map_err(Into::into)
+        }
         let mut entropy = self.entropy.clone();
         let entropy_bytes = entropy.encoded_bytes_mut()?;
         let len_entropy = entropy_bytes.len();
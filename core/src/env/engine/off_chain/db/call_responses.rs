@@ -0,0 +1,158 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OffAccountId;
+use crate::env::{
+    call::Selector,
+    EnvError,
+    EnvTypes,
+};
+use core::cell::RefCell;
+use ink_prelude::collections::BTreeMap;
+use std::{
+    panic::{
+        catch_unwind,
+        AssertUnwindSafe,
+    },
+    rc::Rc,
+};
+
+/// A handler emulating the execution of the message of a called contract.
+///
+/// Receives the encoded input of the call and returns the encoded result.
+///
+/// # Note
+///
+/// The handler is shared so that it can be executed without borrowing the
+/// registry it is stored in.
+pub type CallHandler = Rc<RefCell<dyn FnMut(&[u8]) -> Vec<u8>>>;
+
+/// The registered response to an emulated cross-contract call.
+#[derive(Clone)]
+pub enum CallResponse {
+    /// The encoded value that is returned by the call.
+    Encoded(Vec<u8>),
+    /// The handler that is executed upon the call.
+    Handler(CallHandler),
+}
+
+impl CallResponse {
+    /// Returns the encoded result of the call with the given encoded input.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EnvError::CalleeTrapped`] if the handler of the call panics
+    /// or is called again while it is still being executed.
+    pub fn respond(&self, input: &[u8]) -> Result<Vec<u8>, EnvError> {
+        match self {
+            Self::Encoded(encoded) => Ok(encoded.clone()),
+            Self::Handler(handler) => {
+                catch_unwind(AssertUnwindSafe(|| (&mut *handler.borrow_mut())(input)))
+                    .map_err(|_| EnvError::CalleeTrapped)
+            }
+        }
+    }
+}
+
+/// Registry for the encoded return values of emulated cross-contract calls.
+///
+/// # Note
+///
+/// The off-chain environment is not able to actually execute other
+/// contracts. Instead tests register the encoded value that a call to
+/// a given message of a given contract is expected to return.
+#[derive(Clone)]
+pub struct CallResponses {
+    /// The responses and the gas consumed by the emulated calls keyed by
+    /// callee and message selector.
+    responses: BTreeMap<(OffAccountId, [u8; 4]), (CallResponse, u64)>,
+}
+
+impl CallResponses {
+    /// Creates a new empty call response registry.
+    pub fn new() -> Self {
+        Self {
+            responses: BTreeMap::new(),
+        }
+    }
+
+    /// Resets the call response registry to uninitialized state.
+    pub fn reset(&mut self) {
+        self.responses.clear();
+    }
+
+    /// Registers the response for calls to the selector of the callee.
+    ///
+    /// Overwrites any previously registered response for the same call.
+    pub fn register<T, R>(&mut self, callee: &T::AccountId, selector: Selector, response: &R)
+    where
+        T: EnvTypes,
+        R: scale::Encode,
+    {
+        self.register_with_gas::<T, R>(callee, selector, response, 0)
+    }
+
+    /// Registers the response for calls to the selector of the callee
+    /// together with the gas consumed by the emulated call.
+    ///
+    /// Overwrites any previously registered response for the same call.
+    pub fn register_with_gas<T, R>(
+        &mut self,
+        callee: &T::AccountId,
+        selector: Selector,
+        response: &R,
+        gas: u64,
+    ) where
+        T: EnvTypes,
+        R: scale::Encode,
+    {
+        self.responses.insert(
+            (OffAccountId::new(callee), selector.to_bytes()),
+            (CallResponse::Encoded(response.encode()), gas),
+        );
+    }
+
+    /// Registers the handler executed upon calls to the selector of the callee.
+    ///
+    /// Overwrites any previously registered response for the same call.
+    pub fn register_handler<T, F>(
+        &mut self,
+        callee: &T::AccountId,
+        selector: Selector,
+        handler: F,
+    ) where
+        T: EnvTypes,
+        F: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        self.responses.insert(
+            (OffAccountId::new(callee), selector.to_bytes()),
+            (CallResponse::Handler(Rc::new(RefCell::new(handler))), 0),
+        );
+    }
+
+    /// Returns the response registered for the selector of the callee
+    /// and the gas consumed by the emulated call if any.
+    pub fn get_mut<T>(
+        &mut self,
+        callee: &T::AccountId,
+        selector: Selector,
+    ) -> Option<(&mut CallResponse, u64)>
+    where
+        T: EnvTypes,
+    {
+        self.responses
+            .get_mut(&(OffAccountId::new(callee), selector.to_bytes()))
+            .map(|(response, gas)| (response, *gas))
+    }
+}
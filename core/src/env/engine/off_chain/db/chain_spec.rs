@@ -14,40 +14,102 @@
 
 use super::{
     super::Result,
+    OffAccountId,
     OffBalance,
     OffTimestamp,
 };
 use crate::env::EnvTypes;
+use std::rc::Rc;
+
+/// A custom conversion from weight to fee.
+///
+/// # Note
+///
+/// Shared so that the chain specification can be snapshotted.
+type WeightToFee = Rc<dyn Fn(u64) -> OffBalance>;
+
+/// The handling of the remaining balance (dust) of reaped accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DustPolicy<AccountId> {
+    /// The dust is burned.
+    Burn,
+    /// The dust is sent to the treasury account.
+    Treasury(AccountId),
+}
 
 /// The chain specification.
+#[derive(Clone)]
 pub struct ChainSpec {
     /// The current gas price.
     gas_price: OffBalance,
+    /// The price per unit of computation time of two-dimensional weights.
+    ref_time_price: OffBalance,
+    /// The price per byte of storage proof of two-dimensional weights.
+    proof_size_price: OffBalance,
     /// The minimum value an account of the chain may have.
     minimum_balance: OffBalance,
     /// The tombstone deposit.
     tombstone_deposit: OffBalance,
+    /// The rent charged per byte of contract storage per block.
+    rent_byte_price: OffBalance,
     /// The targeted block time.
     block_time: OffTimestamp,
+    /// The custom conversion from weight to fee if any.
+    ///
+    /// # Note
+    ///
+    /// If this is `None` the fee is linear in the weight using the gas price.
+    weight_to_fee: Option<WeightToFee>,
+    /// The maximum number of topics per emitted event if limited.
+    max_event_topics: Option<u32>,
+    /// The maximum size of the encoded data of an emitted event if limited.
+    max_event_size: Option<u32>,
+    /// The handling of dust if accounts below the minimum balance are reaped.
+    ///
+    /// # Note
+    ///
+    /// If this is `None` accounts are never reaped.
+    reaping: Option<DustPolicy<OffAccountId>>,
+    /// The maximum depth of the call stack of nested contract executions.
+    max_call_depth: u32,
 }
 
+/// The default maximum depth of the call stack as configured on-chain.
+const DEFAULT_MAX_CALL_DEPTH: u32 = 32;
+
 impl ChainSpec {
     /// Creates a new uninitialized chain specification.
     pub fn uninitialized() -> Self {
         Self {
             gas_price: OffBalance::uninitialized(),
+            ref_time_price: OffBalance::uninitialized(),
+            proof_size_price: OffBalance::uninitialized(),
             minimum_balance: OffBalance::uninitialized(),
             tombstone_deposit: OffBalance::uninitialized(),
+            rent_byte_price: OffBalance::uninitialized(),
             block_time: OffTimestamp::uninitialized(),
+            weight_to_fee: None,
+            max_event_topics: None,
+            max_event_size: None,
+            reaping: None,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
         }
     }
 
     /// Resets the chain spec to uninitialized state.
     pub fn reset(&mut self) {
         self.gas_price = OffBalance::uninitialized();
+        self.ref_time_price = OffBalance::uninitialized();
+        self.proof_size_price = OffBalance::uninitialized();
         self.minimum_balance = OffBalance::uninitialized();
         self.tombstone_deposit = OffBalance::uninitialized();
+        self.rent_byte_price = OffBalance::uninitialized();
         self.block_time = OffTimestamp::uninitialized();
+        self.weight_to_fee = None;
+        self.max_event_topics = None;
+        self.max_event_size = None;
+        self.reaping = None;
+        self.max_call_depth = DEFAULT_MAX_CALL_DEPTH;
     }
 
     /// Default initialization for the off-chain specification.
@@ -58,10 +120,16 @@ impl ChainSpec {
     {
         self.gas_price
             .try_initialize::<T::Balance>(&T::Balance::from(100))?;
+        self.ref_time_price
+            .try_initialize::<T::Balance>(&T::Balance::from(100))?;
+        self.proof_size_price
+            .try_initialize::<T::Balance>(&T::Balance::from(1))?;
         self.minimum_balance
             .try_initialize::<T::Balance>(&T::Balance::from(42))?;
         self.tombstone_deposit
             .try_initialize::<T::Balance>(&T::Balance::from(16))?;
+        self.rent_byte_price
+            .try_initialize::<T::Balance>(&T::Balance::from(1))?;
         self.block_time
             .try_initialize::<T::Timestamp>(&T::Timestamp::from(5))?;
         Ok(())
@@ -83,6 +151,63 @@ impl ChainSpec {
         self.gas_price = OffBalance::new(&gas_price)
     }
 
+    /// Returns the price per unit of computation time of two-dimensional weights.
+    pub fn ref_time_price<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.ref_time_price.decode().map_err(Into::into)
+    }
+
+    /// Sets the price per unit of computation time of two-dimensional weights.
+    pub fn set_ref_time_price<T>(&mut self, ref_time_price: T::Balance)
+    where
+        T: EnvTypes,
+    {
+        self.ref_time_price = OffBalance::new(&ref_time_price)
+    }
+
+    /// Returns the price per byte of storage proof of two-dimensional weights.
+    pub fn proof_size_price<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.proof_size_price.decode().map_err(Into::into)
+    }
+
+    /// Sets the price per byte of storage proof of two-dimensional weights.
+    pub fn set_proof_size_price<T>(&mut self, proof_size_price: T::Balance)
+    where
+        T: EnvTypes,
+    {
+        self.proof_size_price = OffBalance::new(&proof_size_price)
+    }
+
+    /// Sets a custom conversion from weight to fee for the chain.
+    ///
+    /// # Note
+    ///
+    /// This replaces the default linear conversion using the gas price.
+    pub fn set_weight_to_fee<T, F>(&mut self, weight_to_fee: F)
+    where
+        T: EnvTypes,
+        F: Fn(u64) -> T::Balance + 'static,
+    {
+        self.weight_to_fee = Some(Rc::new(move |weight| {
+            OffBalance::new(&weight_to_fee(weight))
+        }))
+    }
+
+    /// Returns the fee for the weight if a custom conversion has been set.
+    pub fn weight_to_fee<T>(&self, weight: u64) -> Option<Result<T::Balance>>
+    where
+        T: EnvTypes,
+    {
+        self.weight_to_fee
+            .as_ref()
+            .map(|weight_to_fee| weight_to_fee(weight).decode().map_err(Into::into))
+    }
+
     /// Returns the minimum balance for an account on the chain.
     pub fn minimum_balance<T>(&self) -> Result<T::Balance>
     where
@@ -91,6 +216,14 @@ impl ChainSpec {
         self.minimum_balance.decode().map_err(Into::into)
     }
 
+    /// Sets the minimum balance for an account on the chain.
+    pub fn set_minimum_balance<T>(&mut self, minimum_balance: T::Balance)
+    where
+        T: EnvTypes,
+    {
+        self.minimum_balance = OffBalance::new(&minimum_balance)
+    }
+
     /// Returns the tombstone deposit for the chain.
     pub fn tombstone_deposit<T>(&self) -> Result<T::Balance>
     where
@@ -99,6 +232,30 @@ impl ChainSpec {
         self.tombstone_deposit.decode().map_err(Into::into)
     }
 
+    /// Sets the tombstone deposit for the chain.
+    pub fn set_tombstone_deposit<T>(&mut self, tombstone_deposit: T::Balance)
+    where
+        T: EnvTypes,
+    {
+        self.tombstone_deposit = OffBalance::new(&tombstone_deposit)
+    }
+
+    /// Returns the rent charged per byte of contract storage per block.
+    pub fn rent_byte_price<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.rent_byte_price.decode().map_err(Into::into)
+    }
+
+    /// Sets the rent charged per byte of contract storage per block.
+    pub fn set_rent_byte_price<T>(&mut self, rent_byte_price: T::Balance)
+    where
+        T: EnvTypes,
+    {
+        self.rent_byte_price = OffBalance::new(&rent_byte_price)
+    }
+
     /// Returns the targeted block time for the chain.
     pub fn block_time<T>(&self) -> Result<T::Timestamp>
     where
@@ -106,4 +263,77 @@ impl ChainSpec {
     {
         self.block_time.decode().map_err(Into::into)
     }
+
+    /// Sets the maximum number of topics per emitted event.
+    ///
+    /// # Note
+    ///
+    /// By default the number of topics is not limited.
+    pub fn set_max_event_topics(&mut self, max_event_topics: u32) {
+        self.max_event_topics = Some(max_event_topics);
+    }
+
+    /// Returns the maximum number of topics per emitted event if limited.
+    pub fn max_event_topics(&self) -> Option<u32> {
+        self.max_event_topics
+    }
+
+    /// Sets the maximum size of the encoded data of an emitted event in bytes.
+    ///
+    /// # Note
+    ///
+    /// By default the size of emitted events is not limited.
+    pub fn set_max_event_size(&mut self, max_event_size: u32) {
+        self.max_event_size = Some(max_event_size);
+    }
+
+    /// Returns the maximum size of the encoded data of an emitted event
+    /// in bytes if limited.
+    pub fn max_event_size(&self) -> Option<u32> {
+        self.max_event_size
+    }
+
+    /// Enables reaping of accounts whose balance drops below the minimum
+    /// balance after a transfer.
+    ///
+    /// # Note
+    ///
+    /// The dust of reaped accounts is handled according to the policy.
+    /// By default accounts are never reaped.
+    pub fn enable_reaping<T>(&mut self, dust_policy: DustPolicy<T::AccountId>)
+    where
+        T: EnvTypes,
+    {
+        self.reaping = Some(match dust_policy {
+            DustPolicy::Burn => DustPolicy::Burn,
+            DustPolicy::Treasury(treasury) => {
+                DustPolicy::Treasury(OffAccountId::new(&treasury))
+            }
+        });
+    }
+
+    /// Disables reaping of accounts below the minimum balance.
+    pub fn disable_reaping(&mut self) {
+        self.reaping = None;
+    }
+
+    /// Returns the handling of dust if reaping of accounts is enabled.
+    pub fn reaping(&self) -> Option<&DustPolicy<OffAccountId>> {
+        self.reaping.as_ref()
+    }
+
+    /// Sets the maximum depth of the call stack of nested contract executions.
+    ///
+    /// # Note
+    ///
+    /// The executed contract itself is at depth 1. By default the depth is
+    /// limited to 32 as it is on-chain.
+    pub fn set_max_call_depth(&mut self, max_call_depth: u32) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    /// Returns the maximum depth of the call stack of nested contract executions.
+    pub fn max_call_depth(&self) -> u32 {
+        self.max_call_depth
+    }
 }
@@ -0,0 +1,253 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    super::OffChainError,
+    call_responses::{
+        CallHandler,
+        CallResponse,
+    },
+    OffAccountId,
+    OffHash,
+};
+use crate::env::{
+    EnvError,
+    EnvTypes,
+};
+use core::cell::RefCell;
+use ink_prelude::collections::BTreeMap;
+use std::{
+    panic::{
+        catch_unwind,
+        AssertUnwindSafe,
+    },
+    rc::Rc,
+};
+
+/// The emulated code of a contract.
+///
+/// Receives the encoded input of the instantiation and returns the handler
+/// that answers all subsequent calls to the instantiated contract or an error
+/// if the constructor failed.
+pub type Code = Rc<dyn Fn(&[u8]) -> Result<CallHandler, EnvError>>;
+
+/// The emulated code of a contract that existing contracts can upgrade to.
+///
+/// Returns the handler that answers all subsequent calls to the upgraded
+/// contract without running a constructor.
+type UpgradeCode = Rc<dyn Fn() -> CallHandler>;
+
+/// Registry for the uploaded code and the contracts instantiated from it.
+///
+/// # Note
+///
+/// This models the upload-then-instantiate flow of `pallet-contracts`.
+/// Contracts can only be instantiated from code that has been uploaded
+/// before. Clones share the uploaded code and the handlers of the
+/// instantiated contracts.
+#[derive(Clone)]
+pub struct CodeRegistry {
+    /// The uploaded code keyed by its code hash.
+    codes: BTreeMap<OffHash, Code>,
+    /// The uploaded code that contracts can upgrade to keyed by its code hash.
+    upgrade_codes: BTreeMap<OffHash, UpgradeCode>,
+    /// The handlers of the contracts instantiated from uploaded code.
+    instances: BTreeMap<OffAccountId, CallResponse>,
+}
+
+impl CodeRegistry {
+    /// Creates a new empty code registry.
+    pub fn new() -> Self {
+        Self {
+            codes: BTreeMap::new(),
+            upgrade_codes: BTreeMap::new(),
+            instances: BTreeMap::new(),
+        }
+    }
+
+    /// Resets the code registry to uninitialized state.
+    pub fn reset(&mut self) {
+        self.codes.clear();
+        self.upgrade_codes.clear();
+        self.instances.clear();
+    }
+
+    /// Returns the number of uploaded codes.
+    pub fn num_codes(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Uploads the code under the given code hash.
+    ///
+    /// Overwrites any previously uploaded code with the same code hash.
+    pub fn upload<T, F, H>(&mut self, code_hash: &T::Hash, code: F)
+    where
+        T: EnvTypes,
+        F: Fn(&[u8]) -> H + 'static,
+        H: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        self.upload_fallible::<T, _, H, core::convert::Infallible>(
+            code_hash,
+            move |input| Ok(code(input)),
+        )
+    }
+
+    /// Uploads the code with a fallible constructor under the given code hash.
+    ///
+    /// Overwrites any previously uploaded code with the same code hash.
+    pub fn upload_fallible<T, F, H, E>(&mut self, code_hash: &T::Hash, code: F)
+    where
+        T: EnvTypes,
+        F: Fn(&[u8]) -> Result<H, E> + 'static,
+        H: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        let code_hash = OffHash::new(code_hash);
+        self.upgrade_codes.remove(&code_hash);
+        self.codes.insert(
+            code_hash,
+            Rc::new(move |input: &[u8]| -> Result<CallHandler, EnvError> {
+                let handler = code(input).map_err(|_| EnvError::CalleeReverted)?;
+                Ok(Rc::new(RefCell::new(handler)))
+            }),
+        );
+    }
+
+    /// Uploads the code that contracts can upgrade to under the given code hash.
+    ///
+    /// Contracts instantiated from the code ignore the input of the
+    /// instantiation. Overwrites any previously uploaded code with the same
+    /// code hash.
+    pub fn upload_upgradable<T, F, H>(&mut self, code_hash: &T::Hash, code: F)
+    where
+        T: EnvTypes,
+        F: Fn() -> H + 'static,
+        H: FnMut(&[u8]) -> Vec<u8> + 'static,
+    {
+        let code = Rc::new(code);
+        let instantiate_code = Rc::clone(&code);
+        self.upload::<T, _, _>(code_hash, move |_| instantiate_code());
+        self.upgrade_codes.insert(
+            OffHash::new(code_hash),
+            Rc::new(move || -> CallHandler { Rc::new(RefCell::new(code())) }),
+        );
+    }
+
+    /// Returns `true` if code has been uploaded under the given code hash.
+    pub fn contains<T>(&self, code_hash: &T::Hash) -> bool
+    where
+        T: EnvTypes,
+    {
+        self.codes.contains_key(&OffHash::new(code_hash))
+    }
+
+    /// Returns the code uploaded under the given code hash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EnvError::CodeNotFound`] if there is no code uploaded under
+    /// the code hash.
+    pub fn code<T>(&self, code_hash: &T::Hash) -> Result<Code, EnvError>
+    where
+        T: EnvTypes,
+    {
+        self.codes
+            .get(&OffHash::new(code_hash))
+            .cloned()
+            .ok_or(EnvError::CodeNotFound)
+    }
+
+    /// Runs the constructor of the code with the given input and returns the
+    /// handler of the instantiated contract.
+    ///
+    /// # Note
+    ///
+    /// Does not borrow the code registry so that the constructor is able to
+    /// operate on the environment.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`EnvError::CalleeTrapped`] if the code panics upon
+    ///   instantiation.
+    /// - Returns [`EnvError::CalleeReverted`] if the constructor of the code
+    ///   fails.
+    pub fn construct(code: &Code, input: &[u8]) -> Result<CallHandler, EnvError> {
+        catch_unwind(AssertUnwindSafe(|| code(input)))
+            .map_err(|_| EnvError::CalleeTrapped)?
+    }
+
+    /// Registers the handler of the contract with the given account ID that
+    /// has been instantiated from uploaded code.
+    pub fn add_instance<T>(&mut self, account_id: &T::AccountId, handler: CallHandler)
+    where
+        T: EnvTypes,
+    {
+        self.instances.insert(
+            OffAccountId::new(account_id),
+            CallResponse::Handler(handler),
+        );
+    }
+
+    /// Replaces the handler of the contract with the given account ID by
+    /// the handler of the code with the given code hash.
+    ///
+    /// # Note
+    ///
+    /// No constructor runs upon upgrades. The handler of the contract is
+    /// left untouched if the upgrade fails.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`EnvError::CodeNotFound`] if there is no code uploaded
+    ///   under the code hash.
+    /// - Returns [`OffChainError::CodeNotUpgradable`] if the code has not been
+    ///   uploaded via [`CodeRegistry::upload_upgradable`].
+    /// - Returns [`EnvError::CalleeTrapped`] if the code panics upon
+    ///   creating its handler.
+    pub fn upgrade<T>(
+        &mut self,
+        code_hash: &T::Hash,
+        account_id: &T::AccountId,
+    ) -> Result<(), EnvError>
+    where
+        T: EnvTypes,
+    {
+        let code_hash = OffHash::new(code_hash);
+        if !self.codes.contains_key(&code_hash) {
+            return Err(EnvError::CodeNotFound)
+        }
+        let code = self
+            .upgrade_codes
+            .get(&code_hash)
+            .ok_or(OffChainError::CodeNotUpgradable)?;
+        let handler = catch_unwind(AssertUnwindSafe(|| code()))
+            .map_err(|_| EnvError::CalleeTrapped)?;
+        self.instances.insert(
+            OffAccountId::new(account_id),
+            CallResponse::Handler(handler),
+        );
+        Ok(())
+    }
+
+    /// Returns the handler of the contract instantiated from uploaded code
+    /// with the given account ID if any.
+    pub fn instance_mut<T>(
+        &mut self,
+        account_id: &T::AccountId,
+    ) -> Option<&mut CallResponse>
+    where
+        T: EnvTypes,
+    {
+        self.instances.get_mut(&OffAccountId::new(account_id))
+    }
+}
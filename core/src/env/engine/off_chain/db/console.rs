@@ -12,12 +12,67 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::{
+    super::Result,
+    OffAccountId,
+    OffBlockNumber,
+};
+use crate::env::EnvTypes;
 use ink_prelude::string::String;
 
 /// A debug console used to print console contents and store them.
 pub struct Console {
     /// The buffer to store the already pasted contents.
     past_prints: Vec<String>,
+    /// The buffer to store the recorded debug messages.
+    debug_messages: Vec<DebugMessage>,
+}
+
+/// Record of a debug message of a contract.
+#[derive(Debug, Clone)]
+pub struct DebugMessage {
+    /// The contents of the debug message.
+    message: String,
+    /// The number of the block in which the debug message has been recorded.
+    block_number: OffBlockNumber,
+    /// The caller of the contract execution that recorded the debug message.
+    caller: OffAccountId,
+}
+
+impl DebugMessage {
+    /// Creates a new record of a debug message.
+    pub fn new(
+        message: &str,
+        block_number: OffBlockNumber,
+        caller: OffAccountId,
+    ) -> Self {
+        Self {
+            message: message.to_string(),
+            block_number,
+            caller,
+        }
+    }
+
+    /// Returns the contents of the debug message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the number of the block in which the debug message has been recorded.
+    pub fn block_number<T>(&self) -> Result<T::BlockNumber>
+    where
+        T: EnvTypes,
+    {
+        self.block_number.decode().map_err(Into::into)
+    }
+
+    /// Returns the caller of the contract execution that recorded the debug message.
+    pub fn caller<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.caller.decode().map_err(Into::into)
+    }
 }
 
 impl Console {
@@ -25,12 +80,14 @@ impl Console {
     pub fn new() -> Self {
         Self {
             past_prints: Vec::new(),
+            debug_messages: Vec::new(),
         }
     }
 
     /// Resets the console to uninitialized state.
     pub fn reset(&mut self) {
         self.past_prints.clear();
+        self.debug_messages.clear();
     }
 
     /// Prints the contents to the actual console and stores them.
@@ -39,6 +96,16 @@ impl Console {
         println!("{}", contents);
     }
 
+    /// Records the debug message without printing it.
+    pub fn record_debug_message(&mut self, debug_message: DebugMessage) {
+        self.debug_messages.push(debug_message);
+    }
+
+    /// Returns and removes all recorded debug messages in order.
+    pub fn drain_debug_messages(&mut self) -> Vec<DebugMessage> {
+        core::mem::take(&mut self.debug_messages)
+    }
+
     /// Returns an iterator over the past console prints.
     pub fn past_prints(&self) -> PastPrints {
         PastPrints::new(self)
@@ -0,0 +1,41 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env::Result;
+
+/// A recorded invocation of an `Env` or `TypedEnv` method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvCall {
+    /// The name of the invoked method.
+    pub name: &'static str,
+    /// The SCALE encoded salient arguments of the invocation.
+    ///
+    /// Empty if the method has no salient arguments.
+    pub args: Vec<u8>,
+}
+
+impl EnvCall {
+    /// Creates a new record of an invocation of the named method.
+    pub fn new(name: &'static str, args: Vec<u8>) -> Self {
+        Self { name, args }
+    }
+
+    /// Decodes the salient arguments of the invocation.
+    pub fn decode_args<A>(&self) -> Result<A>
+    where
+        A: scale::Decode,
+    {
+        <A as scale::Decode>::decode(&mut &self.args[..]).map_err(Into::into)
+    }
+}
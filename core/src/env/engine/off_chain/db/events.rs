@@ -25,11 +25,23 @@ pub struct EmittedEvent {
     pub topics: Vec<OffHash>,
     /// Recorded encoding of the emitted event.
     pub data: Vec<u8>,
+    /// The index of the event among all events emitted so far.
+    pub sequence: u64,
+    /// The index of the event among the events emitted by the same call.
+    pub call_index: u32,
+    /// If the event has been emitted during the instantiation of a contract.
+    pub during_instantiation: bool,
 }
 
 impl EmittedEvent {
-    /// Creates a new emitted event.
-    pub fn new<T, E>(emitted_event: E) -> Self
+    /// Creates a new emitted event with the given sequence number and
+    /// index within its call.
+    pub fn new<T, E>(
+        emitted_event: E,
+        sequence: u64,
+        call_index: u32,
+        during_instantiation: bool,
+    ) -> Self
     where
         T: EnvTypes,
         E: Topics<T> + scale::Encode,
@@ -41,13 +53,23 @@ impl EmittedEvent {
                 .map(|hash| OffHash::new(hash))
                 .collect::<Vec<_>>(),
             data: emitted_event.encode(),
+            sequence,
+            call_index,
+            during_instantiation,
         }
     }
 }
 
 /// Records all emitted events for later inspection.
+#[derive(Clone)]
 pub struct EmittedEventsRecorder {
     emitted_events: Vec<EmittedEvent>,
+    /// The index of the first event emitted in the current block.
+    block_start: usize,
+    /// The index of the first event emitted by the most recent call.
+    call_start: usize,
+    /// The sequence number of the next emitted event.
+    next_sequence: u64,
 }
 
 impl EmittedEventsRecorder {
@@ -55,21 +77,99 @@ impl EmittedEventsRecorder {
     pub fn new() -> Self {
         Self {
             emitted_events: Vec::new(),
+            block_start: 0,
+            call_start: 0,
+            next_sequence: 0,
         }
     }
 
     /// Resets the emitted events to none.
     pub fn reset(&mut self) {
         self.emitted_events.clear();
+        self.block_start = 0;
+        self.call_start = 0;
+        self.next_sequence = 0;
     }
 
-    /// Records a new emitted event.
-    pub fn record<T, E>(&mut self, new_event: E)
-    where
+    /// Clears all emitted events recorded so far.
+    ///
+    /// # Note
+    ///
+    /// Subsequently emitted events continue the sequence numbers of the
+    /// cleared events unless `reset_sequence` is `true`.
+    pub fn clear(&mut self, reset_sequence: bool) {
+        self.emitted_events.clear();
+        self.block_start = 0;
+        self.call_start = 0;
+        if reset_sequence {
+            self.next_sequence = 0;
+        }
+    }
+
+    /// Returns the events emitted in the current block in their emission order.
+    pub fn block_events(&self) -> &[EmittedEvent] {
+        &self.emitted_events[self.block_start..]
+    }
+
+    /// Finishes the current block so that subsequently emitted events are
+    /// attributed to the next block.
+    pub fn finish_block(&mut self) {
+        self.block_start = self.emitted_events.len();
+    }
+
+    /// Returns the events emitted by the most recent call in their emission order.
+    pub fn call_events(&self) -> &[EmittedEvent] {
+        &self.emitted_events[self.call_start..]
+    }
+
+    /// Starts a new call so that only subsequently emitted events are
+    /// attributed to the most recent call.
+    pub fn start_call(&mut self) {
+        self.call_start = self.emitted_events.len();
+    }
+
+    /// Records a new emitted event that is the event at `call_index` among
+    /// the events emitted by its call and that has possibly been emitted
+    /// during the instantiation of a contract.
+    ///
+    /// # Panics
+    ///
+    /// - If the event has more than `max_topics` topics.
+    /// - If the encoded event is larger than `max_size` bytes.
+    pub fn record<T, E>(
+        &mut self,
+        new_event: E,
+        call_index: u32,
+        during_instantiation: bool,
+        max_topics: Option<u32>,
+        max_size: Option<u32>,
+    ) where
         T: EnvTypes,
         E: Topics<T> + scale::Encode,
     {
-        self.emitted_events.push(EmittedEvent::new(new_event));
+        let sequence = self.next_sequence;
+        let new_event =
+            EmittedEvent::new(new_event, sequence, call_index, during_instantiation);
+        if let Some(max_topics) = max_topics {
+            let topics = new_event.topics.len();
+            if topics > max_topics as usize {
+                panic!(
+                    "emitted event has {} topics but at most {} topics are allowed",
+                    topics, max_topics,
+                )
+            }
+        }
+        if let Some(max_size) = max_size {
+            let size = new_event.data.len();
+            if size > max_size as usize {
+                panic!(
+                    "emitted event has {} bytes but at most {} bytes are allowed",
+                    size, max_size,
+                )
+            }
+        }
+        self.emitted_events.push(new_event);
+        self.next_sequence += 1;
     }
 
     /// Returns an iterator over the emitted events in their emission order.
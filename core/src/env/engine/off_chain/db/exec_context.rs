@@ -44,8 +44,34 @@ pub struct ExecContext {
     ///
     /// This includes selector and encoded arguments.
     pub call_data: CallData,
+    /// The raw input bytes provided for the whole execution if any.
+    ///
+    /// # Note
+    ///
+    /// If set these are decoded directly instead of `call_data`.
+    pub raw_call_data: Option<Bytes>,
     /// The output of the contract execution.
     pub output: Option<Bytes>,
+    /// If the contract is executed in read-only mode.
+    ///
+    /// # Note
+    ///
+    /// In read-only mode the contract must not mutate its state.
+    pub read_only: bool,
+    /// If the caller of the contract execution is another contract.
+    ///
+    /// # Note
+    ///
+    /// Otherwise the caller is the origin of the whole call chain.
+    pub caller_is_contract: bool,
+    /// If the contract execution instantiates the callee.
+    ///
+    /// # Note
+    ///
+    /// This is the case while one of the constructors of the callee runs.
+    pub is_instantiation: bool,
+    /// The number of events emitted by the contract execution so far.
+    pub emitted_events: u32,
 }
 
 impl ExecContext {
@@ -100,11 +126,6 @@ impl ExecContext {
     }
 
     /// Returns the contract execution output.
-    #[allow(
-        dead_code,
-        // Needed as soon as we support to execute contracts
-        // directly through the off-chain environment.
-    )]
     pub fn output(&self) -> Option<&Bytes> {
         self.output.as_ref()
     }
@@ -225,7 +246,12 @@ where
             transferred_value: TypedEncoded::new(&transferred_value),
             gas: TypedEncoded::new(&gas),
             call_data: self.call_data.unwrap(),
+            raw_call_data: None,
             output: None,
+            read_only: false,
+            caller_is_contract: false,
+            is_instantiation: false,
+            emitted_events: 0,
         }
     }
 }
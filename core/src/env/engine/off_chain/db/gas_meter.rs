@@ -0,0 +1,156 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env::{
+    EnvError,
+    Result,
+};
+use std::collections::BTreeMap;
+
+/// The metered environmental operations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HostFunction {
+    /// Writing a value to the contract storage.
+    SetContractStorage,
+    /// Reading a value from the contract storage.
+    GetContractStorage,
+    /// Querying if the contract storage contains a value.
+    ContainsContractStorage,
+    /// Clearing a value of the contract storage.
+    ClearContractStorage,
+    /// Computing a KECCAK 256-bit hash.
+    HashKeccak256,
+    /// Computing a BLAKE2 256-bit hash.
+    HashBlake2x256,
+    /// Computing a BLAKE2 128-bit hash.
+    HashBlake2x128,
+    /// Computing a SHA2 256-bit hash.
+    HashSha2x256,
+    /// Computing a TWOX 64-bit hash.
+    HashTwox64,
+    /// Computing a TWOX 128-bit hash.
+    HashTwox128,
+    /// Computing a TWOX 256-bit hash.
+    HashTwox256,
+    /// Transferring value.
+    Transfer,
+    /// Calling another contract.
+    CallContract,
+}
+
+/// The accumulated gas usage of a single metered operation.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct GasUsage {
+    /// The number of times the operation has been charged.
+    pub count: u64,
+    /// The total gas charged for the operation.
+    pub gas: u64,
+}
+
+/// The gas usage per metered operation recorded while profiling.
+pub type GasProfile = BTreeMap<HostFunction, GasUsage>;
+
+/// The gas costs of the metered environmental operations.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct GasCosts {
+    /// The gas costs of reading from the contract storage.
+    pub storage_read: u64,
+    /// The gas costs of writing to or clearing the contract storage.
+    pub storage_write: u64,
+    /// The gas costs of computing a hash.
+    pub hashing: u64,
+    /// The gas costs of transferring value.
+    pub transfer: u64,
+}
+
+/// Meters the gas consumed by environmental operations.
+pub struct GasMeter {
+    /// The gas costs per metered operation.
+    costs: GasCosts,
+    /// The gas that is left for the execution.
+    gas_left: u64,
+    /// The recorded gas usage per operation if profiling is enabled.
+    profile: Option<GasProfile>,
+}
+
+impl GasMeter {
+    /// Creates a new gas meter with the given costs and initial gas.
+    pub fn new(costs: GasCosts, initial_gas: u64) -> Self {
+        Self {
+            costs,
+            gas_left: initial_gas,
+            profile: None,
+        }
+    }
+
+    /// Returns the gas costs per metered operation.
+    pub fn costs(&self) -> &GasCosts {
+        &self.costs
+    }
+
+    /// Returns the gas that is left for the execution.
+    pub fn gas_left(&self) -> u64 {
+        self.gas_left
+    }
+
+    /// Enables recording the gas usage per operation.
+    ///
+    /// Does not reset the gas usage recorded so far.
+    pub fn enable_profiling(&mut self) {
+        if self.profile.is_none() {
+            self.profile = Some(GasProfile::new());
+        }
+    }
+
+    /// Returns the gas usage recorded so far and clears the records.
+    ///
+    /// Returns an empty profile if profiling is disabled.
+    pub fn take_profile(&mut self) -> GasProfile {
+        self.profile
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Charges the given amount of gas for the given operation.
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough gas left. In this case no gas is charged.
+    pub fn charge(&mut self, function: HostFunction, amount: u64) -> Result<()> {
+        self.gas_left = self
+            .gas_left
+            .checked_sub(amount)
+            .ok_or(EnvError::OutOfGas)?;
+        if let Some(profile) = &mut self.profile {
+            let usage = profile.entry(function).or_default();
+            usage.count += 1;
+            usage.gas += amount;
+        }
+        Ok(())
+    }
+
+    /// Charges the given amount of gas for the given operation or all gas
+    /// left if there is not enough gas left.
+    ///
+    /// # Note
+    ///
+    /// This is used for operations that cannot report running out of gas.
+    /// The following operations then fail with [`EnvError::OutOfGas`].
+    pub fn charge_or_exhaust(&mut self, function: HostFunction, amount: u64) {
+        if self.charge(function, amount).is_err() {
+            self.gas_left = 0;
+        }
+    }
+}
@@ -14,10 +14,17 @@
 
 mod accounts;
 mod block;
+mod call_responses;
+mod code_registry;
 mod chain_spec;
 mod console;
+mod env_call;
 mod events;
 mod exec_context;
+mod gas_meter;
+mod restoration;
+mod storage_budget;
+mod termination;
 
 pub use self::{
     accounts::{
@@ -27,18 +34,37 @@ pub use self::{
         AccountsDb,
         ContractAccount,
         ContractStorage,
+        StorageAccess,
+        StorageAccessKind,
     },
     block::Block,
-    chain_spec::ChainSpec,
+    call_responses::CallResponses,
+    code_registry::CodeRegistry,
+    chain_spec::{
+        ChainSpec,
+        DustPolicy,
+    },
     console::{
         Console,
+        DebugMessage,
         PastPrints,
     },
+    env_call::EnvCall,
     events::{
         EmittedEvent,
         EmittedEventsRecorder,
     },
     exec_context::ExecContext,
+    gas_meter::{
+        GasCosts,
+        GasMeter,
+        GasProfile,
+        GasUsage,
+        HostFunction,
+    },
+    restoration::ContractRestoration,
+    storage_budget::StorageBudget,
+    termination::ContractTermination,
 };
 use super::{
     OffAccountId,
@@ -0,0 +1,54 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    super::Result,
+    OffAccountId,
+};
+use crate::env::EnvTypes;
+
+/// Record of an attempt to restore an evicted contract.
+#[derive(Debug)]
+pub struct ContractRestoration {
+    /// The account ID of the contract that was supposed to be restored.
+    contract: OffAccountId,
+    /// The outcome of the restoration.
+    outcome: crate::env::Result<()>,
+}
+
+impl ContractRestoration {
+    /// Creates a new record of a contract restoration attempt.
+    pub fn new<T>(contract: &T::AccountId, outcome: crate::env::Result<()>) -> Self
+    where
+        T: EnvTypes,
+    {
+        Self {
+            contract: OffAccountId::new(contract),
+            outcome,
+        }
+    }
+
+    /// Returns the account ID of the contract that was supposed to be restored.
+    pub fn contract<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.contract.decode().map_err(Into::into)
+    }
+
+    /// Returns the outcome of the restoration.
+    pub fn into_outcome(self) -> crate::env::Result<()> {
+        self.outcome
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Limits on the contract storage emulating a host that runs out of space.
+///
+/// # Note
+///
+/// Writes to the contract storage that would exceed the budget fail with
+/// [`crate::env::EnvError::OutOfStorage`]. Clearing storage entries is
+/// always possible.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StorageBudget {
+    /// The maximum total size of the contract storage in bytes if any.
+    max_bytes: Option<usize>,
+    /// The maximum number of writes to the contract storage if any.
+    max_writes: Option<usize>,
+    /// The number of writes to the contract storage so far.
+    writes: usize,
+}
+
+impl StorageBudget {
+    /// Creates a storage budget that limits the total size of the contract
+    /// storage to the given number of bytes.
+    pub fn bytes(max_bytes: usize) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a storage budget that limits the number of writes to the
+    /// contract storage.
+    pub fn writes(max_writes: usize) -> Self {
+        Self {
+            max_writes: Some(max_writes),
+            ..Default::default()
+        }
+    }
+
+    /// Additionally limits the number of writes to the contract storage.
+    pub fn with_max_writes(mut self, max_writes: usize) -> Self {
+        self.max_writes = Some(max_writes);
+        self
+    }
+
+    /// Returns the number of writes to the contract storage so far.
+    pub fn writes_so_far(&self) -> usize {
+        self.writes
+    }
+
+    /// Records a write that leaves the contract storage with the given size.
+    ///
+    /// Returns `false` and records nothing if the write exceeds the budget.
+    pub fn try_write(&mut self, new_size: usize) -> bool {
+        let exceeds_bytes = self.max_bytes.map_or(false, |max| new_size > max);
+        let exceeds_writes = self.max_writes.map_or(false, |max| self.writes >= max);
+        if exceeds_bytes || exceeds_writes {
+            return false
+        }
+        self.writes += 1;
+        true
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    super::Result,
+    OffAccountId,
+    OffBalance,
+};
+use crate::env::EnvTypes;
+
+/// Record of a contract that has been terminated.
+#[derive(Debug, Clone)]
+pub struct ContractTermination {
+    /// The account ID of the terminated contract.
+    contract: OffAccountId,
+    /// The account ID of the beneficiary of the remaining balance.
+    beneficiary: OffAccountId,
+    /// The balance that has been transferred to the beneficiary.
+    transferred: OffBalance,
+}
+
+impl ContractTermination {
+    /// Creates a new record of a contract termination.
+    pub fn new<T>(
+        contract: &T::AccountId,
+        beneficiary: &T::AccountId,
+        transferred: T::Balance,
+    ) -> Self
+    where
+        T: EnvTypes,
+    {
+        Self {
+            contract: OffAccountId::new(contract),
+            beneficiary: OffAccountId::new(beneficiary),
+            transferred: OffBalance::new(&transferred),
+        }
+    }
+
+    /// Returns the account ID of the terminated contract.
+    pub fn contract<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.contract.decode().map_err(Into::into)
+    }
+
+    /// Returns the account ID of the beneficiary.
+    pub fn beneficiary<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.beneficiary.decode().map_err(Into::into)
+    }
+
+    /// Returns the balance transferred to the beneficiary.
+    pub fn transferred<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.transferred.decode().map_err(Into::into)
+    }
+}
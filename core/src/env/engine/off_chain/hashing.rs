@@ -35,6 +35,34 @@ pub fn blake2b_128(input: &[u8], output: &mut [u8; 16]) {
     blake2b_var(16, input, output)
 }
 
+/// Helper routine implementing the TWOX hash computation as done by Substrate.
+///
+/// The output is the concatenation of the little endian encoded 64-bit xxHash
+/// of the input seeded with `0`, `1`, etc. for every 8 bytes of output.
+fn twox(input: &[u8], output: &mut [u8]) {
+    use core::hash::Hasher as _;
+    for (seed, chunk) in output.chunks_exact_mut(8).enumerate() {
+        let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+        hasher.write(input);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+}
+
+/// Conduct the TWOX 64-bit hash and place the result into `output`.
+pub fn twox_64(input: &[u8], output: &mut [u8; 8]) {
+    twox(input, output)
+}
+
+/// Conduct the TWOX 128-bit hash and place the result into `output`.
+pub fn twox_128(input: &[u8], output: &mut [u8; 16]) {
+    twox(input, output)
+}
+
+/// Conduct the TWOX 256-bit hash and place the result into `output`.
+pub fn twox_256(input: &[u8], output: &mut [u8; 32]) {
+    twox(input, output)
+}
+
 /// Conduct the KECCAK 256-bit hash and place the result into `output`.
 pub fn keccak_256(input: &[u8], output: &mut [u8; 32]) {
     use ::sha3::{
@@ -15,7 +15,22 @@
 use super::{
     hashing,
     Account,
+    AccountError,
+    CodeRegistry,
+    ContractRestoration,
+    ContractReturned,
+    ContractStorage,
+    ContractTerminated,
+    ContractTermination,
+    DebugMessage,
+    DustPolicy,
+    EnvCall,
     EnvInstance,
+    GasCosts,
+    HostFunction,
+    OffAccountId,
+    OffBalance,
+    OffChainError,
 };
 use crate::env::{
     call::{
@@ -23,76 +38,308 @@ use crate::env::{
         CreateParams,
         utils::ReturnType,
     },
+    hash::{
+        Blake2x128,
+        Blake2x256,
+        CryptoHash,
+        HashOutput,
+        Keccak256,
+        Sha2x256,
+        Twox128,
+        Twox256,
+        Twox64,
+    },
     Env,
     EnvError,
     EnvTypes,
+    RentStatus,
     Result,
     ReturnFlags,
     Topics,
     TypedEnv,
 };
-use core::convert::TryInto;
+use core::convert::{
+    TryFrom,
+    TryInto,
+};
 use ink_primitives::Key;
 use num_traits::Bounded;
 
+/// Whether a transfer may leave the destination with dust.
+///
+/// Mirrors the `AllowDeath` and `KeepAlive` existence requirements of Substrate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ExistenceRequirement {
+    /// The destination may end up with a balance below the minimum balance.
+    AllowDeath,
+    /// The destination must end up with either no balance or at least the
+    /// minimum balance.
+    KeepAlive,
+}
+
 impl EnvInstance {
     /// Returns the callee account.
-    fn callee_account(&self) -> &Account {
-        let callee = self
-            .exec_context()
-            .expect("uninitialized execution context")
-            .callee
-            .clone();
+    ///
+    /// # Errors
+    ///
+    /// - If the execution context is uninitialized.
+    /// - If there is no account for the callee.
+    fn callee_account(&self) -> Result<&Account> {
+        let callee = self.exec_context()?.callee.clone();
         self.accounts
             .get_account_off(&callee)
-            .expect("callee account does not exist")
+            .ok_or(EnvError::AccountNotFound)
     }
 
     /// Returns the callee account as mutable reference.
-    fn callee_account_mut(&mut self) -> &mut Account {
-        let callee = self
-            .exec_context()
-            .expect("uninitialized execution context")
-            .callee
-            .clone();
+    ///
+    /// # Errors
+    ///
+    /// - If the execution context is uninitialized.
+    /// - If there is no account for the callee.
+    fn callee_account_mut(&mut self) -> Result<&mut Account> {
+        let callee = self.exec_context()?.callee.clone();
         self.accounts
             .get_account_off_mut(&callee)
-            .expect("callee account does not exist")
+            .ok_or(EnvError::AccountNotFound)
+    }
+
+    /// Charges the storage budget for writing the value under the key
+    /// of the callee's storage.
+    ///
+    /// Does nothing if there is no storage budget.
+    ///
+    /// # Errors
+    ///
+    /// - If the write would exceed the storage budget.
+    /// - If the callee account does not exist or is no contract.
+    fn charge_storage_budget<V>(&mut self, key: &Key, value: &V) -> Result<()>
+    where
+        V: scale::Encode,
+    {
+        if self.storage_budget.is_none() {
+            return Ok(())
+        }
+        let callee = self.callee_account()?;
+        let overwritten_size = callee.storage_entry_size(*key)?.unwrap_or(0) as usize;
+        let written_size = scale::Encode::encode(value).len();
+        let new_size = callee.storage_size()? - overwritten_size + written_size;
+        let budget = self
+            .storage_budget
+            .as_mut()
+            .expect("storage budget must exist at this point");
+        if !budget.try_write(new_size) {
+            return Err(EnvError::OutOfStorage)
+        }
+        Ok(())
+    }
+}
+
+impl EnvInstance {
+    /// Records the invocation of the env method if env calls are traced.
+    ///
+    /// The salient arguments are only encoded while tracing.
+    fn trace_env_call<F>(&mut self, name: &'static str, args: F)
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        if let Some(env_call_trace) = &mut self.env_call_trace {
+            env_call_trace.push(EnvCall::new(name, args()))
+        }
+    }
+
+    /// Returns the account ID of the executed contract.
+    fn callee_id<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.exec_context()
+            .expect("uninitialized execution context")
+            .callee::<T>()
+            .map_err(|_| scale::Error::from("could not decode callee"))
+            .map_err(Into::into)
+    }
+
+    /// Returns the balance of the executed contract.
+    fn callee_balance<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.callee_account()?
+            .balance::<T>()
+            .map_err(|_| scale::Error::from("could not decode callee balance"))
+            .map_err(Into::into)
+    }
+
+    /// Charges the gas costs selected from the gas costs of the gas meter.
+    ///
+    /// Does nothing if gas metering is disabled.
+    fn charge_gas<F>(&mut self, function: HostFunction, select_cost: F) -> Result<()>
+    where
+        F: FnOnce(&GasCosts) -> u64,
+    {
+        match &mut self.gas_meter {
+            Some(gas_meter) => {
+                let cost = select_cost(gas_meter.costs());
+                gas_meter.charge(function, cost)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Charges the gas costs for hashing.
+    ///
+    /// # Note
+    ///
+    /// The hash functions do not operate on the environmental instance
+    /// so we have to access it explicitly. This only happens if gas metering
+    /// is enabled. Hashing by environmental functions, e.g. by call handlers
+    /// or while decoding storage entries, is not charged.
+    ///
+    /// Since the hash functions cannot report running out of gas all gas
+    /// left is consumed instead so that the following metered operations
+    /// fail with [`EnvError::OutOfGas`].
+    fn charge_hashing_gas(function: HostFunction) {
+        if !Self::hashing_hooks().metered {
+            return
+        }
+        Self::try_on_instance(|instance| {
+            if let Some(gas_meter) = &mut instance.gas_meter {
+                let cost = gas_meter.costs().hashing;
+                gas_meter.charge_or_exhaust(function, cost)
+            }
+        });
+    }
+}
+
+impl CryptoHash for Sha2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashSha2x256);
+        hashing::sha2_256(input, output)
+    }
+}
+
+impl CryptoHash for Keccak256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashKeccak256);
+        hashing::keccak_256(input, output)
+    }
+}
+
+impl CryptoHash for Blake2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashBlake2x256);
+        hashing::blake2b_256(input, output)
+    }
+}
+
+impl CryptoHash for Blake2x128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashBlake2x128);
+        hashing::blake2b_128(input, output)
+    }
+}
+
+impl CryptoHash for Twox64 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashTwox64);
+        hashing::twox_64(input, output)
+    }
+}
+
+impl CryptoHash for Twox128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashTwox128);
+        hashing::twox_128(input, output)
+    }
+}
+
+impl CryptoHash for Twox256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        EnvInstance::charge_hashing_gas(HostFunction::HashTwox256);
+        hashing::twox_256(input, output)
     }
 }
 
 impl Env for EnvInstance {
-    fn set_contract_storage<V>(&mut self, key: &Key, value: &V)
+    fn set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Option<u32>
     where
         V: scale::Encode,
     {
+        self.trace_env_call("set_contract_storage", || scale::Encode::encode(key));
+        self.charge_gas(HostFunction::SetContractStorage, |costs| {
+            costs.storage_write
+        })
+        .expect("contract execution ran out of gas");
+        self.charge_storage_budget(key, value)
+            .expect("contract storage is out of space");
         self.callee_account_mut()
+            .expect("callee account does not exist")
+            .set_storage(*key, value)
+            .expect("callee account is not a smart contract")
+    }
+
+    fn try_set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Result<Option<u32>>
+    where
+        V: scale::Encode,
+    {
+        self.trace_env_call("try_set_contract_storage", || scale::Encode::encode(key));
+        self.charge_gas(HostFunction::SetContractStorage, |costs| {
+            costs.storage_write
+        })?;
+        self.charge_storage_budget(key, value)?;
+        self.callee_account_mut()?
             .set_storage(*key, value)
-            .expect("callee account is not a smart contract");
+            .map_err(Into::into)
     }
 
     fn get_contract_storage<R>(&mut self, key: &Key) -> Result<Option<R>>
     where
         R: scale::Decode,
     {
-        self.callee_account()
+        self.trace_env_call("get_contract_storage", || scale::Encode::encode(key));
+        self.charge_gas(HostFunction::GetContractStorage, |costs| costs.storage_read)?;
+        self.callee_account()?
             .get_storage::<R>(*key)
             .map_err(Into::into)
     }
 
-    fn clear_contract_storage(&mut self, key: &Key) {
+    fn contains_contract_storage(&mut self, key: &Key) -> Option<u32> {
+        self.trace_env_call("contains_contract_storage", || scale::Encode::encode(key));
+        self.charge_gas(HostFunction::ContainsContractStorage, |costs| {
+            costs.storage_read
+        })
+        .expect("contract execution ran out of gas");
+        self.callee_account()
+            .expect("callee account does not exist")
+            .contains_storage(*key)
+            .expect("callee account is not a smart contract")
+    }
+
+    fn clear_contract_storage(&mut self, key: &Key) -> Option<u32> {
+        self.trace_env_call("clear_contract_storage", || scale::Encode::encode(key));
+        self.charge_gas(HostFunction::ClearContractStorage, |costs| {
+            costs.storage_write
+        })
+        .expect("contract execution ran out of gas");
         self.callee_account_mut()
+            .expect("callee account does not exist")
             .clear_storage(*key)
-            .expect("callee account is not a smart contract");
+            .expect("callee account is not a smart contract")
     }
 
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
     {
+        self.trace_env_call("decode_input", Vec::new);
         self.exec_context()
-            .map(|exec_ctx| &exec_ctx.call_data)
-            .map(|call_data| scale::Encode::encode(call_data))
+            .map(|exec_ctx| {
+                match &exec_ctx.raw_call_data {
+                    Some(raw_call_data) => raw_call_data.clone(),
+                    None => scale::Encode::encode(&exec_ctx.call_data),
+                }
+            })
             .map_err(Into::into)
             .and_then(|encoded| {
                 <T as scale::Decode>::decode(&mut &encoded[..])
@@ -101,53 +348,355 @@ impl Env for EnvInstance {
             })
     }
 
+    fn with_input<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        self.trace_env_call("with_input", Vec::new);
+        let exec_ctx = self.exec_context().expect("uninitialized execution context");
+        match &exec_ctx.raw_call_data {
+            Some(raw_call_data) => f(raw_call_data),
+            None => f(&scale::Encode::encode(&exec_ctx.call_data)),
+        }
+    }
+
     fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
     {
-        let ctx = self
-            .exec_context_mut()
-            .expect("uninitialized execution context");
-        ctx.output = Some(return_value.encode());
-        std::process::exit(flags.into_u32() as i32)
+        let return_value = return_value.encode();
+        self.trace_env_call("return_value", || {
+            scale::Encode::encode(&(flags.into_u32(), &return_value))
+        });
+        self.return_and_exit(flags, &return_value)
     }
 
-    fn println(&mut self, content: &str) {
-        self.console.println(content)
+    fn return_raw(&mut self, flags: ReturnFlags, return_value: &[u8]) -> ! {
+        self.trace_env_call("return_raw", || {
+            scale::Encode::encode(&(flags.into_u32(), return_value))
+        });
+        self.return_and_exit(flags, return_value)
     }
 
-    fn hash_keccak_256(input: &[u8], output: &mut [u8; 32]) {
-        hashing::keccak_256(input, output)
+    fn is_read_only(&mut self) -> bool {
+        self.trace_env_call("is_read_only", Vec::new);
+        self.exec_context()
+            .expect("uninitialized execution context")
+            .read_only
     }
 
-    fn hash_blake2_256(input: &[u8], output: &mut [u8; 32]) {
-        hashing::blake2b_256(input, output)
+    fn println(&mut self, content: &str) {
+        self.trace_env_call("println", || scale::Encode::encode(content));
+        self.console.println(content)
     }
 
-    fn hash_blake2_128(input: &[u8], output: &mut [u8; 16]) {
-        hashing::blake2b_128(input, output)
+    fn debug_message(&mut self, message: &str) {
+        self.trace_env_call("debug_message", || scale::Encode::encode(message));
+        let block_number = self
+            .current_block()
+            .expect("uninitialized blocks")
+            .encoded_number()
+            .clone();
+        let caller = self
+            .exec_context()
+            .expect("uninitialized execution context")
+            .caller
+            .clone();
+        self.console
+            .record_debug_message(DebugMessage::new(message, block_number, caller))
     }
 
-    fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]) {
-        hashing::sha2_256(input, output)
+    fn hash_bytes<H>(input: &[u8], output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash,
+    {
+        // Hashing does not operate on the environmental instance so we have
+        // to access it explicitly if it is traced or has overridden outputs.
+        let hash_override = if Self::hashing_hooks().inspected {
+            Self::try_on_instance(|instance| {
+                let hash_fn = core::any::type_name::<H>();
+                instance.trace_env_call("hash_bytes", || {
+                    scale::Encode::encode(&(hash_fn, input))
+                });
+                instance
+                    .hash_overrides
+                    .get(&(hash_fn, input.to_vec()))
+                    .cloned()
+            })
+            .flatten()
+        } else {
+            None
+        };
+        match hash_override {
+            Some(hash_override) => output.as_mut().copy_from_slice(&hash_override),
+            None => <H as CryptoHash>::hash(input, output),
+        }
     }
 
-    #[cfg(feature = "ink-unstable-chain-extensions")]
     fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
     where
         I: scale::Codec + 'static,
         O: scale::Codec + 'static,
     {
+        self.trace_env_call("call_chain_extension", || {
+            scale::Encode::encode(&(func_id, input))
+        });
         self.chain_extension_handler.eval(func_id, input)
     }
+
+    fn call_chain_extension_with_status<I, O>(
+        &mut self,
+        func_id: u32,
+        input: &I,
+    ) -> Result<(u32, O)>
+    where
+        I: scale::Codec + 'static,
+        O: scale::Codec + 'static,
+    {
+        self.trace_env_call("call_chain_extension_with_status", || {
+            scale::Encode::encode(&(func_id, input))
+        });
+        self.chain_extension_handler.eval_with_status(func_id, input)
+    }
 }
 
 impl EnvInstance {
-    fn transfer_impl<T>(&mut self, destination: T::AccountId, value: T::Balance) -> Result<()>
+    /// Reaps the account if its balance is below the minimum balance.
+    ///
+    /// # Note
+    ///
+    /// Does nothing unless reaping has been enabled in the chain
+    /// specification. The dust of the reaped account is either burned
+    /// or sent to the treasury account.
+    fn reap_if_below_minimum_balance<T>(
+        &mut self,
+        account_id: &T::AccountId,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        use crate::env::arithmetic::Saturating as _;
+        let dust_policy = match self.chain_spec.reaping() {
+            Some(dust_policy) => dust_policy.clone(),
+            None => return Ok(()),
+        };
+        let minimum_balance = self.chain_spec.minimum_balance::<T>()?;
+        let dust = match self.accounts.get_account::<T>(account_id) {
+            Some(account) => account.balance::<T>()?,
+            None => return Ok(()),
+        };
+        if dust >= minimum_balance {
+            return Ok(())
+        }
+        self.accounts.remove_account::<T>(account_id);
+        if let DustPolicy::Treasury(treasury) = dust_policy {
+            let treasury = treasury.decode::<T::AccountId>()?;
+            let treasury = self.accounts.get_or_create_account::<T>(&treasury);
+            let balance = treasury.balance::<T>()?;
+            treasury.set_balance::<T>(balance.saturating_add(dust))?;
+        }
+        Ok(())
+    }
+
+    /// Emulates the call to another contract and returns its encoded result.
+    ///
+    /// # Errors
+    ///
+    /// - If no response has been registered for the called message.
+    /// - If the callee consumes more gas than the gas limit of the call.
+    /// - If the registered handler of the called message panics.
+    fn call_contract_impl<T, Args, R>(
+        &mut self,
+        call_params: &CallParams<T, Args, R>,
+    ) -> Result<Vec<u8>>
     where
         T: EnvTypes,
+        Args: scale::Encode,
     {
-        let src_id = self.account_id::<T>()?;
+        let callee = call_params.callee();
+        let selector = call_params.exec_input().selector();
+        // Registered call responses take precedence over the handlers of
+        // contracts instantiated from uploaded code which consume no gas.
+        let gas = match self.call_responses.get_mut::<T>(callee, selector) {
+            Some((_, gas)) => gas,
+            None if self.code_registry.instance_mut::<T>(callee).is_some() => 0,
+            None => return Err(OffChainError::UnregisteredCallResponse.into()),
+        };
+        // A gas limit of zero lets the callee use all of the gas that is left.
+        let gas_limit = call_params.gas_limit();
+        if gas_limit != 0 && gas > gas_limit {
+            // The callee consumes its whole gas budget before it runs out of gas.
+            self.charge_gas(HostFunction::CallContract, |_| gas_limit)?;
+            return Err(EnvError::OutOfGas)
+        }
+        self.charge_gas(HostFunction::CallContract, |_| gas)?;
+        let input = scale::Encode::encode(call_params.exec_input());
+        let response = match self.call_responses.get_mut::<T>(callee, selector) {
+            Some((response, _)) => response.clone(),
+            None => {
+                self.code_registry
+                    .instance_mut::<T>(callee)
+                    .expect("encountered missing contract instance")
+                    .clone()
+            }
+        };
+        // Handlers may call back into the environment, e.g. in order to
+        // reenter the calling contract.
+        self.with_released(|| response.respond(&input))
+    }
+
+    /// Instantiates a contract from the uploaded code.
+    fn instantiate_contract_impl<T, Args, C>(
+        &mut self,
+        params: &CreateParams<T, Args, C>,
+    ) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+    {
+        let code = self.code_registry.code::<T>(params.code_hash())?;
+        let creator = self.callee_id::<T>()?;
+        let creator_account = self
+            .accounts
+            .get_account::<T>(&creator)
+            .expect("account of executed contract must exist");
+        let creator_balance = creator_account.balance::<T>()?;
+        if creator_balance < *params.endowment() {
+            return Err(EnvError::TransferFailed)
+        }
+        let account_id = Self::derive_contract_account_id::<T>(
+            params.code_hash(),
+            &creator,
+            creator_account.nonce(),
+            params.salt_bytes(),
+        )?;
+        if self.accounts.get_account::<T>(&account_id).is_some() {
+            return Err(OffChainError::DuplicateContract.into())
+        }
+        let input = scale::Encode::encode(params.exec_input());
+        self.accounts.add_contract_account::<T>(
+            account_id.clone(),
+            T::Balance::from(0),
+            T::Balance::from(0),
+        );
+        self.accounts
+            .get_account_mut::<T>(&account_id)
+            .expect("instantiated contract account must exist")
+            .set_code_hash::<T>(*params.code_hash())?;
+        // The endowment is transferred before the constructor runs. A failed
+        // deployment is rolled back as it is on-chain so that the creator
+        // keeps its endowment and no contract account is left behind. The
+        // transfer hook only observes the endowment of successful deployments.
+        let deployed = self
+            .transfer_balance::<T>(
+                account_id.clone(),
+                *params.endowment(),
+                ExistenceRequirement::AllowDeath,
+            )
+            .and_then(|_| {
+                // Constructors may call back into the environment just like
+                // the handlers of the instantiated contracts.
+                self.with_released(|| CodeRegistry::construct(&code, &input))
+            })
+            .map(|handler| {
+                self.code_registry
+                    .add_instance::<T>(&account_id, handler)
+            });
+        if let Err(error) = deployed {
+            self.accounts.remove_account::<T>(&account_id);
+            self.accounts
+                .get_account_mut::<T>(&creator)
+                .expect("account of executed contract must exist")
+                .set_balance::<T>(creator_balance)?;
+            return Err(error)
+        }
+        self.invoke_transfer_hook::<T>(&account_id, *params.endowment())?;
+        self.accounts
+            .get_account_mut::<T>(&creator)
+            .expect("account of executed contract must exist")
+            .inc_nonce();
+        Ok(account_id)
+    }
+
+    /// Runs the cross-contract call or instantiation one level deeper in the
+    /// call stack.
+    ///
+    /// # Note
+    ///
+    /// Execution contexts pushed to emulate nested contract executions count
+    /// towards the depth of the call stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EnvError::CallStackExhausted`] without running `f` if the call
+    /// would exceed the maximum call depth of the chain.
+    fn nested_call<F, R>(&mut self, f: F) -> Result<R>
+    where
+        F: FnOnce(&mut Self) -> Result<R>,
+    {
+        let depth = self.exec_context.len() as u32 + self.call_depth;
+        if depth >= self.chain_spec.max_call_depth() {
+            return Err(EnvError::CallStackExhausted)
+        }
+        self.call_depth += 1;
+        let result = f(self);
+        self.call_depth -= 1;
+        result
+    }
+
+    /// Transfers value from the executed contract to the destination and
+    /// charges the gas for it.
+    ///
+    /// Reaps the executed contract if it is left below the minimum balance.
+    fn transfer_metered<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+        requirement: ExistenceRequirement,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.charge_gas(HostFunction::Transfer, |costs| costs.transfer)?;
+        self.transfer_impl::<T>(destination, value, requirement)?;
+        let account_id = self.callee_id::<T>()?;
+        self.accounts
+            .get_account_mut::<T>(&account_id)
+            .expect("account of executed contract must exist")
+            .inc_nonce();
+        self.reap_if_below_minimum_balance::<T>(&account_id)?;
+        Ok(())
+    }
+
+    /// Transfers value from the executed contract to the destination and
+    /// invokes the transfer hook.
+    fn transfer_impl<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+        requirement: ExistenceRequirement,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.transfer_balance::<T>(destination.clone(), value, requirement)?;
+        self.invoke_transfer_hook::<T>(&destination, value)
+    }
+
+    /// Transfers value from the executed contract to the destination without
+    /// invoking the transfer hook.
+    fn transfer_balance<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+        requirement: ExistenceRequirement,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        use crate::env::arithmetic::Saturating as _;
+        use num_traits::Bounded as _;
+        let src_id = self.callee_id::<T>()?;
         let src_value = self
             .accounts
             .get_account::<T>(&src_id)
@@ -156,24 +705,203 @@ impl EnvInstance {
         if src_value < value {
             return Err(EnvError::TransferFailed)
         }
-        let dst_value = self
+        // Transferring to the source account itself leaves its balance
+        // unchanged. Updating both sides would make the second write
+        // clobber the first.
+        if destination != src_id {
+            let dst_value = self
+                .accounts
+                .get_or_create_account::<T>(&destination)
+                .balance::<T>()?;
+            // The destination balance must not overflow regardless of the
+            // width of the balance type.
+            if T::Balance::max_value() - dst_value < value {
+                return Err(EnvError::TransferFailed)
+            }
+            // Dust below the minimum balance would be reaped and thus lost.
+            let new_dst_value = dst_value.saturating_add(value);
+            if requirement == ExistenceRequirement::KeepAlive
+                && new_dst_value != T::Balance::from(0)
+                && new_dst_value < self.chain_spec.minimum_balance::<T>()?
+            {
+                return Err(EnvError::BelowMinimumBalance)
+            }
+            self.accounts
+                .get_account_mut::<T>(&src_id)
+                .expect("account of executed contract must exist")
+                .set_balance::<T>(src_value.saturating_sub(value))?;
+            self.accounts
+                .get_account_mut::<T>(&destination)
+                .expect("the account must exist already or has just been created")
+                .set_balance::<T>(new_dst_value)?;
+        }
+        Ok(())
+    }
+
+    /// Invokes the transfer hook, if any, with the transfer of value from the
+    /// executed contract to the destination.
+    fn invoke_transfer_hook<T>(
+        &mut self,
+        destination: &T::AccountId,
+        value: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        let src_id = self.callee_id::<T>()?;
+        if let Some(transfer_hook) = &mut self.transfer_hook {
+            transfer_hook(
+                &OffAccountId::new(&src_id),
+                &OffAccountId::new(destination),
+                &OffBalance::new(&value),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl EnvInstance {
+    /// Stores the given bytes verbatim as output of the executed contract
+    /// without diverging.
+    pub(super) fn return_raw_impl(&mut self, return_value: &[u8]) -> Result<()> {
+        self.exec_context_mut()?.output = Some(return_value.to_vec());
+        Ok(())
+    }
+
+    /// Stores the given bytes as output of the executed contract and exits.
+    ///
+    /// Unwinds instead of exiting the process while returns are caught.
+    fn return_and_exit(&mut self, flags: ReturnFlags, return_value: &[u8]) -> ! {
+        self.return_raw_impl(return_value)
+            .expect("uninitialized execution context");
+        if self.catches_returns {
+            std::panic::resume_unwind(Box::new(ContractReturned(flags)))
+        }
+        std::process::exit(flags.into_u32() as i32)
+    }
+
+    /// Terminates the executed contract without diverging.
+    ///
+    /// Transfers the whole balance of the executed contract to the beneficiary,
+    /// removes its storage and records the termination for later inspection.
+    fn terminate_contract_impl<T>(
+        &mut self,
+        beneficiary: T::AccountId,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        let contract = self.callee_id::<T>()?;
+        let remaining = self.callee_balance::<T>()?;
+        self.transfer_impl::<T>(
+            beneficiary.clone(),
+            remaining,
+            ExistenceRequirement::AllowDeath,
+        )?;
+        self.callee_account_mut()?.clear_all_storage()?;
+        self.terminations.push(ContractTermination::new::<T>(
+            &contract,
+            &beneficiary,
+            remaining,
+        ));
+        Ok(())
+    }
+
+    /// Restores the evicted contract from the storage of the executed contract.
+    ///
+    /// # Errors
+    ///
+    /// - If there is no tombstone for the account ID.
+    /// - If the storage of the executed contract without the filtered keys
+    ///   together with the code hash does not match the tombstone hash.
+    fn restore_contract_impl<T>(
+        &mut self,
+        account_id: T::AccountId,
+        code_hash: T::Hash,
+        rent_allowance: T::Balance,
+        filtered_keys: &[Key],
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        let tombstone_hash = self
             .accounts
-            .get_or_create_account::<T>(&destination)
-            .balance::<T>()?;
-        self.accounts
-            .get_account_mut::<T>(&src_id)
-            .expect("account of executed contract must exist")
-            .set_balance::<T>(src_value - value)?;
+            .get_account::<T>(&account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))?
+            .tombstone_hash()?;
+        let storage = self
+            .callee_account()?
+            .storage()?
+            .filtered(filtered_keys);
+        if Self::tombstone_hash::<T>(&storage, &code_hash) != tombstone_hash {
+            return Err(OffChainError::TombstoneMismatch.into())
+        }
         self.accounts
-            .get_account_mut::<T>(&destination)
-            .expect("the account must exist already or has just been created")
-            .set_balance::<T>(dst_value + value)?;
+            .get_account_mut::<T>(&account_id)
+            .expect("just checked that the tombstone exists")
+            .revive::<T>(code_hash, rent_allowance, storage)?;
         Ok(())
     }
+
+    /// Returns the tombstone hash of the contract storage and code hash.
+    pub(super) fn tombstone_hash<T>(storage: &ContractStorage, code_hash: &T::Hash) -> [u8; 32]
+    where
+        T: EnvTypes,
+    {
+        let entries = storage.iter().collect::<Vec<_>>();
+        let mut output = [0x00; 32];
+        hashing::blake2b_256(&scale::Encode::encode(&(entries, code_hash)), &mut output);
+        output
+    }
+
+    /// Returns the encoded callee, transferred value and input of the call.
+    fn encode_call_params<T, Args, R>(call_params: &CallParams<T, Args, R>) -> Vec<u8>
+    where
+        T: EnvTypes,
+        Args: scale::Encode,
+    {
+        scale::Encode::encode(&(
+            call_params.callee(),
+            call_params.transferred_value(),
+            call_params.exec_input(),
+        ))
+    }
+
+    /// Derives the account ID of a contract instantiated by the creator.
+    ///
+    /// # Note
+    ///
+    /// The account ID is the BLAKE2 hash of the code hash, the creator and
+    /// the creator's nonce so that it is deterministic across test runs
+    /// but distinct for every instantiation of the same creator.
+    ///
+    /// If a non-empty salt is given it replaces the nonce so that the account
+    /// ID only depends on the code hash, the creator and the salt.
+    fn derive_contract_account_id<T>(
+        code_hash: &T::Hash,
+        creator: &T::AccountId,
+        nonce: u64,
+        salt: &[u8],
+    ) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        let mut output = [0x00; 32];
+        let subject = if salt.is_empty() {
+            scale::Encode::encode(&(code_hash, creator, nonce))
+        } else {
+            scale::Encode::encode(&(code_hash, creator, salt))
+        };
+        hashing::blake2b_256(&subject, &mut output);
+        <T::AccountId as scale::Decode>::decode(&mut &output[..])
+            .map_err(|_| scale::Error::from("could not decode derived account id"))
+            .map_err(Into::into)
+    }
 }
 
 impl TypedEnv for EnvInstance {
     fn caller<T: EnvTypes>(&mut self) -> Result<T::AccountId> {
+        self.trace_env_call("caller", Vec::new);
         self.exec_context()
             .expect("uninitialized execution context")
             .caller::<T>()
@@ -181,7 +909,26 @@ impl TypedEnv for EnvInstance {
             .map_err(Into::into)
     }
 
+    fn caller_is_origin<T: EnvTypes>(&mut self) -> Result<bool> {
+        self.trace_env_call("caller_is_origin", Vec::new);
+        self.exec_context()
+            .map(|exec_context| !exec_context.caller_is_contract)
+            .map_err(Into::into)
+    }
+
+    fn caller_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.trace_env_call("caller_balance", Vec::new);
+        let caller = self.exec_context()?.caller.clone();
+        self.accounts
+            .get_account_off(&caller)
+            .ok_or(EnvError::AccountNotFound)?
+            .balance::<T>()
+            .map_err(|_| scale::Error::from("could not decode caller balance"))
+            .map_err(Into::into)
+    }
+
     fn transferred_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.trace_env_call("transferred_balance", Vec::new);
         self.exec_context()
             .expect("uninitialized execution context")
             .transferred_value::<T>()
@@ -192,7 +939,11 @@ impl TypedEnv for EnvInstance {
     /// Emulates gas price calculation
     fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance> {
         use crate::env::arithmetic::Saturating as _;
+        self.trace_env_call("weight_to_fee", || scale::Encode::encode(&gas));
 
+        if let Some(fee) = self.chain_spec.weight_to_fee::<T>(gas) {
+            return fee.map_err(Into::into)
+        }
         let gas_price = self
             .chain_spec
             .gas_price::<T>()
@@ -202,7 +953,38 @@ impl TypedEnv for EnvInstance {
             .saturating_mul(gas.try_into().unwrap_or_else(|_| Bounded::max_value())))
     }
 
+    fn weight_to_fee_v2<T: EnvTypes>(
+        &mut self,
+        ref_time: u64,
+        proof_size: u64,
+    ) -> Result<T::Balance> {
+        use crate::env::arithmetic::Saturating as _;
+        self.trace_env_call("weight_to_fee_v2", || {
+            scale::Encode::encode(&(ref_time, proof_size))
+        });
+        let ref_time_price = self
+            .chain_spec
+            .ref_time_price::<T>()
+            .map_err(|_| scale::Error::from("could not decode ref time price"))?;
+        let proof_size_price = self
+            .chain_spec
+            .proof_size_price::<T>()
+            .map_err(|_| scale::Error::from("could not decode proof size price"))?;
+        let to_balance = |weight: u64| -> T::Balance {
+            weight.try_into().unwrap_or_else(|_| Bounded::max_value())
+        };
+        Ok(ref_time_price
+            .saturating_mul(to_balance(ref_time))
+            .saturating_add(proof_size_price.saturating_mul(to_balance(proof_size))))
+    }
+
     fn gas_left<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.trace_env_call("gas_left", Vec::new);
+        if let Some(gas_meter) = &self.gas_meter {
+            return T::Balance::try_from(gas_meter.gas_left())
+                .map_err(|_| scale::Error::from("could not convert gas left"))
+                .map_err(Into::into)
+        }
         self.exec_context()
             .expect("uninitialized execution context")
             .gas::<T>()
@@ -210,7 +992,18 @@ impl TypedEnv for EnvInstance {
             .map_err(Into::into)
     }
 
+    fn account_nonce<T: EnvTypes>(&mut self) -> Result<u64> {
+        self.trace_env_call("account_nonce", Vec::new);
+        let account_id = self.account_id::<T>()?;
+        self.accounts
+            .get_account::<T>(&account_id)
+            .map(Account::nonce)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))
+            .map_err(Into::into)
+    }
+
     fn block_timestamp<T: EnvTypes>(&mut self) -> Result<T::Timestamp> {
+        self.trace_env_call("block_timestamp", Vec::new);
         self.current_block()
             .expect("uninitialized execution context")
             .timestamp::<T>()
@@ -219,28 +1012,32 @@ impl TypedEnv for EnvInstance {
     }
 
     fn account_id<T: EnvTypes>(&mut self) -> Result<T::AccountId> {
-        self.exec_context()
-            .expect("uninitialized execution context")
-            .callee::<T>()
-            .map_err(|_| scale::Error::from("could not decode callee"))
+        self.trace_env_call("account_id", Vec::new);
+        self.callee_id::<T>()
+    }
+
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash> {
+        self.trace_env_call("own_code_hash", Vec::new);
+        self.callee_account()?
+            .code_hash::<T>()
             .map_err(Into::into)
     }
 
     fn balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
-        self.callee_account()
-            .balance::<T>()
-            .map_err(|_| scale::Error::from("could not decode callee balance"))
-            .map_err(Into::into)
+        self.trace_env_call("balance", Vec::new);
+        self.callee_balance::<T>()
     }
 
     fn rent_allowance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
-        self.callee_account()
+        self.trace_env_call("rent_allowance", Vec::new);
+        self.callee_account()?
             .rent_allowance::<T>()
             .map_err(|_| scale::Error::from("could not decode callee rent allowance"))
             .map_err(Into::into)
     }
 
     fn block_number<T: EnvTypes>(&mut self) -> Result<T::BlockNumber> {
+        self.trace_env_call("block_number", Vec::new);
         self.current_block()
             .expect("uninitialized execution context")
             .number::<T>()
@@ -249,6 +1046,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn minimum_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.trace_env_call("minimum_balance", Vec::new);
         self.chain_spec
             .minimum_balance::<T>()
             .map_err(|_| scale::Error::from("could not decode minimum balance"))
@@ -256,93 +1054,232 @@ impl TypedEnv for EnvInstance {
     }
 
     fn tombstone_deposit<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.trace_env_call("tombstone_deposit", Vec::new);
         self.chain_spec
             .tombstone_deposit::<T>()
             .map_err(|_| scale::Error::from("could not decode tombstone deposit"))
             .map_err(Into::into)
     }
 
+    fn rent_status<T: EnvTypes>(&mut self) -> Result<RentStatus<T>> {
+        use crate::env::arithmetic::Saturating as _;
+        self.trace_env_call("rent_status", Vec::new);
+        let tombstone_deposit = self.chain_spec.tombstone_deposit::<T>()?;
+        let rent_byte_price = self.chain_spec.rent_byte_price::<T>()?;
+        let callee = self.callee_account()?;
+        let balance = callee.balance::<T>()?;
+        let max_rent = callee.rent_allowance::<T>()?;
+        let storage_size = callee.storage_size()? as u32;
+        let current_rent =
+            rent_byte_price.saturating_mul(T::Balance::from(storage_size));
+        Ok(RentStatus {
+            max_rent,
+            current_rent,
+            storage_size,
+            below_deposit: balance < tombstone_deposit.saturating_add(current_rent),
+        })
+    }
+
     fn emit_event<T, Event>(&mut self, new_event: Event)
     where
         T: EnvTypes,
         Event: Topics<T> + scale::Encode,
     {
-        self.emitted_events.record::<T, Event>(new_event)
+        self.trace_env_call("emit_event", || scale::Encode::encode(&new_event));
+        let exec_context = self
+            .exec_context_mut()
+            .expect("uninitialized execution context");
+        let call_index = exec_context.emitted_events;
+        let during_instantiation = exec_context.is_instantiation;
+        exec_context.emitted_events += 1;
+        self.emitted_events.record::<T, Event>(
+            new_event,
+            call_index,
+            during_instantiation,
+            self.chain_spec.max_event_topics(),
+            self.chain_spec.max_event_size(),
+        )
     }
 
     fn set_rent_allowance<T>(&mut self, new_rent_allowance: T::Balance)
     where
         T: EnvTypes,
     {
+        self.trace_env_call("set_rent_allowance", || {
+            scale::Encode::encode(&new_rent_allowance)
+        });
         self.callee_account_mut()
+            .expect("callee account does not exist")
             .set_rent_allowance::<T>(new_rent_allowance)
             .expect("could not encode rent allowance")
     }
 
     fn invoke_contract<T, Args>(
         &mut self,
-        _call_params: &CallParams<T, Args, ()>,
+        call_params: &CallParams<T, Args, ()>,
     ) -> Result<()>
     where
         T: EnvTypes,
         Args: scale::Encode,
     {
-        unimplemented!("off-chain environment does not support contract invokation")
+        self.trace_env_call("invoke_contract", || {
+            EnvInstance::encode_call_params(call_params)
+        });
+        self.nested_call(|instance| instance.call_contract_impl(call_params))
+            .map(|_| ())
     }
 
     fn eval_contract<T, Args, R>(
         &mut self,
-        _call_params: &CallParams<T, Args, ReturnType<R>>,
+        call_params: &CallParams<T, Args, ReturnType<R>>,
     ) -> Result<R>
     where
         T: EnvTypes,
         Args: scale::Encode,
         R: scale::Decode,
     {
-        unimplemented!("off-chain environment does not support contract evaluation")
+        self.trace_env_call("eval_contract", || {
+            EnvInstance::encode_call_params(call_params)
+        });
+        let encoded =
+            self.nested_call(|instance| instance.call_contract_impl(call_params))?;
+        <R as scale::Decode>::decode(&mut &encoded[..])
+            .map_err(|_| scale::Error::from("could not decode call response"))
+            .map_err(Into::into)
     }
 
     fn instantiate_contract<T, Args, C>(
         &mut self,
-        _params: &CreateParams<T, Args, C>,
+        params: &CreateParams<T, Args, C>,
     ) -> Result<T::AccountId>
     where
         T: EnvTypes,
         Args: scale::Encode,
     {
-        unimplemented!("off-chain environment does not support contract instantiation")
+        self.trace_env_call("instantiate_contract", || {
+            scale::Encode::encode(&(
+                params.code_hash(),
+                params.endowment(),
+                params.exec_input(),
+                params.salt_bytes(),
+            ))
+        });
+        self.nested_call(|instance| instance.instantiate_contract_impl(params))
     }
 
-    fn terminate_contract<T>(&mut self, _beneficiary: T::AccountId) -> !
+    fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
     where
         T: EnvTypes,
     {
-        unimplemented!("off-chain environment does not support contract termination")
+        self.trace_env_call("terminate_contract", || scale::Encode::encode(&beneficiary));
+        self.terminate_contract_impl::<T>(beneficiary)
+            .expect("could not terminate the executed contract");
+        if self.catches_returns {
+            std::panic::resume_unwind(Box::new(ContractTerminated))
+        }
+        std::process::exit(0)
     }
 
     fn restore_contract<T>(
         &mut self,
-        _account_id: T::AccountId,
-        _code_hash: T::Hash,
-        _rent_allowance: T::Balance,
-        _filtered_keys: &[Key],
+        account_id: T::AccountId,
+        code_hash: T::Hash,
+        rent_allowance: T::Balance,
+        filtered_keys: &[Key],
     ) where
         T: EnvTypes,
     {
-        unimplemented!("off-chain environment does not support contract restoration")
+        self.trace_env_call("restore_contract", || {
+            scale::Encode::encode(&(
+                &account_id,
+                &code_hash,
+                &rent_allowance,
+                filtered_keys,
+            ))
+        });
+        // Restoration is silently cancelled upon failure as it is on-chain.
+        // The outcome is recorded so that tests can inspect failures.
+        let outcome = self.restore_contract_impl::<T>(
+            account_id.clone(),
+            code_hash,
+            rent_allowance,
+            filtered_keys,
+        );
+        self.restorations
+            .push(ContractRestoration::new::<T>(&account_id, outcome));
+    }
+
+    fn set_code_hash<T>(&mut self, code_hash: T::Hash) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.trace_env_call("set_code_hash", || scale::Encode::encode(&code_hash));
+        if !self.code_registry.contains::<T>(&code_hash) {
+            return Err(EnvError::CodeNotFound)
+        }
+        let account_id = self.callee_id::<T>()?;
+        // Only contract accounts have code that can be replaced.
+        self.callee_account()?.code_hash::<T>()?;
+        // The code hash is only replaced once the new handler is in place so
+        // that a failed upgrade leaves the contract untouched.
+        self.code_registry.upgrade::<T>(&code_hash, &account_id)?;
+        self.callee_account_mut()?.set_code_hash::<T>(code_hash)?;
+        Ok(())
     }
 
     fn transfer<T>(&mut self, destination: T::AccountId, value: T::Balance) -> Result<()>
     where
         T: EnvTypes,
     {
-        self.transfer_impl::<T>(destination, value)
+        self.trace_env_call("transfer", || scale::Encode::encode(&(&destination, value)));
+        self.transfer_metered::<T>(destination, value, ExistenceRequirement::AllowDeath)
+    }
+
+    fn transfer_keep_alive<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.trace_env_call("transfer_keep_alive", || {
+            scale::Encode::encode(&(&destination, value))
+        });
+        self.transfer_metered::<T>(destination, value, ExistenceRequirement::KeepAlive)
+    }
+
+    fn transfer_saturating<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.trace_env_call("transfer_saturating", || {
+            scale::Encode::encode(&(&destination, value))
+        });
+        let available = self.callee_balance::<T>()?;
+        let value = if value < available { value } else { available };
+        self.transfer_metered::<T>(destination, value, ExistenceRequirement::AllowDeath)?;
+        Ok(value)
+    }
+
+    fn call_runtime<C>(&mut self, call: &C) -> Result<()>
+    where
+        C: scale::Encode,
+    {
+        self.trace_env_call("call_runtime", || scale::Encode::encode(call));
+        let origin = self.exec_context()?.callee.clone();
+        self.runtime_call_handler.eval(&mut self.accounts, &origin, call)
     }
 
     fn random<T>(&mut self, subject: &[u8]) -> Result<T::Hash>
     where
         T: EnvTypes,
     {
+        self.trace_env_call("random", || scale::Encode::encode(subject));
         self.current_block()
             .expect("uninitialized execution context")
             .random::<T>(subject)
@@ -18,15 +18,18 @@ mod hashing;
 mod impls;
 pub mod test_api;
 mod typed_encoded;
-#[cfg(feature = "ink-unstable-chain-extensions")]
 mod chain_extension;
+mod runtime_call;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
 pub use self::{
-    call_data::CallData,
+    call_data::{
+        encode_call,
+        CallData,
+    },
     db::{
         AccountError,
         EmittedEvent,
@@ -39,10 +42,22 @@ use self::{
         Account,
         AccountsDb,
         Block,
+        CallResponses,
         ChainSpec,
+        CodeRegistry,
         Console,
+        ContractRestoration,
+        ContractStorage,
+        ContractTermination,
+        DebugMessage,
+        DustPolicy,
         EmittedEventsRecorder,
+        EnvCall,
         ExecContext,
+        GasCosts,
+        GasMeter,
+        HostFunction,
+        StorageBudget,
     },
     typed_encoded::TypedEncoded,
     types::{
@@ -53,12 +68,21 @@ use self::{
         OffTimestamp,
     },
 };
-#[cfg(feature = "ink-unstable-chain-extensions")]
-use self::chain_extension::ChainExtensionHandler;
+use self::{
+    chain_extension::ChainExtensionHandler,
+    runtime_call::RuntimeCallHandler,
+};
 use super::OnInstance;
 use crate::env::EnvTypes;
-use core::cell::RefCell;
+use core::cell::{
+    Cell,
+    RefCell,
+};
 use derive_more::From;
+use std::{
+    collections::HashMap,
+    rc::Rc,
+};
 
 #[derive(Debug, From, PartialEq, Eq)]
 pub enum OffChainError {
@@ -69,11 +93,37 @@ pub enum OffChainError {
     #[from(ignore)]
     UninitializedExecutionContext,
     #[from(ignore)]
-    UnregisteredChainExtension,
+    UnregisteredChainExtension(u32),
+    #[from(ignore)]
+    UnregisteredRuntimeCall(&'static str),
+    #[from(ignore)]
+    UnregisteredCallResponse,
+    #[from(ignore)]
+    TombstoneMismatch,
+    #[from(ignore)]
+    DuplicateContract,
+    #[from(ignore)]
+    CodeNotUpgradable,
 }
 
 pub type Result<T> = core::result::Result<T, OffChainError>;
 
+/// A hook that is invoked with the sender, the receiver and the value of
+/// every successful transfer.
+type TransferHook = Box<dyn FnMut(&OffAccountId, &OffAccountId, &OffBalance)>;
+
+/// A hook that is invoked with the events emitted in a block whenever the
+/// block is finished by advancing the chain.
+type BlockFinalizationHook = Box<dyn FnMut(&[EmittedEvent])>;
+
+/// The unwinding payload of a contract returning a value while returns are
+/// being caught via [`test_api::catch_return`].
+struct ContractReturned(crate::env::ReturnFlags);
+
+/// The unwinding payload of a contract terminating itself while returns are
+/// being caught via [`test_api::catch_return`].
+struct ContractTerminated;
+
 /// The off-chain environment.
 ///
 /// Mainly used for off-chain testing.
@@ -89,10 +139,35 @@ pub struct EnvInstance {
     /// The console to print debug contents.
     console: Console,
     /// Handler for registered chain extensions.
-    #[cfg(feature = "ink-unstable-chain-extensions")]
     chain_extension_handler: ChainExtensionHandler,
+    /// Handler for registered runtime calls.
+    runtime_call_handler: RuntimeCallHandler,
     /// Emitted events recorder.
     emitted_events: EmittedEventsRecorder,
+    /// Registered return values of emulated cross-contract calls.
+    call_responses: CallResponses,
+    /// Uploaded code and the contracts instantiated from it.
+    code_registry: CodeRegistry,
+    /// Recorded terminations of contracts.
+    terminations: Vec<ContractTermination>,
+    /// Recorded attempts to restore evicted contracts.
+    restorations: Vec<ContractRestoration>,
+    /// The gas meter if gas metering is enabled.
+    gas_meter: Option<GasMeter>,
+    /// The budget of the contract storage if any.
+    storage_budget: Option<StorageBudget>,
+    /// The hook invoked on every successful transfer if any.
+    transfer_hook: Option<TransferHook>,
+    /// The hook invoked whenever a block is finished if any.
+    block_finalization_hook: Option<BlockFinalizationHook>,
+    /// If returning a value unwinds instead of exiting the process.
+    catches_returns: bool,
+    /// The number of currently running cross-contract calls and instantiations.
+    call_depth: u32,
+    /// The recorded invocations of env methods if env calls are traced.
+    env_call_trace: Option<Vec<EnvCall>>,
+    /// The pre-set hash outputs keyed by the hash function name and the input.
+    hash_overrides: HashMap<(&'static str, Vec<u8>), Vec<u8>>,
 }
 
 impl EnvInstance {
@@ -104,9 +179,21 @@ impl EnvInstance {
             chain_spec: ChainSpec::uninitialized(),
             blocks: Vec::new(),
             console: Console::new(),
-            #[cfg(feature = "ink-unstable-chain-extensions")]
             chain_extension_handler: ChainExtensionHandler::new(),
+            runtime_call_handler: RuntimeCallHandler::new(),
             emitted_events: EmittedEventsRecorder::new(),
+            call_responses: CallResponses::new(),
+            code_registry: CodeRegistry::new(),
+            terminations: Vec::new(),
+            restorations: Vec::new(),
+            gas_meter: None,
+            storage_budget: None,
+            transfer_hook: None,
+            block_finalization_hook: None,
+            catches_returns: false,
+            call_depth: 0,
+            env_call_trace: None,
+            hash_overrides: HashMap::new(),
         }
     }
 
@@ -135,9 +222,21 @@ impl EnvInstance {
         self.chain_spec.reset();
         self.blocks.clear();
         self.console.reset();
-        #[cfg(feature = "ink-unstable-chain-extensions")]
         self.chain_extension_handler.reset();
+        self.runtime_call_handler.reset();
         self.emitted_events.reset();
+        self.call_responses.reset();
+        self.code_registry.reset();
+        self.terminations.clear();
+        self.restorations.clear();
+        self.gas_meter = None;
+        self.storage_budget = None;
+        self.transfer_hook = None;
+        self.block_finalization_hook = None;
+        self.catches_returns = false;
+        self.call_depth = 0;
+        self.env_call_trace = None;
+        self.hash_overrides.clear();
     }
 
     /// Initializes the whole off-chain environment.
@@ -219,11 +318,45 @@ impl EnvInstance {
     where
         T: EnvTypes,
     {
+        let block_time = self.chain_spec.block_time::<T>()?;
+        self.advance_block_by::<T>(block_time)
+    }
+
+    /// Advances the chain by a single block that is `by_timestamp` later
+    /// than the current block.
+    ///
+    /// # Note
+    ///
+    /// If the current block has a randomness seed the new block's seed is
+    /// the BLAKE2 256-bit hash of it so that randomness stays deterministic
+    /// but differs between blocks.
+    ///
+    /// The block finalization hook, if any, is invoked with the events that
+    /// have been emitted in the finished block.
+    pub fn advance_block_by<T>(
+        &mut self,
+        by_timestamp: T::Timestamp,
+    ) -> crate::env::Result<()>
+    where
+        T: EnvTypes,
+    {
+        let current_block = self.current_block()?;
         let new_block_number = T::BlockNumber::from(self.blocks.len() as u32);
-        let new_timestamp = self.current_block()?.timestamp::<T>()?
-            + self.chain_spec.block_time::<T>()?;
-        self.blocks
-            .push(Block::new::<T>(new_block_number, new_timestamp));
+        let new_timestamp = current_block.timestamp::<T>()? + by_timestamp;
+        let new_seed = current_block.seed().map(|seed| {
+            let mut new_seed = [0x00; 32];
+            hashing::blake2b_256(seed, &mut new_seed);
+            new_seed
+        });
+        let mut new_block = Block::new::<T>(new_block_number, new_timestamp);
+        if let Some(new_seed) = new_seed {
+            new_block.set_seed(&new_seed);
+        }
+        if let Some(block_finalization_hook) = &mut self.block_finalization_hook {
+            block_finalization_hook(self.emitted_events.block_events());
+        }
+        self.emitted_events.finish_block();
+        self.blocks.push(new_block);
         Ok(())
     }
 
@@ -258,16 +391,125 @@ impl EnvInstance {
     }
 }
 
+thread_local!(
+    /// The stack of off-chain environments of the current thread.
+    ///
+    /// # Note
+    ///
+    /// Only the environment on top of the stack is operated on. The others are
+    /// left uninitialized while their state has been released via
+    /// [`EnvInstance::with_released`].
+    static INSTANCES: RefCell<Vec<Rc<RefCell<EnvInstance>>>> = RefCell::new(vec![
+        Rc::new(RefCell::new(EnvInstance::uninitialized()))
+    ])
+);
+
+impl EnvInstance {
+    /// Runs `f` with the state of the environment released so that `f` is able
+    /// to operate on the environment itself.
+    ///
+    /// # Note
+    ///
+    /// This allows call handlers to call back into the calling contract while
+    /// the environment is still borrowed for the call. The state is moved onto
+    /// a new environment on top of the stack of environments for the duration
+    /// of `f` and moved back afterwards.
+    fn with_released<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let released = Rc::new(RefCell::new(core::mem::replace(
+            self,
+            EnvInstance::uninitialized(),
+        )));
+        INSTANCES.with(|instances| instances.borrow_mut().push(Rc::clone(&released)));
+        let result = f();
+        INSTANCES.with(|instances| instances.borrow_mut().pop());
+        *self = Rc::try_unwrap(released)
+            .ok()
+            .expect("encountered leaked off-chain environment")
+            .into_inner();
+        result
+    }
+}
+
+thread_local!(
+    /// The parts of the off-chain environment of the current thread that
+    /// hashing operates on.
+    ///
+    /// # Note
+    ///
+    /// Mirrors the state of the environment on top of the stack so that the
+    /// hash functions only have to access the environment if they operate on it.
+    static HASHING_HOOKS: Cell<HashingHooks> = Cell::new(HashingHooks::default())
+);
+
+/// The parts of the off-chain environment that hashing operates on.
+#[derive(Debug, Default, Copy, Clone)]
+struct HashingHooks {
+    /// If hashing is charged with gas.
+    metered: bool,
+    /// If hashing is traced or has overridden outputs.
+    inspected: bool,
+}
+
+impl EnvInstance {
+    /// Returns the parts of the environment on top of the stack that hashing
+    /// operates on.
+    fn hashing_hooks() -> HashingHooks {
+        HASHING_HOOKS.with(Cell::get)
+    }
+
+    /// Mirrors the parts of the environment that hashing operates on.
+    fn mirror_hashing_hooks(&self) {
+        HASHING_HOOKS.with(|hooks| {
+            hooks.set(HashingHooks {
+                metered: self.gas_meter.is_some(),
+                inspected: self.env_call_trace.is_some()
+                    || !self.hash_overrides.is_empty(),
+            })
+        })
+    }
+
+    /// Runs `f` on the environment on top of the stack if it is not in use.
+    ///
+    /// # Note
+    ///
+    /// Returns `None` if the environment is already borrowed, e.g. by a
+    /// call handler or while decoding a storage entry.
+    fn try_on_instance<F, R>(f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let instance = INSTANCES.with(|instances| {
+            Rc::clone(
+                instances
+                    .borrow()
+                    .last()
+                    .expect("encountered empty stack of off-chain environments"),
+            )
+        });
+        let mut instance = instance.try_borrow_mut().ok()?;
+        Some(f(&mut instance))
+    }
+}
+
 impl OnInstance for EnvInstance {
     fn on_instance<F, R>(f: F) -> R
     where
         F: FnOnce(&mut Self) -> R,
     {
-        thread_local!(
-            static INSTANCE: RefCell<EnvInstance> = RefCell::new(
-                EnvInstance::uninitialized()
+        let instance = INSTANCES.with(|instances| {
+            Rc::clone(
+                instances
+                    .borrow()
+                    .last()
+                    .expect("encountered empty stack of off-chain environments"),
             )
-        );
-        INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
+        });
+        let mut instance = instance.borrow_mut();
+        let result = f(&mut instance);
+        instance.mirror_hashing_hooks();
+        result
     }
 }
@@ -0,0 +1,95 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    db::AccountsDb,
+    types::OffAccountId,
+    OffChainError,
+};
+use crate::env::Result;
+use std::collections::HashMap;
+
+/// The type name of a runtime call that identifies its registered handler.
+type CallType = &'static str;
+
+/// A raw runtime call handler.
+///
+/// Wraps the real runtime call handler and decodes the encoded runtime call
+/// before handing it to the real runtime call handler together with the
+/// accounts database and the origin of the runtime call.
+type RuntimeCallFn =
+    Box<dyn FnMut(&mut AccountsDb, &OffAccountId, Vec<u8>) -> Result<()>>;
+
+/// Handler for runtime calls dispatched by contracts.
+///
+/// Emulates the runtime dispatchables that contracts call into.
+pub struct RuntimeCallHandler {
+    /// The currently registered runtime call handlers.
+    registered: HashMap<CallType, RuntimeCallFn>,
+}
+
+impl RuntimeCallHandler {
+    /// Creates a new runtime call handler.
+    ///
+    /// Initialized with an empty set of runtime call handlers.
+    pub fn new() -> Self {
+        Self {
+            registered: HashMap::new(),
+        }
+    }
+
+    /// Resets the runtime call handler to uninitialized state.
+    pub fn reset(&mut self) {
+        self.registered.clear()
+    }
+
+    /// Registers a new handler for runtime calls of type `C`.
+    ///
+    /// Replaces any handler previously registered for the same type.
+    pub fn register<C, F>(&mut self, mut handler: F)
+    where
+        C: scale::Decode + 'static,
+        F: FnMut(&mut AccountsDb, &OffAccountId, C) -> Result<()> + 'static,
+    {
+        self.registered.insert(
+            core::any::type_name::<C>(),
+            Box::new(move |accounts, origin, encoded_call: Vec<u8>| {
+                let decoded_call = scale::Decode::decode(&mut &encoded_call[..])?;
+                handler(accounts, origin, decoded_call)
+            }),
+        );
+    }
+
+    /// Dispatches the runtime call on behalf of the given origin.
+    ///
+    /// # Errors
+    ///
+    /// - If no handler has been registered for runtime calls of type `C`.
+    /// - If the registered handler failed.
+    pub fn eval<C>(
+        &mut self,
+        accounts: &mut AccountsDb,
+        origin: &OffAccountId,
+        call: &C,
+    ) -> Result<()>
+    where
+        C: scale::Encode,
+    {
+        let call_type = core::any::type_name::<C>();
+        match self.registered.get_mut(call_type) {
+            Some(handler) => handler(accounts, origin, scale::Encode::encode(call)),
+            None => Err(OffChainError::UnregisteredRuntimeCall(call_type).into()),
+        }
+    }
+}
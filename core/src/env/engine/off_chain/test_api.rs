@@ -15,23 +15,57 @@
 //! Operations on the off-chain testing environment.
 
 pub use super::{
+    encode_call,
     CallData,
     EmittedEvent,
-    db::ChainSpec,
+    db::{
+        ChainSpec,
+        DustPolicy,
+        EnvCall,
+        GasCosts,
+        GasProfile,
+        GasUsage,
+        HostFunction,
+        StorageAccess,
+        StorageAccessKind,
+        StorageBudget,
+    },
 };
-#[cfg(feature = "ink-unstable-chain-extensions")]
-use super::chain_extension::ChainExtension;
 use super::{
-    db::ExecContext,
+    db::{
+        AccountsDb,
+        Block,
+        CallResponses,
+        CodeRegistry,
+        ContractTermination,
+        EmittedEventsRecorder,
+        ExecContext,
+        GasMeter,
+    },
+    hashing,
+    types::OffAccountId,
     AccountError,
+    ContractReturned,
+    ContractTerminated,
     EnvInstance,
+    OffChainError,
     OnInstance,
 };
 use crate::env::{
+    call::Selector,
+    hash::{
+        CryptoHash,
+        HashOutput,
+    },
+    EnvError,
     EnvTypes,
     Result,
+    ReturnFlags,
+    TopicFields,
 };
+use core::marker::PhantomData;
 use ink_prelude::string::String;
+use ink_primitives::Key;
 
 /// Pushes a contract execution context.
 ///
@@ -41,6 +75,8 @@ use ink_prelude::string::String;
 ///
 /// Together with [`pop_execution_context`] this can be used to emulated
 /// nested calls.
+///
+/// Events emitted before are no longer counted by [`event_count`].
 pub fn push_execution_context<T>(
     caller: T::AccountId,
     callee: T::AccountId,
@@ -51,6 +87,7 @@ pub fn push_execution_context<T>(
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.emitted_events.start_call();
         instance.exec_context.push(
             ExecContext::build::<T>()
                 .caller(caller)
@@ -63,6 +100,36 @@ pub fn push_execution_context<T>(
     })
 }
 
+/// Pushes a contract execution context for a call initiated by a contract.
+///
+/// # Note
+///
+/// Unlike [`push_execution_context`] the caller of the pushed execution
+/// context is another contract instead of the origin, so that
+/// [`crate::env::caller_is_origin`] returns `false` until it is popped.
+pub fn push_contract_execution_context<T>(
+    caller: T::AccountId,
+    callee: T::AccountId,
+    gas_limit: T::Balance,
+    endowment: T::Balance,
+    call_data: CallData,
+) where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let mut exec_context = ExecContext::build::<T>()
+            .caller(caller)
+            .callee(callee)
+            .gas(gas_limit)
+            .transferred_value(endowment)
+            .call_data(call_data)
+            .finish();
+        exec_context.caller_is_contract = true;
+        instance.emitted_events.start_call();
+        instance.exec_context.push(exec_context)
+    })
+}
+
 /// Pops the top contract execution context.
 ///
 /// # Note
@@ -75,6 +142,372 @@ pub fn pop_execution_context() {
     })
 }
 
+/// Sets the caller of all subsequent calls that are not executed within an
+/// execution context of their own.
+///
+/// # Note
+///
+/// This replaces the caller of the default execution context so that
+/// scenario tests can run many calls as the same actor. Execution contexts
+/// pushed via [`push_execution_context`] or [`execution_context`] still take
+/// precedence for as long as they are not popped.
+///
+/// # Errors
+///
+/// - If there is no execution context.
+pub fn set_default_caller<T>(caller: T::AccountId) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let exec_context = instance
+            .exec_context
+            .first_mut()
+            .ok_or(OffChainError::UninitializedExecutionContext)?;
+        exec_context.caller = OffAccountId::new(&caller);
+        Ok(())
+    })
+}
+
+/// Sets the raw input bytes of the current execution context.
+///
+/// # Note
+///
+/// The raw input bytes take precedence over the typed call data of the
+/// execution context and are decoded as is by [`crate::env::decode_input`].
+/// This way the decoded input is exactly the same as for a real call
+/// carrying these bytes.
+///
+/// # Errors
+///
+/// If there is no execution context.
+pub fn set_raw_call_data(raw_call_data: Vec<u8>) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context_mut()
+            .map(|exec_context| exec_context.raw_call_data = Some(raw_call_data))
+    })
+    .map_err(Into::into)
+}
+
+/// Sets whether the current contract execution is in read-only mode.
+///
+/// # Note
+///
+/// While in read-only mode [`crate::env::is_read_only`] returns `true`
+/// so that `&mut self` ink! messages are rejected upon dispatch.
+///
+/// # Errors
+///
+/// If there is no execution context.
+pub fn set_read_only(read_only: bool) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context_mut()
+            .map(|exec_context| exec_context.read_only = read_only)
+    })
+    .map_err(Into::into)
+}
+
+/// Returns the output of the current execution context if any.
+///
+/// # Note
+///
+/// The output is set by [`crate::env::return_value`] and
+/// [`crate::env::return_raw`].
+///
+/// # Errors
+///
+/// If there is no execution context.
+pub fn get_return_value() -> Result<Option<Vec<u8>>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context()
+            .map(|exec_context| exec_context.output().cloned())
+    })
+    .map_err(Into::into)
+}
+
+/// Returns the decoded output of the current execution context if any.
+///
+/// # Note
+///
+/// Use [`catch_return`] to observe the value returned by a message
+/// without exiting the process.
+///
+/// # Errors
+///
+/// - If there is no execution context.
+/// - If the output cannot be decoded as `R`.
+pub fn last_return_value<R>() -> Result<Option<R>>
+where
+    R: scale::Decode,
+{
+    get_return_value()?
+        .map(|encoded| {
+            <R as scale::Decode>::decode(&mut &encoded[..])
+                .map_err(|_| scale::Error::from("could not decode return value"))
+                .map_err(Into::into)
+        })
+        .transpose()
+}
+
+/// Runs `f` while catching the contract returning a value.
+///
+/// Returns the flags of the return if `f` called [`crate::env::return_value`]
+/// or [`crate::env::return_raw`] and `None` if `f` completed normally.
+/// A call to [`crate::env::terminate_contract`] is reported as a successful
+/// return without new output.
+///
+/// # Note
+///
+/// In the off-chain environment returning a value or terminating exits the
+/// process. Within `f` both unwind up to this function instead so that the
+/// returned value can be inspected via [`last_return_value`] and the
+/// termination via [`recorded_terminations`] afterwards.
+/// Other panics within `f` are propagated.
+pub fn catch_return<F>(f: F) -> Option<ReturnFlags>
+where
+    F: FnOnce(),
+{
+    let catches_returns = <EnvInstance as OnInstance>::on_instance(|instance| {
+        core::mem::replace(&mut instance.catches_returns, true)
+    });
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.catches_returns = catches_returns;
+    });
+    match result {
+        Ok(()) => None,
+        Err(payload) => {
+            if payload.is::<ContractTerminated>() {
+                return Some(ReturnFlags::default())
+            }
+            match payload.downcast::<ContractReturned>() {
+                Ok(returned) => Some(returned.0),
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        }
+    }
+}
+
+/// Returns `true` if the unwinding payload stems from the contract returning
+/// a value or terminating while returns are being caught via [`catch_return`].
+///
+/// # Note
+///
+/// Code that catches panics within [`catch_return`] must resume unwinding
+/// with such payloads so that the returned value reaches [`catch_return`].
+pub fn is_contract_return(payload: &(dyn core::any::Any + Send)) -> bool {
+    payload.is::<ContractReturned>() || payload.is::<ContractTerminated>()
+}
+
+/// Sets a hook that is invoked with the sender, the receiver and the value
+/// of every successful transfer.
+///
+/// # Note
+///
+/// - The hook is invoked after the balances have been updated.
+/// - The hook must not call into the off-chain environment itself.
+/// - This replaces any previously set transfer hook.
+pub fn set_transfer_hook<T, F>(mut transfer_hook: F)
+where
+    T: EnvTypes,
+    F: FnMut(T::AccountId, T::AccountId, T::Balance) + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.transfer_hook = Some(Box::new(move |from, to, value| {
+            transfer_hook(
+                from.decode::<T::AccountId>()
+                    .expect("encountered invalid account id in transfer hook"),
+                to.decode::<T::AccountId>()
+                    .expect("encountered invalid account id in transfer hook"),
+                value
+                    .decode::<T::Balance>()
+                    .expect("encountered invalid balance in transfer hook"),
+            )
+        }))
+    })
+}
+
+/// Removes the transfer hook if any.
+pub fn clear_transfer_hook() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.transfer_hook = None;
+    })
+}
+
+/// Builder for the execution context of a simulated contract call.
+///
+/// # Note
+///
+/// Use [`execution_context`] to create a new builder and push the built
+/// execution context using [`ExecutionContextBuilder::push`]. Pop it again
+/// using [`pop_execution_context`] after the call.
+pub struct ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    caller: T::AccountId,
+    callee: T::AccountId,
+    transferred_value: T::Balance,
+    gas: T::Balance,
+    call_data: CallData,
+    instantiation: bool,
+}
+
+/// Returns a builder for the execution context of a call from `caller` to `callee`.
+///
+/// # Note
+///
+/// By default no value is transferred, the gas limit is `500_000` and the call
+/// data consists only of the `0x00000000` selector.
+pub fn execution_context<T>(
+    caller: T::AccountId,
+    callee: T::AccountId,
+) -> ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    ExecutionContextBuilder {
+        caller,
+        callee,
+        transferred_value: T::Balance::from(0),
+        gas: T::Balance::from(500_000),
+        call_data: CallData::new(Selector::new([0x00; 4])),
+        instantiation: false,
+    }
+}
+
+impl<T> ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    /// Sets the value transferred from the caller to the callee.
+    pub fn transferred_value(mut self, transferred_value: T::Balance) -> Self {
+        self.transferred_value = transferred_value;
+        self
+    }
+
+    /// Sets the gas limit of the call.
+    pub fn gas(mut self, gas: T::Balance) -> Self {
+        self.gas = gas;
+        self
+    }
+
+    /// Sets the call data of the call.
+    pub fn call_data(mut self, call_data: CallData) -> Self {
+        self.call_data = call_data;
+        self
+    }
+
+    /// Marks the call as the instantiation of the callee.
+    ///
+    /// # Note
+    ///
+    /// Events emitted within the execution context, e.g. by the constructor
+    /// of the callee, are attributed to its instantiation.
+    pub fn instantiation(mut self) -> Self {
+        self.instantiation = true;
+        self
+    }
+
+    /// Pushes the built execution context.
+    ///
+    /// # Errors
+    ///
+    /// - If the caller account does not exist.
+    /// - If the caller has less balance than the transferred value.
+    pub fn push(self) -> Result<()> {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            let caller_balance = instance
+                .accounts
+                .get_account::<T>(&self.caller)
+                .ok_or_else(|| AccountError::no_account_for_id::<T>(&self.caller))?
+                .balance::<T>()?;
+            if caller_balance < self.transferred_value {
+                return Err(EnvError::TransferFailed)
+            }
+            let mut exec_context = ExecContext::build::<T>()
+                .caller(self.caller)
+                .callee(self.callee)
+                .gas(self.gas)
+                .transferred_value(self.transferred_value)
+                .call_data(self.call_data)
+                .finish();
+            exec_context.is_instantiation = self.instantiation;
+            instance.emitted_events.start_call();
+            instance.exec_context.push(exec_context);
+            Ok(())
+        })
+    }
+}
+
+/// A read-only view of the current execution context.
+pub struct ExecutionContextView<T>
+where
+    T: EnvTypes,
+{
+    /// The caller of the contract execution.
+    pub caller: T::AccountId,
+    /// The callee of the contract execution.
+    pub callee: T::AccountId,
+    /// The value transferred from the caller to the callee.
+    pub transferred_value: T::Balance,
+    /// The gas provided for the contract execution.
+    pub gas: T::Balance,
+}
+
+/// Returns a view of the current execution context.
+///
+/// # Note
+///
+/// This is an inspection aid for tests, e.g. to verify the execution
+/// contexts pushed by [`ExecutionContextBuilder::push`].
+///
+/// # Errors
+///
+/// - If there is no execution context.
+/// - If the fields cannot be decoded into the given environmental types.
+pub fn current_execution_context<T>() -> Result<ExecutionContextView<T>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let exec_context = instance.exec_context()?;
+        Ok(ExecutionContextView {
+            caller: exec_context.caller::<T>()?,
+            callee: exec_context.callee::<T>()?,
+            transferred_value: exec_context.transferred_value::<T>()?,
+            gas: exec_context.gas::<T>()?,
+        })
+    })
+}
+
+/// Returns the nonce of the account.
+///
+/// # Note
+///
+/// The nonce is incremented once for every contract instantiation and every
+/// transfer originating from the account.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+pub fn get_account_nonce<T>(account_id: T::AccountId) -> Result<u64>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(&account_id)
+            .map(|account| account.nonce())
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))
+            .map_err(Into::into)
+    })
+}
+
 /// Sets the balance of the account to the given balance.
 ///
 /// # Note
@@ -104,6 +537,24 @@ where
     })
 }
 
+/// Creates a new user account with the initial balance and returns its
+/// account ID.
+///
+/// # Note
+///
+/// The account IDs are deterministic across test runs and distinct from
+/// each other as well as from all existing accounts. This is useful to set
+/// up a cast of actors beyond the [`default_accounts`].
+pub fn create_account<T>(initial_balance: T::Balance) -> T::AccountId
+where
+    T: EnvTypes,
+    <T as EnvTypes>::AccountId: From<[u8; 32]>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.accounts.create_user_account::<T>(initial_balance)
+    })
+}
+
 /// Returns the balance of the account.
 ///
 /// # Note
@@ -130,6 +581,36 @@ where
     })
 }
 
+/// Returns all accounts of the off-chain environment together with their
+/// balances and whether they are contract accounts.
+///
+/// # Note
+///
+/// The accounts are ordered by their encoded account IDs so that the
+/// returned list is deterministic and can be used to dump test fixtures.
+///
+/// # Errors
+///
+/// - If the underlying account ID or balance types do not match.
+pub fn accounts_snapshot<T>() -> Result<Vec<(T::AccountId, T::Balance, bool)>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .iter()
+            .map(|(account_id, account)| -> Result<_> {
+                let account_id = account_id
+                    .decode::<T::AccountId>()
+                    .map_err(AccountError::from)?;
+                let balance = account.balance::<T>()?;
+                Ok((account_id, balance, account.is_contract()))
+            })
+            .collect()
+    })
+}
+
 /// Sets the rent allowance of the contract account to the given rent allowance.
 ///
 /// # Errors
@@ -179,76 +660,872 @@ where
     })
 }
 
-/// Registers a new chain extension.
-#[cfg(feature = "ink-unstable-chain-extensions")]
-pub fn register_chain_extension<E, I, O>(extension: E)
+/// Sets the code hash of the contract account to the given code hash.
+///
+/// # Note
+///
+/// Contracts instantiated from uploaded code already have the code hash of
+/// their code. This is useful for contracts that exist from the start of the
+/// test, e.g. the default executed contract.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If the underlying `account` type does not match.
+pub fn set_contract_code_hash<T>(
+    account_id: T::AccountId,
+    code_hash: T::Hash,
+) -> Result<()>
 where
-    E: ChainExtension<Input = I, Output = O> + 'static,
-    I: scale::Codec + 'static,
-    O: scale::Codec + 'static,
+    T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        instance.chain_extension_handler.register(Box::new(extension));
+        instance
+            .accounts
+            .get_account_mut::<T>(&account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.set_code_hash::<T>(code_hash).map_err(Into::into))
     })
 }
 
-/// Set the entropy hash of the current block.
+/// Evicts the contract account leaving behind a tombstone.
 ///
 /// # Note
 ///
-/// This allows to control what [`crate::env::random`] returns.
-pub fn set_block_entropy<T>(entropy: T::Hash) -> Result<()>
+/// The tombstone hash is computed from the current storage of the contract
+/// and the given code hash. Use [`crate::env::restore_contract`] to restore it.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn evict_contract<T>(account_id: T::AccountId, code_hash: T::Hash) -> Result<()>
 where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        instance.current_block_mut()?.set_entropy::<T>(entropy)
+        let account = instance
+            .accounts
+            .get_account_mut::<T>(&account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))?;
+        let tombstone_hash =
+            EnvInstance::tombstone_hash::<T>(account.storage()?, &code_hash);
+        account.evict(tombstone_hash).map_err(Into::into)
     })
-    .map_err(Into::into)
 }
 
-/// Update the [ChainSpec](`crate::env::engine::off_chain::db::ChainSpec`) for the test environment
-pub fn update_chain_spec<F>(f: F) -> Result<()>
+/// Registers a chain extension handler for the given function ID.
+///
+/// Calls to [`crate::env::call_chain_extension`] with `func_id` decode their
+/// input as `I`, invoke `handler` and encode its output as `O`.
+///
+/// # Note
+///
+/// Registering a handler for an already registered function ID replaces it.
+/// Calling an unregistered function ID returns an error that carries
+/// the unknown function ID.
+pub fn register_chain_extension<I, O, F>(func_id: u32, mut handler: F)
 where
-    F: FnOnce(&mut ChainSpec)
+    I: scale::Codec + 'static,
+    O: scale::Codec + 'static,
+    F: FnMut(I) -> O + 'static,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        f(instance.chain_spec_mut())
-    });
-    Ok(())
-}
-
-/// Returns the contents of the past performed environmental `println` in order.
-pub fn recorded_printlns() -> impl Iterator<Item = String> {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        // We return a clone of the recorded strings instead of
-        // references to them since this would require the whole `on_instance`
-        // API to operate on `'static` environmental instances which would
-        // ultimately allow leaking those `'static` references to the outside
-        // and potentially lead to terrible bugs such as iterator invalidation.
         instance
-            .console
-            .past_prints()
-            .map(ToOwned::to_owned)
-            .collect::<Vec<_>>()
-            .into_iter()
+            .chain_extension_handler
+            .register(func_id, move |input: I| Ok(handler(input)));
     })
 }
 
-/// Returns the recorded emitted events in order.
+/// Registers a chain extension handler returning a status code alongside
+/// its output for the given function ID.
+///
+/// # Note
+///
+/// Calls to [`crate::env::call_chain_extension_with_status`] with `func_id`
+/// return the status code and the output of `handler` as is whereas calls
+/// to [`crate::env::call_chain_extension`] fail for non-zero status codes.
+pub fn register_chain_extension_with_status<I, O, F>(func_id: u32, mut handler: F)
+where
+    I: scale::Codec + 'static,
+    O: scale::Codec + 'static,
+    F: FnMut(I) -> (u32, O) + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_extension_handler
+            .register_with_status(func_id, move |input: I| Ok(handler(input)));
+    })
+}
+
+/// The view of the off-chain environment given to runtime call handlers.
+///
+/// Allows runtime call handlers to model the effects of runtime calls on
+/// the balances of accounts.
+pub struct RuntimeCallContext<'a, T>
+where
+    T: EnvTypes,
+{
+    /// The accounts of the off-chain environment.
+    accounts: &'a mut AccountsDb,
+    /// The contract that dispatched the runtime call.
+    origin: &'a OffAccountId,
+    /// The environmental types.
+    env_types: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> RuntimeCallContext<'a, T>
+where
+    T: EnvTypes,
+{
+    /// Returns the account ID of the contract that dispatched the runtime call.
+    pub fn origin(&self) -> Result<T::AccountId> {
+        self.origin.decode().map_err(Into::into)
+    }
+
+    /// Returns the balance of the account.
+    ///
+    /// # Errors
+    ///
+    /// - If `account_id` does not exist.
+    /// - If the underlying `account_id` or balance type does not match.
+    pub fn balance(&self, account_id: &T::AccountId) -> Result<T::Balance> {
+        self.accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.balance::<T>().map_err(Into::into))
+    }
+
+    /// Sets the balance of the account to the given balance.
+    ///
+    /// # Errors
+    ///
+    /// - If `account_id` does not exist.
+    /// - If the underlying `account_id` or balance type does not match.
+    pub fn set_balance(
+        &mut self,
+        account_id: &T::AccountId,
+        new_balance: T::Balance,
+    ) -> Result<()> {
+        self.accounts
+            .get_account_mut::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.set_balance::<T>(new_balance).map_err(Into::into))
+    }
+}
+
+/// Registers a handler for runtime calls of type `C`.
+///
+/// Calls to [`crate::env::call_runtime`] with a runtime call of type `C`
+/// decode the encoded runtime call and invoke `handler` with it.
+///
+/// # Note
+///
+/// Registering a handler for an already registered runtime call type
+/// replaces it. Dispatching a runtime call of an unregistered type returns
+/// an error that carries the name of the unknown runtime call type.
+pub fn register_runtime_call<T, C, F>(mut handler: F)
+where
+    T: EnvTypes,
+    C: scale::Decode + 'static,
+    F: FnMut(&mut RuntimeCallContext<T>, C) -> Result<()> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .runtime_call_handler
+            .register(move |accounts, origin, call: C| {
+                let mut context = RuntimeCallContext {
+                    accounts,
+                    origin,
+                    env_types: PhantomData,
+                };
+                handler(&mut context, call)
+            });
+    })
+}
+
+/// Registers the value returned by evaluating the message of the callee.
+///
+/// # Note
+///
+/// The off-chain environment cannot execute other contracts. Instead calls
+/// to [`crate::env::eval_contract`] with the given callee and selector
+/// decode and return the registered response.
+pub fn register_call_response<T, R>(callee: T::AccountId, selector: Selector, response: R)
+where
+    T: EnvTypes,
+    R: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .call_responses
+            .register::<T, R>(&callee, selector, &response);
+    })
+}
+
+/// Registers the value returned by evaluating the message of the callee
+/// together with the gas consumed by the emulated call.
+///
+/// # Note
+///
+/// Calls to [`crate::env::eval_contract`] with a gas limit below the given
+/// gas fail with [`EnvError::OutOfGas`] after consuming their gas limit.
+/// The consumed gas is charged to the caller if gas metering is enabled.
+pub fn register_call_response_with_gas<T, R>(
+    callee: T::AccountId,
+    selector: Selector,
+    response: R,
+    gas: u64,
+) where
+    T: EnvTypes,
+    R: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .call_responses
+            .register_with_gas::<T, R>(&callee, selector, &response, gas);
+    })
+}
+
+/// Registers the handler that is executed upon calls to the message of the callee.
+///
+/// # Note
+///
+/// The handler receives the encoded input of the call and returns the
+/// encoded result. If the handler panics the calling contract observes
+/// [`EnvError::CalleeTrapped`] instead of the whole test aborting.
+/// The handler may interact with the off-chain environment itself, e.g. in
+/// order to simulate the callee calling back into the calling contract.
+pub fn register_call_handler<T, F>(callee: T::AccountId, selector: Selector, handler: F)
+where
+    T: EnvTypes,
+    F: FnMut(&[u8]) -> Vec<u8> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .call_responses
+            .register_handler::<T, F>(&callee, selector, handler);
+    })
+}
+
+/// Uploads the code of a contract and returns its code hash.
+///
+/// # Note
+///
+/// Contracts can only be instantiated from uploaded code. Upon instantiation
+/// the code receives the encoded input of the instantiation and returns the
+/// handler that answers all subsequent calls to the new contract instance.
+/// The handler receives the encoded input of a call and returns the encoded
+/// result. Call responses registered for the new contract take precedence.
+/// Both the code and the handler may interact with the off-chain environment
+/// itself just like the handlers registered via [`register_call_handler`].
+/// Contracts cannot upgrade to the code via [`crate::env::set_code_hash`],
+/// use [`upload_upgradable_code`] for this instead.
+///
+/// The code hash is derived deterministically from the number of codes that
+/// have been uploaded before.
+pub fn upload_code<T, F, H>(code: F) -> Result<T::Hash>
+where
+    T: EnvTypes,
+    F: Fn(&[u8]) -> H + 'static,
+    H: FnMut(&[u8]) -> Vec<u8> + 'static,
+{
+    upload_fallible_code::<T, _, H, core::convert::Infallible>(move |input| {
+        Ok(code(input))
+    })
+}
+
+/// Uploads the code of a contract with a fallible constructor and returns
+/// its code hash.
+///
+/// # Note
+///
+/// Works like [`upload_code`] except that the code may fail upon
+/// instantiation the way a fallible ink! constructor does. A failed
+/// instantiation is rolled back: the endowment is refunded to the creator,
+/// no contract account is created and the instantiation returns
+/// [`EnvError::CalleeReverted`].
+pub fn upload_fallible_code<T, F, H, E>(code: F) -> Result<T::Hash>
+where
+    T: EnvTypes,
+    F: Fn(&[u8]) -> core::result::Result<H, E> + 'static,
+    H: FnMut(&[u8]) -> Vec<u8> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let code_hash = next_code_hash::<T>(instance)?;
+        instance
+            .code_registry
+            .upload_fallible::<T, F, H, E>(&code_hash, code);
+        Ok(code_hash)
+    })
+}
+
+/// Uploads the code of a contract that existing contracts can upgrade to and
+/// returns its code hash.
+///
+/// # Note
+///
+/// Works like [`upload_code`] except that the handler does not depend on the
+/// input of the instantiation. This allows contracts to upgrade to the code
+/// via [`crate::env::set_code_hash`] which swaps their handler without running
+/// a constructor.
+pub fn upload_upgradable_code<T, F, H>(code: F) -> Result<T::Hash>
+where
+    T: EnvTypes,
+    F: Fn() -> H + 'static,
+    H: FnMut(&[u8]) -> Vec<u8> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let code_hash = next_code_hash::<T>(instance)?;
+        instance
+            .code_registry
+            .upload_upgradable::<T, F, H>(&code_hash, code);
+        Ok(code_hash)
+    })
+}
+
+/// Returns the code hash of the next uploaded code.
+///
+/// # Note
+///
+/// The code hash is derived deterministically from the number of codes that
+/// have been uploaded before.
+fn next_code_hash<T>(instance: &EnvInstance) -> Result<T::Hash>
+where
+    T: EnvTypes,
+{
+    let nonce = instance.code_registry.num_codes() as u64;
+    let mut output = [0x00; 32];
+    let subject = scale::Encode::encode(&(&b"code"[..], nonce));
+    hashing::blake2b_256(&subject, &mut output);
+    <T::Hash as scale::Decode>::decode(&mut &output[..])
+        .map_err(|_| scale::Error::from("could not decode derived code hash").into())
+}
+
+/// Enables gas metering with the given gas costs and initial gas.
+///
+/// # Note
+///
+/// While gas metering is enabled [`crate::env::gas_left`] returns the gas
+/// left after charging the gas costs of the metered operations. Operations
+/// that would exceed the gas left fail with [`EnvError::OutOfGas`] or panic
+/// if they cannot return an error. Hashing consumes all gas left instead.
+pub fn set_gas_metering(costs: GasCosts, initial_gas: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_meter = Some(GasMeter::new(costs, initial_gas));
+    })
+}
+
+/// Disables gas metering.
+pub fn disable_gas_metering() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_meter = None;
+    })
+}
+
+/// Limits the contract storage to the given budget.
+///
+/// # Note
+///
+/// This emulates a host that cannot accept any more storage. While the budget
+/// is set [`crate::env::try_set_contract_storage`] fails with
+/// [`EnvError::OutOfStorage`] for writes exceeding the budget and
+/// [`crate::env::set_contract_storage`] panics for them.
+pub fn set_storage_budget(budget: StorageBudget) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.storage_budget = Some(budget);
+    })
+}
+
+/// Removes the limits on the contract storage.
+pub fn disable_storage_budget() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.storage_budget = None;
+    })
+}
+
+/// Enables recording the gas consumed per metered operation.
+///
+/// # Panics
+///
+/// If gas metering is disabled.
+pub fn enable_gas_profiling() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .gas_meter
+            .as_mut()
+            .expect("gas metering must be enabled to profile gas usage")
+            .enable_profiling()
+    })
+}
+
+/// Returns the gas consumed per metered operation since the last call and
+/// clears the records.
+///
+/// # Note
+///
+/// Returns an empty profile if gas metering or profiling is disabled.
+pub fn take_gas_profile() -> GasProfile {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .gas_meter
+            .as_mut()
+            .map(GasMeter::take_profile)
+            .unwrap_or_default()
+    })
+}
+
+/// Set the entropy hash of the current block.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::random`] returns.
+pub fn set_block_entropy<T>(entropy: T::Hash) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.current_block_mut()?.set_entropy::<T>(entropy)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the randomness seed of the current block.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::random`] returns: it becomes
+/// the BLAKE2 256-bit hash of the concatenation of the seed and the subject.
+pub fn set_block_randomness_seed(seed: &[u8]) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.current_block_mut().map(|block| block.set_seed(seed))
+    })
+    .map_err(Into::into)
+}
+
+/// Makes the hash function `H` return the given output for the given input.
+///
+/// # Note
+///
+/// This allows to force code paths that depend on specific hash outputs.
+/// It affects all hash functions of [`crate::env::hash`] using `H`, e.g.
+/// [`crate::env::hash::blake2_256`] for [`Blake2x256`](`crate::env::hash::Blake2x256`).
+/// All other inputs are still hashed as usual, as are all inputs hashed by
+/// call handlers.
+pub fn set_hash_override<H>(input: &[u8], output: <H as HashOutput>::Type)
+where
+    H: CryptoHash,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.hash_overrides.insert(
+            (core::any::type_name::<H>(), input.to_vec()),
+            output.as_ref().to_vec(),
+        );
+    })
+}
+
+/// Removes all hash outputs set via [`set_hash_override`].
+pub fn clear_hash_overrides() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.hash_overrides.clear();
+    })
+}
+
+/// Update the [ChainSpec](`crate::env::engine::off_chain::db::ChainSpec`) for the test environment
+pub fn update_chain_spec<F>(f: F) -> Result<()>
+where
+    F: FnOnce(&mut ChainSpec)
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        f(instance.chain_spec_mut())
+    });
+    Ok(())
+}
+
+/// Sets the minimum balance that accounts of the chain may have.
+///
+/// # Note
+///
+/// This is what [`crate::env::minimum_balance`] returns and what reaping of
+/// accounts is checked against.
+pub fn set_minimum_balance<T>(minimum_balance: T::Balance)
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_spec_mut()
+            .set_minimum_balance::<T>(minimum_balance)
+    })
+}
+
+/// Sets the tombstone deposit of the chain.
+///
+/// # Note
+///
+/// This is what [`crate::env::tombstone_deposit`] returns.
+pub fn set_tombstone_deposit<T>(tombstone_deposit: T::Balance)
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_spec_mut()
+            .set_tombstone_deposit::<T>(tombstone_deposit)
+    })
+}
+
+/// Returns the contents of the past performed environmental `println` in order.
+pub fn recorded_printlns() -> impl Iterator<Item = String> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        // We return a clone of the recorded strings instead of
+        // references to them since this would require the whole `on_instance`
+        // API to operate on `'static` environmental instances which would
+        // ultimately allow leaking those `'static` references to the outside
+        // and potentially lead to terrible bugs such as iterator invalidation.
+        instance
+            .console
+            .past_prints()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>()
+            .into_iter()
+    })
+}
+
+/// A recorded debug message with typed metadata.
+pub struct RecordedDebugMessage<T>
+where
+    T: EnvTypes,
+{
+    /// The contents of the debug message.
+    pub message: String,
+    /// The number of the block in which the debug message has been recorded.
+    pub block_number: T::BlockNumber,
+    /// The caller of the contract execution that recorded the debug message.
+    pub caller: T::AccountId,
+}
+
+/// Returns and removes the recorded debug messages in order.
+///
+/// # Note
+///
+/// Debug messages are recorded by [`crate::env::debug_message`].
+///
+/// # Errors
+///
+/// If the recorded metadata does not match the environmental types.
+pub fn drain_debug_messages<T>() -> Result<Vec<RecordedDebugMessage<T>>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .console
+            .drain_debug_messages()
+            .into_iter()
+            .map(|debug_message| {
+                Ok(RecordedDebugMessage {
+                    message: debug_message.message().to_string(),
+                    block_number: debug_message.block_number::<T>()?,
+                    caller: debug_message.caller::<T>()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })
+}
+
+/// Returns the recorded emitted events in order.
 pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        // We return a clone of the recorded emitted events instead of
-        // references to them since this would require the whole `on_instance`
-        // API to operate on `'static` environmental instances which would
-        // ultimately allow leaking those `'static` references to the outside
-        // and potentially lead to terrible bugs such as iterator invalidation.
+        // We return a clone of the recorded emitted events instead of
+        // references to them since this would require the whole `on_instance`
+        // API to operate on `'static` environmental instances which would
+        // ultimately allow leaking those `'static` references to the outside
+        // and potentially lead to terrible bugs such as iterator invalidation.
+        instance
+            .emitted_events
+            .emitted_events()
+            .map(Clone::clone)
+            .collect::<Vec<_>>()
+            .into_iter()
+    })
+}
+
+/// A contract termination recorded by the off-chain environment.
+pub struct TerminatedContract<T>
+where
+    T: EnvTypes,
+{
+    /// The account ID of the terminated contract.
+    pub contract: T::AccountId,
+    /// The beneficiary of the remaining balance of the terminated contract.
+    pub beneficiary: T::AccountId,
+    /// The balance transferred to the beneficiary.
+    pub transferred: T::Balance,
+}
+
+/// Returns the recorded contract terminations in order.
+///
+/// # Errors
+///
+/// If the recorded account IDs or balances do not match the environmental types.
+pub fn recorded_terminations<T>() -> Result<Vec<TerminatedContract<T>>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .terminations
+            .iter()
+            .map(|termination| {
+                Ok(TerminatedContract {
+                    contract: termination.contract::<T>()?,
+                    beneficiary: termination.beneficiary::<T>()?,
+                    transferred: termination.transferred::<T>()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })
+}
+
+/// An attempt to restore an evicted contract recorded by the off-chain environment.
+pub struct RestorationAttempt<T>
+where
+    T: EnvTypes,
+{
+    /// The account ID of the contract that was supposed to be restored.
+    pub contract: T::AccountId,
+    /// The outcome of the restoration.
+    pub outcome: Result<()>,
+}
+
+/// Removes and returns the recorded attempts to restore evicted contracts in order.
+///
+/// # Note
+///
+/// [`crate::env::restore_contract`] silently cancels a failed restoration
+/// as it does on-chain. Use this to inspect why a restoration failed.
+///
+/// # Errors
+///
+/// If the recorded account IDs do not match the environmental types.
+pub fn take_restoration_attempts<T>() -> Result<Vec<RestorationAttempt<T>>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        core::mem::take(&mut instance.restorations)
+            .into_iter()
+            .map(|restoration| {
+                Ok(RestorationAttempt {
+                    contract: restoration.contract::<T>()?,
+                    outcome: restoration.into_outcome(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })
+}
+
+/// A recorded emitted event with typed topics.
+pub struct RecordedEvent<T>
+where
+    T: EnvTypes,
+{
+    /// The topic hashes of the emitted event.
+    pub topics: Vec<T::Hash>,
+    /// The SCALE encoded data of the emitted event.
+    pub data: Vec<u8>,
+    /// The index of the event among all events emitted so far.
+    pub sequence: u64,
+    /// The index of the event among the events emitted by the same call.
+    ///
+    /// # Note
+    ///
+    /// Every execution context pushed via [`push_execution_context`]
+    /// counts as a separate call.
+    pub call_index: u32,
+    /// If the event has been emitted by a constructor upon the instantiation
+    /// of a contract.
+    ///
+    /// # Note
+    ///
+    /// Execution contexts of instantiations are pushed via
+    /// [`ExecutionContextBuilder::instantiation`].
+    pub during_instantiation: bool,
+}
+
+impl<T> RecordedEvent<T>
+where
+    T: EnvTypes,
+{
+    /// Decodes the data of the emitted event into the given event type.
+    ///
+    /// # Errors
+    ///
+    /// If the data cannot be decoded into an `E`.
+    pub fn decode<E>(&self) -> Result<E>
+    where
+        E: scale::Decode,
+    {
+        <E as scale::Decode>::decode(&mut &self.data[..])
+            .map_err(|_| scale::Error::from("could not decode emitted event"))
+            .map_err(Into::into)
+    }
+}
+
+/// Returns the recorded emitted events in emission order with typed topics.
+///
+/// # Errors
+///
+/// If the recorded topics do not match the environmental hash type.
+pub fn emitted_events<T>() -> Result<Vec<RecordedEvent<T>>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
         instance
             .emitted_events
             .emitted_events()
-            .map(Clone::clone)
-            .collect::<Vec<_>>()
-            .into_iter()
+            .map(|event| {
+                Ok(RecordedEvent {
+                    topics: event
+                        .topics
+                        .iter()
+                        .map(|topic| topic.decode::<T::Hash>())
+                        .collect::<core::result::Result<Vec<_>, _>>()?,
+                    data: event.data.clone(),
+                    sequence: event.sequence,
+                    call_index: event.call_index,
+                    during_instantiation: event.during_instantiation,
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })
+}
+
+/// Returns the topic hash of the recorded event at the index that has been
+/// produced by the topic field with the given name.
+///
+/// # Note
+///
+/// The topic positions of the fields are looked up in the
+/// [`TopicFields`](`crate::env::TopicFields`) of the event type `E`.
+/// Returns `None` if there is no recorded event at the index, if `E` has
+/// no topic field with the name or if the event has no topic at its position.
+///
+/// # Errors
+///
+/// If the recorded topic does not match the environmental hash type.
+pub fn event_topic<T, E>(event_index: usize, field_name: &str) -> Result<Option<T::Hash>>
+where
+    T: EnvTypes,
+    E: TopicFields,
+{
+    let position = match E::TOPIC_FIELDS.iter().position(|field| *field == field_name) {
+        Some(position) => position,
+        None => return Ok(None),
+    };
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .emitted_events
+            .emitted_events()
+            .nth(event_index)
+            .and_then(|event| event.topics.get(position))
+            .map(|topic| topic.decode::<T::Hash>())
+            .transpose()
+            .map_err(Into::into)
+    })
+}
+
+/// Returns the number of events emitted by the most recent call.
+///
+/// # Note
+///
+/// A call starts whenever an execution context is pushed, e.g. via
+/// [`push_execution_context`]. Without any pushed execution context all
+/// events emitted since the environment has been initialized are counted.
+pub fn event_count() -> usize {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.emitted_events.call_events().len()
+    })
+}
+
+/// Asserts that the most recent call has not emitted any events.
+///
+/// # Panics
+///
+/// If the most recent call has emitted at least one event.
+pub fn assert_no_events() {
+    let count = event_count();
+    assert!(
+        count == 0,
+        "expected no emitted events but the most recent call emitted {}",
+        count,
+    );
+}
+
+/// Clears all events emitted so far without resetting the rest of the
+/// off-chain environment.
+///
+/// # Note
+///
+/// This allows multi-phase tests to only inspect the events of the phase
+/// under test. Events emitted afterwards continue the sequence numbers of
+/// the cleared events unless `reset_sequence` is `true`.
+pub fn reset_events(reset_sequence: bool) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.emitted_events.clear(reset_sequence)
+    })
+}
+
+/// Sets the hook that is invoked whenever a block is finished by advancing
+/// the chain.
+///
+/// # Note
+///
+/// - The hook receives the events emitted in the finished block in their
+///   emission order which allows to model off-chain workers reacting to them.
+/// - The hook must not call into the off-chain environment itself.
+/// - This replaces any previously set block finalization hook.
+pub fn set_block_finalization_hook<T, F>(mut block_finalization_hook: F)
+where
+    T: EnvTypes,
+    F: FnMut(&[RecordedEvent<T>]) + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.block_finalization_hook = Some(Box::new(move |events| {
+            let events = events
+                .iter()
+                .map(|event| {
+                    RecordedEvent {
+                        topics: event
+                            .topics
+                            .iter()
+                            .map(|topic| {
+                                topic.decode::<T::Hash>().expect(
+                                    "encountered invalid topic in block finalization hook",
+                                )
+                            })
+                            .collect(),
+                        data: event.data.clone(),
+                        sequence: event.sequence,
+                        call_index: event.call_index,
+                        during_instantiation: event.during_instantiation,
+                    }
+                })
+                .collect::<Vec<_>>();
+            block_finalization_hook(&events)
+        }))
+    })
+}
+
+/// Removes the block finalization hook if any.
+pub fn clear_block_finalization_hook() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.block_finalization_hook = None;
     })
 }
 
@@ -260,6 +1537,23 @@ where
     <EnvInstance as OnInstance>::on_instance(|instance| instance.advance_block::<T>())
 }
 
+/// Advances the chain by a single block that is `by_timestamp` later than
+/// the current block.
+///
+/// # Note
+///
+/// The block number is incremented by one. Subsequent calls to
+/// [`crate::env::block_number`] and [`crate::env::block_timestamp`]
+/// reflect the new block.
+pub fn advance_block_by<T>(by_timestamp: T::Timestamp) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.advance_block_by::<T>(by_timestamp)
+    })
+}
+
 /// The default accounts.
 pub struct DefaultAccounts<T>
 where
@@ -325,6 +1619,228 @@ where
     f(default_accounts)
 }
 
+/// Enables logging of all accesses to the contract's storage.
+///
+/// # Note
+///
+/// Use [`storage_access_log`] or [`drain_storage_access_log`] to inspect
+/// the logged accesses.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn enable_storage_access_log<T>(account_id: &T::AccountId) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account_mut::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.enable_storage_access_log().map_err(Into::into))
+    })
+}
+
+/// Returns the logged accesses to the contract's storage in order.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn storage_access_log<T>(account_id: &T::AccountId) -> Result<Vec<StorageAccess>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.storage_access_log().map_err(Into::into))
+    })
+}
+
+/// Returns and resets the logged accesses to the contract's storage in order.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn drain_storage_access_log<T>(
+    account_id: &T::AccountId,
+) -> Result<Vec<StorageAccess>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account_mut::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.drain_storage_access_log().map_err(Into::into))
+    })
+}
+
+/// Enables tracing of all invocations of `Env` and `TypedEnv` methods.
+///
+/// # Note
+///
+/// Every invocation by the contract is recorded in order together with its
+/// salient arguments, e.g. the key of a storage access or the destination and
+/// value of a transfer. Invocations of env methods by other env methods are
+/// not recorded. Use [`env_call_trace`] or [`drain_env_call_trace`] to inspect
+/// the recorded invocations.
+pub fn enable_env_call_trace() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.env_call_trace.get_or_insert_with(Vec::new);
+    })
+}
+
+/// Returns the recorded invocations of env methods in order.
+///
+/// Returns an empty trace if env calls are not traced.
+pub fn env_call_trace() -> Vec<EnvCall> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.env_call_trace.clone().unwrap_or_default()
+    })
+}
+
+/// Returns and clears the recorded invocations of env methods in order.
+///
+/// Env calls are still traced afterwards.
+pub fn drain_env_call_trace() -> Vec<EnvCall> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .env_call_trace
+            .as_mut()
+            .map(core::mem::take)
+            .unwrap_or_default()
+    })
+}
+
+/// Returns the keys of all entries of the contract's storage ordered by key.
+///
+/// # Note
+///
+/// This is an inspection aid for tests, e.g. to assert that clearing a
+/// storage collection removed all of its entries.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn storage_keys<T>(account_id: &T::AccountId) -> Result<Vec<Key>>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.storage_keys().map_err(Into::into))
+    })
+}
+
+/// Returns the total size of the encoded values in the contract's storage
+/// in bytes.
+///
+/// # Note
+///
+/// This is the storage footprint that rent is charged for and allows tests
+/// to assert that a contract shrinks its footprint after clearing data.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn storage_size<T>(account_id: &T::AccountId) -> Result<u64>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.storage_size().map_err(Into::into))
+            .map(|size| size as u64)
+    })
+}
+
+/// Writes the value to the storage of the contract under the given key.
+///
+/// # Note
+///
+/// Unlike [`crate::env::set_contract_storage`] this operates on the storage of
+/// any contract account and not just the one of the callee. This allows to
+/// seed the storage of contracts without replaying their messages.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+pub fn set_account_storage<T, V>(
+    account_id: &T::AccountId,
+    key: &Key,
+    value: &V,
+) -> Result<()>
+where
+    T: EnvTypes,
+    V: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account_mut::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| {
+                account
+                    .set_storage(*key, value)
+                    .map(|_| ())
+                    .map_err(Into::into)
+            })
+    })
+}
+
+/// Returns the value stored under the given key in the storage of the contract.
+///
+/// # Note
+///
+/// Unlike [`crate::env::get_contract_storage`] this operates on the storage of
+/// any contract account and not just the one of the callee.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If `account` is not a contract account.
+/// - If the stored value cannot be decoded.
+pub fn get_account_storage<T, R>(
+    account_id: &T::AccountId,
+    key: &Key,
+) -> Result<Option<R>>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.get_storage::<R>(*key).map_err(Into::into))
+    })
+}
+
 /// Returns the total number of reads and writes of the contract's storage.
 pub fn get_contract_storage_rw<T>(account_id: &T::AccountId) -> Result<(usize, usize)>
 where
@@ -339,3 +1855,65 @@ where
             .and_then(|account| account.get_storage_rw().map_err(Into::into))
     })
 }
+
+/// A snapshot of the state of the off-chain environment.
+///
+/// # Note
+///
+/// Captures the accounts including their contract storage, the chain
+/// specification, the blocks, the emitted events, the registered call
+/// responses, the code registry and the recorded contract terminations by
+/// value so that later mutations of the environment do not leak into the
+/// snapshot.
+///
+/// The handlers of registered calls and instantiated contracts are shared
+/// with the snapshot, so state captured by them is not rolled back.
+/// Restoration attempts, the gas meter, the storage budget and all other
+/// settings of the environment are not captured either.
+#[derive(Clone)]
+pub struct EnvSnapshot {
+    accounts: AccountsDb,
+    chain_spec: ChainSpec,
+    blocks: Vec<Block>,
+    emitted_events: EmittedEventsRecorder,
+    call_responses: CallResponses,
+    code_registry: CodeRegistry,
+    terminations: Vec<ContractTermination>,
+}
+
+/// Takes a snapshot of the current state of the off-chain environment.
+///
+/// # Note
+///
+/// Use [`restore`] to roll the environment back to the snapshot.
+pub fn snapshot() -> EnvSnapshot {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvSnapshot {
+            accounts: instance.accounts.clone(),
+            chain_spec: instance.chain_spec.clone(),
+            blocks: instance.blocks.clone(),
+            emitted_events: instance.emitted_events.clone(),
+            call_responses: instance.call_responses.clone(),
+            code_registry: instance.code_registry.clone(),
+            terminations: instance.terminations.clone(),
+        }
+    })
+}
+
+/// Restores the state of the off-chain environment from the snapshot.
+///
+/// # Note
+///
+/// The same snapshot can be restored any number of times.
+pub fn restore(snapshot: &EnvSnapshot) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let snapshot = snapshot.clone();
+        instance.accounts = snapshot.accounts;
+        instance.chain_spec = snapshot.chain_spec;
+        instance.blocks = snapshot.blocks;
+        instance.emitted_events = snapshot.emitted_events;
+        instance.call_responses = snapshot.call_responses;
+        instance.code_registry = snapshot.code_registry;
+        instance.terminations = snapshot.terminations;
+    })
+}
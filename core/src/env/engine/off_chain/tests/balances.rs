@@ -0,0 +1,300 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+
+#[test]
+fn transfer_hook_records_transfers() -> env::Result<()> {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        let transfers = Rc::new(RefCell::new(Vec::new()));
+        let recorded = transfers.clone();
+        env::test::set_transfer_hook::<env::DefaultEnvTypes, _>(
+            move |from, to, value| recorded.borrow_mut().push((from, to, value)),
+        );
+        // Pays out to several accounts in a row.
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 10)?;
+        env::transfer::<env::DefaultEnvTypes>(accounts.charlie.clone(), 20)?;
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 30)?;
+        // Failed transfers are not recorded.
+        assert!(env::transfer::<env::DefaultEnvTypes>(accounts.eve, 1_000).is_err());
+        assert_eq!(
+            *transfers.borrow(),
+            vec![
+                (callee.clone(), accounts.bob.clone(), 10),
+                (callee.clone(), accounts.charlie, 20),
+                (callee, accounts.bob, 30),
+            ]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_into_nearly_full_account() -> env::Result<()> {
+    type Balance = <env::DefaultEnvTypes as env::EnvTypes>::Balance;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        let nearly_full = Balance::max_value() - 5;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(
+            accounts.bob.clone(),
+            nearly_full,
+        )?;
+        // Overflowing the balance of the destination fails without effects.
+        assert_eq!(
+            env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 10),
+            Err(env::EnvError::TransferFailed)
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.bob.clone()),
+            Ok(nearly_full)
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee.clone()),
+            Ok(100)
+        );
+        // Filling up the balance of the destination works.
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 5)?;
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.bob),
+            Ok(Balance::max_value())
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee),
+            Ok(95)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_to_self_preserves_balance() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract.clone(), 100)?;
+        env::transfer::<env::DefaultEnvTypes>(contract.clone(), 60)?;
+        assert_eq!(env::balance::<env::DefaultEnvTypes>(), Ok(100));
+        // Self-transfers still require a sufficient balance.
+        assert_eq!(
+            env::transfer::<env::DefaultEnvTypes>(contract, 101),
+            Err(env::EnvError::TransferFailed),
+        );
+        assert_eq!(env::balance::<env::DefaultEnvTypes>(), Ok(100));
+        Ok(())
+    })
+}
+
+#[test]
+fn create_account_works() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let actors = [
+            env::test::create_account::<env::DefaultEnvTypes>(10),
+            env::test::create_account::<env::DefaultEnvTypes>(20),
+            env::test::create_account::<env::DefaultEnvTypes>(30),
+        ];
+        for (n, actor) in actors.iter().enumerate() {
+            assert_eq!(
+                env::test::get_account_balance::<env::DefaultEnvTypes>(actor.clone()),
+                Ok(10 * (n as u128 + 1)),
+            );
+            assert!(actors[..n].iter().all(|other| other != actor));
+            assert_ne!(actor, &accounts.alice);
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn account_below_minimum_balance_is_reaped() -> env::Result<()> {
+    use env::test::DustPolicy;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        env::test::update_chain_spec(|chain_spec| {
+            chain_spec.enable_reaping::<env::DefaultEnvTypes>(DustPolicy::Treasury(
+                accounts.django.clone(),
+            ))
+        })?;
+        // The remaining balance stays above the minimum balance of 42.
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 10)?;
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee.clone()),
+            Ok(90),
+        );
+        // The remaining balance of 30 drops below the minimum balance.
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob, 60)?;
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee),
+            Err(env::EnvError::AccountNotFound),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.django),
+            Ok(30),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_saturating_within_budget() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        assert_eq!(
+            env::transfer_saturating::<env::DefaultEnvTypes>(accounts.django.clone(), 30),
+            Ok(30),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee),
+            Ok(70),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.django),
+            Ok(30),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_saturating_moves_only_available_balance() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        // The strict transfer fails entirely.
+        assert_eq!(
+            env::transfer::<env::DefaultEnvTypes>(accounts.django.clone(), 150),
+            Err(env::EnvError::TransferFailed),
+        );
+        assert_eq!(
+            env::transfer_saturating::<env::DefaultEnvTypes>(accounts.django.clone(), 150),
+            Ok(100),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee),
+            Ok(0),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.django),
+            Ok(100),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_keep_alive_refuses_dust() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        let django = accounts.django;
+        // The minimum balance is 42 so that 10 units would be reaped as dust.
+        assert_eq!(
+            env::transfer_keep_alive::<env::DefaultEnvTypes>(django.clone(), 10),
+            Err(env::EnvError::BelowMinimumBalance),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee.clone()),
+            Ok(100),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(django.clone()),
+            Ok(0),
+        );
+        // Transfers that leave the destination at the minimum balance are fine.
+        assert_eq!(
+            env::transfer_keep_alive::<env::DefaultEnvTypes>(django.clone(), 42),
+            Ok(()),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(callee),
+            Ok(58),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(django),
+            Ok(42),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn caller_balance_follows_the_caller() -> env::Result<()> {
+    use env::call::Selector;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(accounts.alice, 1000)?;
+        assert_eq!(env::caller_balance::<env::DefaultEnvTypes>(), Ok(1000));
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.django,
+            contract,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(env::caller_balance::<env::DefaultEnvTypes>(), Ok(0));
+        env::test::pop_execution_context();
+        Ok(())
+    })
+}
+
+#[test]
+fn accounts_snapshot_works() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(callee.clone(), 100)?;
+        let first = env::test::create_account::<env::DefaultEnvTypes>(10);
+        let second = env::test::create_account::<env::DefaultEnvTypes>(20);
+        env::transfer::<env::DefaultEnvTypes>(first.clone(), 30)?;
+        env::transfer::<env::DefaultEnvTypes>(accounts.eve.clone(), 50)?;
+        let snapshot = env::test::accounts_snapshot::<env::DefaultEnvTypes>()?;
+        let account_ids = snapshot
+            .iter()
+            .map(|(account_id, _, _)| account_id.clone())
+            .collect::<Vec<_>>();
+        // Created accounts are ordered before the default accounts.
+        assert_eq!(
+            account_ids,
+            vec![
+                first.clone(),
+                second.clone(),
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie.clone(),
+                accounts.django,
+                accounts.eve.clone(),
+                accounts.frank,
+                callee.clone(),
+            ]
+        );
+        let summary = |account_id| {
+            snapshot
+                .iter()
+                .find(|(id, _, _)| id == &account_id)
+                .map(|(_, balance, is_contract)| (*balance, *is_contract))
+        };
+        assert_eq!(summary(first), Some((40, false)));
+        assert_eq!(summary(second), Some((20, false)));
+        assert_eq!(summary(accounts.charlie), Some((0, false)));
+        assert_eq!(summary(accounts.eve), Some((50, false)));
+        assert_eq!(summary(callee), Some((20, true)));
+        Ok(())
+    })
+}
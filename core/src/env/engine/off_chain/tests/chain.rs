@@ -0,0 +1,213 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+use ink_primitives::Key;
+
+#[test]
+fn random_with_seed() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let expected = |seed: &[u8], subject: &[u8]| {
+            let mut output = [0x00; 32];
+            env::hash::blake2_256(&[seed, subject].concat(), &mut output);
+            env::Hash::from(output)
+        };
+        env::test::set_block_randomness_seed(b"first")?;
+        let first = env::random::<env::DefaultEnvTypes>(b"subject")?;
+        assert_eq!(first, expected(b"first", b"subject"));
+        assert_eq!(env::random::<env::DefaultEnvTypes>(b"subject")?, first);
+        env::test::set_block_randomness_seed(b"second")?;
+        let second = env::random::<env::DefaultEnvTypes>(b"subject")?;
+        assert_eq!(second, expected(b"second", b"subject"));
+        assert_ne!(first, second);
+        Ok(())
+    })
+}
+
+#[test]
+fn advance_block_by_timestamp() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut number = env::block_number::<env::DefaultEnvTypes>()?;
+        let mut timestamp = env::block_timestamp::<env::DefaultEnvTypes>()?;
+        env::test::set_block_randomness_seed(b"seed")?;
+        let mut randomness = env::random::<env::DefaultEnvTypes>(b"subject")?;
+        for by_timestamp in &[1, 10, 100] {
+            env::test::advance_block_by::<env::DefaultEnvTypes>(*by_timestamp)?;
+            let new_number = env::block_number::<env::DefaultEnvTypes>()?;
+            let new_timestamp = env::block_timestamp::<env::DefaultEnvTypes>()?;
+            assert_eq!(new_number, number + 1);
+            assert_eq!(new_timestamp, timestamp + by_timestamp);
+            // The randomness seed is rotated with every new block.
+            let new_randomness = env::random::<env::DefaultEnvTypes>(b"subject")?;
+            assert_ne!(new_randomness, randomness);
+            number = new_number;
+            timestamp = new_timestamp;
+            randomness = new_randomness;
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn snapshot_and_restore() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let key = Key::from([0x42; 32]);
+        env::set_contract_storage(&key, &1);
+        let snapshot = env::test::snapshot();
+        env::set_contract_storage(&key, &2);
+        env::test::set_account_balance::<env::DefaultEnvTypes>(accounts.bob.clone(), 7)?;
+        env::test::advance_block::<env::DefaultEnvTypes>()?;
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(Some(2)));
+        env::test::restore(&snapshot);
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(Some(1)));
+        assert_ne!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.bob.clone()),
+            Ok(7)
+        );
+        assert_eq!(env::block_number::<env::DefaultEnvTypes>(), Ok(0));
+        // Restoring does not consume the snapshot.
+        env::set_contract_storage(&key, &3);
+        env::test::restore(&snapshot);
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(Some(1)));
+        Ok(())
+    })
+}
+
+#[test]
+fn restore_rolls_back_uploaded_code() -> env::Result<()> {
+    use super::instantiate;
+    use env::call::{
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent, 1000)?;
+        let snapshot = env::test::snapshot();
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let new = || ExecutionInput::new(Selector::new([0xDE, 0xAD, 0xBE, 0xEF]));
+        assert!(instantiate(code_hash, 100, new(), &[]).is_ok());
+        // The code has been uploaded after the snapshot has been taken.
+        env::test::restore(&snapshot);
+        assert_eq!(
+            instantiate(code_hash, 100, new(), &[]),
+            Err(env::EnvError::CodeNotFound),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn debug_messages_are_recorded() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        env::debug_message("first");
+        env::test::advance_block::<env::DefaultEnvTypes>()?;
+        env::debug_message("second");
+        let debug_messages = env::test::drain_debug_messages::<env::DefaultEnvTypes>()?;
+        let recorded = debug_messages
+            .iter()
+            .map(|debug_message| {
+                (
+                    debug_message.message.as_str(),
+                    debug_message.block_number,
+                    debug_message.caller.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            recorded,
+            vec![
+                ("first", 0, accounts.alice.clone()),
+                ("second", 1, accounts.alice),
+            ]
+        );
+        // Debug messages are not printed to the console.
+        assert_eq!(env::test::recorded_printlns().count(), 0);
+        assert!(env::test::drain_debug_messages::<env::DefaultEnvTypes>()?.is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn env_call_trace_records_invocations_in_order() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let key = Key::from([0x42; 32]);
+        // Invocations are not traced unless enabled.
+        let _ = env::account_id::<env::DefaultEnvTypes>()?;
+        assert_eq!(env::test::env_call_trace(), Vec::new());
+        env::test::enable_env_call_trace();
+        // A message that increments a counter and pays out the caller.
+        let counter = env::get_contract_storage::<u32>(&key)?.unwrap_or_default();
+        env::set_contract_storage(&key, &(counter + 1));
+        let caller = env::caller::<env::DefaultEnvTypes>()?;
+        let _ = env::transfer_saturating::<env::DefaultEnvTypes>(caller, 100)?;
+        let trace = env::test::drain_env_call_trace();
+        // The balance query and transfer of `transfer_saturating` are not traced.
+        assert_eq!(
+            trace.iter().map(|call| call.name).collect::<Vec<_>>(),
+            vec![
+                "get_contract_storage",
+                "set_contract_storage",
+                "caller",
+                "transfer_saturating",
+            ],
+        );
+        assert_eq!(trace[0].decode_args::<Key>()?, key);
+        assert_eq!(trace[1].decode_args::<Key>()?, key);
+        assert!(trace[2].args.is_empty());
+        assert_eq!(
+            trace[3].decode_args::<(
+                <env::DefaultEnvTypes as env::EnvTypes>::AccountId,
+                <env::DefaultEnvTypes as env::EnvTypes>::Balance,
+            )>()?,
+            (accounts.alice, 100),
+        );
+        assert_eq!(env::test::env_call_trace(), Vec::new());
+        Ok(())
+    })
+}
+
+#[test]
+fn hash_overrides_force_hash_dependent_branches() -> env::Result<()> {
+    use env::hash::Blake2x256;
+    // Emulates a lottery whose winning tickets hash to a leading zero byte.
+    fn is_winner(ticket: &[u8]) -> bool {
+        let mut output = [0x00; 32];
+        env::hash::blake2_256(ticket, &mut output);
+        output[0] == 0x00
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut expected = [0x00; 32];
+        env::hash::sha2_256(b"ticket", &mut expected);
+        assert!(!is_winner(b"ticket"));
+        env::test::set_hash_override::<Blake2x256>(b"ticket", [0x00; 32]);
+        assert!(is_winner(b"ticket"));
+        // Other inputs and other hash functions are hashed as usual.
+        assert!(!is_winner(b"other ticket"));
+        let mut output = [0x00; 32];
+        env::hash::sha2_256(b"ticket", &mut output);
+        assert_eq!(output, expected);
+        // Call handlers operate on the environment and hash as usual.
+        env::test::register_chain_extension(42, |ticket: Vec<u8>| is_winner(&ticket));
+        assert_eq!(
+            env::call_chain_extension::<Vec<u8>, bool>(42, &b"ticket".to_vec()),
+            Ok(false),
+        );
+        env::test::clear_hash_overrides();
+        assert!(!is_winner(b"ticket"));
+        Ok(())
+    })
+}
@@ -0,0 +1,651 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    instantiate,
+    AccountId,
+    Hash,
+    NEW,
+};
+use crate::env;
+use ink_primitives::Key;
+
+#[test]
+fn eval_contract_returns_registered_response() -> env::Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    use env::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        let call = || {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(accounts.bob.clone())
+                .exec_input(ExecutionInput::new(selector).push_arg(true))
+                .returns::<ReturnType<u64>>()
+                .fire()
+        };
+        assert_eq!(
+            call(),
+            Err(env::EnvError::OffChain(
+                OffChainError::UnregisteredCallResponse
+            )),
+        );
+        env::test::register_call_response::<env::DefaultEnvTypes, _>(
+            accounts.bob,
+            selector,
+            42_u64,
+        );
+        assert_eq!(call(), Ok(42_u64));
+        Ok(())
+    })
+}
+
+#[test]
+fn instantiate_contract_creates_endowed_account() -> env::Result<()> {
+    use env::call::ExecutionInput;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent.clone(), 1000)?;
+        let instantiate =
+            |endowment| instantiate(code_hash, endowment, ExecutionInput::new(NEW), &[]);
+        let first = instantiate(300)?;
+        let second = instantiate(200)?;
+        assert_ne!(first, second);
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(first),
+            Ok(300),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(second),
+            Ok(200),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(parent),
+            Ok(500),
+        );
+        assert_eq!(instantiate(501), Err(env::EnvError::TransferFailed));
+        Ok(())
+    })
+}
+
+#[test]
+fn terminate_contract_transfers_remaining_balance() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract.clone(), 100)?;
+        let key = Key::from([0x42; 32]);
+        env::set_contract_storage(&key, &1337);
+        let flags = env::test::catch_return(|| {
+            env::terminate_contract::<env::DefaultEnvTypes>(accounts.django.clone())
+        });
+        assert_eq!(flags, Some(env::ReturnFlags::default()));
+        let terminations = env::test::recorded_terminations::<env::DefaultEnvTypes>()?;
+        assert_eq!(terminations.len(), 1);
+        assert_eq!(terminations[0].contract, contract);
+        assert_eq!(terminations[0].beneficiary, accounts.django);
+        assert_eq!(terminations[0].transferred, 100);
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.django),
+            Ok(100),
+        );
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(None));
+        Ok(())
+    })
+}
+
+#[test]
+fn restore_contract_requires_matching_tombstone() -> env::Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    use env::call::{
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let restorer_code = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let code_hash = Hash::from([0x42; 32]);
+        let victim = env::account_id::<env::DefaultEnvTypes>()?;
+        let restorer = instantiate(restorer_code, 0, ExecutionInput::new(NEW), &[])?;
+        let key = Key::from([0x42; 32]);
+        let filtered_key = Key::from([0x43; 32]);
+        env::set_contract_storage(&key, &1337);
+        env::test::evict_contract::<env::DefaultEnvTypes>(victim.clone(), code_hash)?;
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.alice,
+            restorer,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        env::set_contract_storage(&key, &1337);
+        env::set_contract_storage(&filtered_key, &true);
+        let restore = |filtered_keys: &[Key]| -> env::Result<_> {
+            env::restore_contract::<env::DefaultEnvTypes>(
+                victim.clone(),
+                code_hash,
+                20,
+                filtered_keys,
+            );
+            let mut attempts =
+                env::test::take_restoration_attempts::<env::DefaultEnvTypes>()?;
+            assert_eq!(attempts.len(), 1);
+            let attempt = attempts.remove(0);
+            assert_eq!(attempt.contract, victim);
+            Ok(attempt.outcome)
+        };
+        assert_eq!(
+            restore(&[])?,
+            Err(env::EnvError::OffChain(OffChainError::TombstoneMismatch)),
+        );
+        assert_eq!(restore(&[filtered_key])?, Ok(()));
+        env::test::pop_execution_context();
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(Some(1337)));
+        assert_eq!(env::get_contract_storage::<bool>(&filtered_key), Ok(None));
+        assert_eq!(
+            env::test::get_contract_rent_allowance::<env::DefaultEnvTypes>(victim),
+            Ok(20),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn account_nonce_increments_once_per_instantiation() -> env::Result<()> {
+    use env::call::ExecutionInput;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent.clone(), 1000)?;
+        let instantiate =
+            |endowment| instantiate(code_hash, endowment, ExecutionInput::new(NEW), &[]);
+        assert_eq!(env::account_nonce::<env::DefaultEnvTypes>(), Ok(0));
+        let child = instantiate(100)?;
+        assert_eq!(env::account_nonce::<env::DefaultEnvTypes>(), Ok(1));
+        instantiate(0)?;
+        assert_eq!(env::account_nonce::<env::DefaultEnvTypes>(), Ok(2));
+        // Failed instantiations do not increment the nonce.
+        assert!(instantiate(10_000).is_err());
+        assert_eq!(env::account_nonce::<env::DefaultEnvTypes>(), Ok(2));
+        // Transfers originating from the account increment the nonce as well.
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 10)?;
+        assert_eq!(env::account_nonce::<env::DefaultEnvTypes>(), Ok(3));
+        assert_eq!(
+            env::test::get_account_nonce::<env::DefaultEnvTypes>(parent),
+            Ok(3)
+        );
+        // Receiving accounts keep their nonce.
+        assert_eq!(
+            env::test::get_account_nonce::<env::DefaultEnvTypes>(child),
+            Ok(0)
+        );
+        assert_eq!(
+            env::test::get_account_nonce::<env::DefaultEnvTypes>(accounts.bob),
+            Ok(0)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn return_raw_bytes_verbatim() -> env::Result<()> {
+    use crate::env::engine::{
+        off_chain::EnvInstance,
+        OnInstance,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::test::get_return_value(), Ok(None));
+        // Already encoded output of another contract.
+        let forwarded = scale::Encode::encode(&(42_u32, true));
+        // The real `return_raw` exits the process, so we use
+        // the non-diverging part of it to inspect the output.
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            instance.return_raw_impl(&forwarded)
+        })?;
+        assert_eq!(env::test::get_return_value(), Ok(Some(forwarded)));
+        Ok(())
+    })
+}
+
+#[test]
+fn last_return_value_works() -> env::Result<()> {
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    struct Returned {
+        value: u32,
+        flag: bool,
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::test::last_return_value::<Returned>(), Ok(None));
+        // Completing normally does not return a value.
+        assert_eq!(env::test::catch_return(|| ()), None);
+        let flags = env::test::catch_return(|| {
+            env::return_value(
+                env::ReturnFlags::default(),
+                &Returned {
+                    value: 42,
+                    flag: true,
+                },
+            )
+        });
+        assert_eq!(flags, Some(env::ReturnFlags::default()));
+        assert_eq!(
+            env::test::last_return_value::<Returned>(),
+            Ok(Some(Returned {
+                value: 42,
+                flag: true,
+            })),
+        );
+        // Reverting returns are caught the same way.
+        let reverted = env::ReturnFlags::default().set_reverted(true);
+        let flags = env::test::catch_return(|| env::return_value(reverted, &1337_u32));
+        assert_eq!(flags, Some(reverted));
+        assert_eq!(env::test::last_return_value::<u32>(), Ok(Some(1337)));
+        Ok(())
+    })
+}
+
+#[test]
+fn instantiate_with_salt_derives_reproducible_account_ids() -> env::Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    use env::call::ExecutionInput;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent, 1000)?;
+        let instantiate =
+            |salt: &[u8]| instantiate(code_hash, 100, ExecutionInput::new(NEW), salt);
+        let snapshot = env::test::snapshot();
+        let first = instantiate(b"first")?;
+        let second = instantiate(b"second")?;
+        assert_ne!(first, second);
+        // Salted account IDs do not depend on the nonce of the creator.
+        env::test::restore(&snapshot);
+        instantiate(&[])?;
+        assert_eq!(instantiate(b"second"), Ok(second.clone()));
+        assert_eq!(instantiate(b"first"), Ok(first));
+        assert_eq!(
+            instantiate(b"second"),
+            Err(env::EnvError::OffChain(OffChainError::DuplicateContract)),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn eval_contract_respects_gas_limit() -> env::Result<()> {
+    use env::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        let call = |gas_limit| {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(accounts.bob.clone())
+                .gas_limit(gas_limit)
+                .exec_input(ExecutionInput::new(selector))
+                .returns::<ReturnType<u64>>()
+                .fire()
+        };
+        env::test::set_gas_metering(env::test::GasCosts::default(), 1000);
+        env::test::register_call_response_with_gas::<env::DefaultEnvTypes, _>(
+            accounts.bob.clone(),
+            selector,
+            42_u64,
+            500,
+        );
+        // The callee runs out of gas but the caller can carry on.
+        assert_eq!(call(100), Err(env::EnvError::OutOfGas));
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(900));
+        assert_eq!(call(600), Ok(42));
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(400));
+        assert_eq!(call(0), Err(env::EnvError::OutOfGas));
+        Ok(())
+    })
+}
+
+#[test]
+fn panicking_callee_traps() -> env::Result<()> {
+    use env::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        env::test::register_call_handler::<env::DefaultEnvTypes, _>(
+            accounts.bob.clone(),
+            selector,
+            |input: &[u8]| {
+                let (_, value) =
+                    <([u8; 4], u32) as scale::Decode>::decode(&mut &input[..])
+                        .expect("encountered invalid input");
+                if value == 0 {
+                    panic!("division by zero")
+                }
+                scale::Encode::encode(&(100 / value))
+            },
+        );
+        let call = |value: u32| {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(accounts.bob.clone())
+                .exec_input(ExecutionInput::new(selector).push_arg(value))
+                .returns::<ReturnType<u32>>()
+                .fire()
+        };
+        assert_eq!(call(4), Ok(25));
+        assert_eq!(call(0), Err(env::EnvError::CalleeTrapped));
+        // The caller can carry on after the callee has trapped.
+        assert_eq!(call(5), Ok(20));
+        Ok(())
+    })
+}
+
+#[test]
+fn uploaded_code_answers_calls_to_its_instances() -> env::Result<()> {
+    use env::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent, 1000)?;
+        let new = Selector::new([0x00, 0x00, 0x00, 0x01]);
+        let get = Selector::new([0x00, 0x00, 0x00, 0x02]);
+        // Each instance of the counter starts at its constructor argument.
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|input| {
+            let (_, mut value) =
+                <([u8; 4], u32) as scale::Decode>::decode(&mut &input[..])
+                    .expect("encountered invalid constructor input");
+            move |_: &[u8]| {
+                value += 1;
+                scale::Encode::encode(&value)
+            }
+        })?;
+        let instantiate = |code_hash, value: u32| {
+            instantiate(code_hash, 100, ExecutionInput::new(new).push_arg(value), &[])
+        };
+        let call = |callee: &AccountId| {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(callee.clone())
+                .exec_input(ExecutionInput::new(get))
+                .returns::<ReturnType<u32>>()
+                .fire()
+        };
+        let first = instantiate(code_hash, 10)?;
+        let second = instantiate(code_hash, 20)?;
+        assert_eq!(call(&first), Ok(11));
+        assert_eq!(call(&first), Ok(12));
+        assert_eq!(call(&second), Ok(21));
+        // Contracts cannot be instantiated from code that has not been uploaded.
+        assert_eq!(
+            instantiate(Hash::from([0x42; 32]), 0),
+            Err(env::EnvError::CodeNotFound),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn uploaded_code_may_operate_on_the_environment() -> env::Result<()> {
+    use env::call::ExecutionInput;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        // The constructor stores its argument.
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(move |input| {
+            let (_, value) = <([u8; 4], u32) as scale::Decode>::decode(&mut &input[..])
+                .expect("encountered invalid constructor input");
+            env::set_contract_storage(&key, &value);
+            |_: &[u8]| Vec::new()
+        })?;
+        instantiate(code_hash, 0, ExecutionInput::new(NEW).push_arg(1337_u32), &[])?;
+        assert_eq!(env::get_contract_storage::<u32>(&key), Ok(Some(1337)));
+        Ok(())
+    })
+}
+
+#[test]
+fn reentrant_calls_exhaust_the_call_stack() -> env::Result<()> {
+    use env::call::{
+        build_call,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        env::test::update_chain_spec(|chain_spec| chain_spec.set_max_call_depth(4))?;
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        env::test::register_call_response::<env::DefaultEnvTypes, _>(
+            accounts.bob.clone(),
+            selector,
+            (),
+        );
+        let call_bob = || {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(accounts.bob.clone())
+                .exec_input(ExecutionInput::new(selector))
+                .returns::<()>()
+                .fire()
+        };
+        // Bob calls back into the contract that calls bob again and so on.
+        let mut depth = 1;
+        let result = loop {
+            if let Err(error) = call_bob() {
+                break error
+            }
+            let (caller, callee) = if depth % 2 == 1 {
+                (contract.clone(), accounts.bob.clone())
+            } else {
+                (accounts.bob.clone(), contract.clone())
+            };
+            env::test::push_execution_context::<env::DefaultEnvTypes>(
+                caller,
+                callee,
+                1_000_000,
+                0,
+                env::test::CallData::new(selector),
+            );
+            depth += 1;
+        };
+        assert_eq!(result, env::EnvError::CallStackExhausted);
+        assert_eq!(depth, 4);
+        // The call stack is available again once the nested executions return.
+        for _ in 1..depth {
+            env::test::pop_execution_context();
+        }
+        assert_eq!(call_bob(), Ok(()));
+        Ok(())
+    })
+}
+
+#[test]
+fn failed_instantiation_refunds_the_endowment() -> env::Result<()> {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+    };
+    use env::call::ExecutionInput;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        // The constructor fails if its only argument is `true`.
+        let code_hash =
+            env::test::upload_fallible_code::<env::DefaultEnvTypes, _, _, _>(|input| {
+                let (_, fails) =
+                    <([u8; 4], bool) as scale::Decode>::decode(&mut &input[..])
+                        .expect("encountered invalid constructor input");
+                if fails {
+                    Err(())
+                } else {
+                    Ok(|_: &[u8]| Vec::<u8>::new())
+                }
+            })?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(parent.clone(), 1000)?;
+        let transfers = Rc::new(RefCell::new(Vec::new()));
+        let recorded = transfers.clone();
+        env::test::set_transfer_hook::<env::DefaultEnvTypes, _>(
+            move |from, to, value| recorded.borrow_mut().push((from, to, value)),
+        );
+        let instantiate = |fails: bool| {
+            instantiate(code_hash, 300, ExecutionInput::new(NEW).push_arg(fails), b"child")
+        };
+        assert_eq!(instantiate(true), Err(env::EnvError::CalleeReverted));
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(parent.clone()),
+            Ok(1000),
+        );
+        // The reverted endowment is not observed by the transfer hook.
+        assert_eq!(*transfers.borrow(), Vec::new());
+        // No contract account has been left behind at the derived account ID
+        // so that it is still available for a successful deployment.
+        let child = instantiate(false)?;
+        assert_eq!(*transfers.borrow(), vec![(parent.clone(), child.clone(), 300)]);
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(child),
+            Ok(300),
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(parent),
+            Ok(700),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn own_code_hash_works() -> env::Result<()> {
+    use env::call::{
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let child = instantiate(code_hash, 0, ExecutionInput::new(NEW), &[])?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        // Contracts instantiated from uploaded code know the hash of their code.
+        env::test::push_contract_execution_context::<env::DefaultEnvTypes>(
+            parent.clone(),
+            child,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(env::own_code_hash::<env::DefaultEnvTypes>(), Ok(code_hash));
+        env::test::pop_execution_context();
+        // The code hash of pre-existing contracts is set by the harness.
+        let parent_code_hash = Hash::from([0x42; 32]);
+        env::test::set_contract_code_hash::<env::DefaultEnvTypes>(
+            parent,
+            parent_code_hash,
+        )?;
+        assert_eq!(
+            env::own_code_hash::<env::DefaultEnvTypes>(),
+            Ok(parent_code_hash)
+        );
+        // User accounts have no code hash.
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.alice,
+            accounts.bob,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert!(env::own_code_hash::<env::DefaultEnvTypes>().is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn set_code_hash_upgrades_the_executed_contract() -> env::Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    use env::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let version = Selector::new([0x00, 0x00, 0x00, 0x01]);
+        let v1 = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| scale::Encode::encode(&1u32)
+        })?;
+        let v2 = env::test::upload_upgradable_code::<env::DefaultEnvTypes, _, _>(|| {
+            |_: &[u8]| scale::Encode::encode(&2u32)
+        })?;
+        // Code that depends on the input of its constructor cannot be upgraded to.
+        let v3 = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|input: &[u8]| {
+            let value = input.len() as u32;
+            move |_: &[u8]| scale::Encode::encode(&value)
+        })?;
+        let contract = instantiate(v1, 0, ExecutionInput::new(NEW), &[])?;
+        let call_version = || {
+            build_call::<env::DefaultEnvTypes>()
+                .callee(contract.clone())
+                .exec_input(ExecutionInput::new(version))
+                .returns::<ReturnType<u32>>()
+                .fire()
+        };
+        assert_eq!(call_version(), Ok(1));
+        // The contract upgrades itself to the second version of its code.
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::push_contract_execution_context::<env::DefaultEnvTypes>(
+            parent.clone(),
+            contract.clone(),
+            1_000_000,
+            0,
+            env::test::CallData::new(version),
+        );
+        assert_eq!(
+            env::set_code_hash::<env::DefaultEnvTypes>(Hash::from([0x42; 32])),
+            Err(env::EnvError::CodeNotFound),
+        );
+        // A failed upgrade leaves the code hash and the handler untouched.
+        assert_eq!(
+            env::set_code_hash::<env::DefaultEnvTypes>(v3),
+            Err(env::EnvError::OffChain(OffChainError::CodeNotUpgradable)),
+        );
+        assert_eq!(env::own_code_hash::<env::DefaultEnvTypes>(), Ok(v1));
+        env::test::pop_execution_context();
+        assert_eq!(call_version(), Ok(1));
+        env::test::push_contract_execution_context::<env::DefaultEnvTypes>(
+            parent,
+            contract.clone(),
+            1_000_000,
+            0,
+            env::test::CallData::new(version),
+        );
+        env::set_code_hash::<env::DefaultEnvTypes>(v2)?;
+        assert_eq!(env::own_code_hash::<env::DefaultEnvTypes>(), Ok(v2));
+        env::test::pop_execution_context();
+        assert_eq!(call_version(), Ok(2));
+        Ok(())
+    })
+}
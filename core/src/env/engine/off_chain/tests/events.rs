@@ -0,0 +1,347 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+
+#[test]
+fn emitted_events_are_recorded_in_order() -> env::Result<()> {
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    struct Transfer {
+        value: u128,
+    }
+    impl env::Topics<env::DefaultEnvTypes> for Transfer {
+        fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+            Vec::new()
+        }
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::emit_event::<env::DefaultEnvTypes, _>(Transfer { value: 1 });
+        env::emit_event::<env::DefaultEnvTypes, _>(Transfer { value: 2 });
+        let events = env::test::emitted_events::<env::DefaultEnvTypes>()?;
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.topics.is_empty()));
+        assert_eq!(events[0].decode::<Transfer>()?, Transfer { value: 1 });
+        assert_eq!(events[1].decode::<Transfer>()?, Transfer { value: 2 });
+        Ok(())
+    })
+}
+
+#[test]
+fn block_finalization_hook_observes_block_events() -> env::Result<()> {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+    };
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    struct Scheduled {
+        job: u32,
+    }
+    impl env::Topics<env::DefaultEnvTypes> for Scheduled {
+        fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+            Vec::new()
+        }
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let observed = Rc::new(RefCell::new(Vec::new()));
+        let observed_by_hook = observed.clone();
+        env::test::set_block_finalization_hook::<env::DefaultEnvTypes, _>(
+            move |events| {
+                let jobs = events
+                    .iter()
+                    .map(|event| event.decode::<Scheduled>().unwrap().job)
+                    .collect::<Vec<_>>();
+                observed_by_hook.borrow_mut().push(jobs);
+            },
+        );
+        env::emit_event::<env::DefaultEnvTypes, _>(Scheduled { job: 1 });
+        env::emit_event::<env::DefaultEnvTypes, _>(Scheduled { job: 2 });
+        env::test::advance_block::<env::DefaultEnvTypes>()?;
+        env::test::advance_block::<env::DefaultEnvTypes>()?;
+        env::emit_event::<env::DefaultEnvTypes, _>(Scheduled { job: 3 });
+        env::test::advance_block::<env::DefaultEnvTypes>()?;
+        assert_eq!(*observed.borrow(), vec![vec![1, 2], vec![], vec![3]]);
+        // All events are still recorded globally.
+        assert_eq!(env::test::recorded_events().count(), 3);
+        Ok(())
+    })
+}
+
+/// An event with the given number of topics and payload.
+#[derive(scale::Encode)]
+struct LimitedEvent {
+    topics: u32,
+    payload: Vec<u8>,
+}
+
+impl env::Topics<env::DefaultEnvTypes> for LimitedEvent {
+    fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+        vec![env::Hash::from([0x01; 32]); self.topics as usize]
+    }
+}
+
+/// Emits the event after limiting events to 2 topics and 16 bytes.
+fn emit_limited_event(event: LimitedEvent) -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::update_chain_spec(|chain_spec| {
+            chain_spec.set_max_event_topics(2);
+            chain_spec.set_max_event_size(16);
+        })?;
+        env::emit_event::<env::DefaultEnvTypes, _>(event);
+        assert_eq!(env::test::recorded_events().count(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn event_within_limits_is_recorded() -> env::Result<()> {
+    emit_limited_event(LimitedEvent {
+        topics: 2,
+        payload: vec![0x00; 4],
+    })
+}
+
+#[test]
+#[should_panic(expected = "emitted event has 3 topics but at most 2 topics are allowed")]
+fn event_with_too_many_topics_is_rejected() {
+    let _ = emit_limited_event(LimitedEvent {
+        topics: 3,
+        payload: Vec::new(),
+    });
+}
+
+#[test]
+#[should_panic(expected = "emitted event has 37 bytes but at most 16 bytes are allowed")]
+fn event_with_too_large_payload_is_rejected() {
+    let _ = emit_limited_event(LimitedEvent {
+        topics: 0,
+        payload: vec![0x00; 32],
+    });
+}
+
+/// An event with two topic fields.
+#[derive(scale::Encode)]
+struct Transfer {
+    from: u8,
+    to: u8,
+    value: u32,
+}
+
+impl env::Topics<env::DefaultEnvTypes> for Transfer {
+    fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+        vec![
+            env::topic_hash::<env::DefaultEnvTypes, _>(&self.from),
+            env::topic_hash::<env::DefaultEnvTypes, _>(&self.to),
+        ]
+    }
+}
+
+impl env::TopicFields for Transfer {
+    const TOPIC_FIELDS: &'static [&'static str] = &["from", "to"];
+}
+
+#[test]
+fn event_topic_by_field_name() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let transfer = |from, to| Transfer { from, to, value: 10 };
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(0x01, 0x02));
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(0x03, 0x04));
+        let topic = |event_index, field_name| {
+            env::test::event_topic::<env::DefaultEnvTypes, Transfer>(
+                event_index,
+                field_name,
+            )
+        };
+        // The topics are the hashes of the encoded field values.
+        let hashed = |value: u8| {
+            let mut output = [0x00; 32];
+            env::hash::blake2_256(&[value], &mut output);
+            Some(env::Hash::from(output))
+        };
+        assert_eq!(topic(0, "from")?, hashed(0x01));
+        assert_eq!(topic(0, "to")?, hashed(0x02));
+        assert_eq!(topic(1, "from")?, hashed(0x03));
+        assert_eq!(topic(1, "to")?, hashed(0x04));
+        // Fields that are no topics and events that have not been emitted.
+        assert_eq!(topic(0, "value")?, None);
+        assert_eq!(topic(2, "from")?, None);
+        Ok(())
+    })
+}
+
+#[test]
+fn emitted_events_are_attributed_to_their_call() -> env::Result<()> {
+    use env::call::Selector;
+    #[derive(Debug, PartialEq, scale::Encode, scale::Decode)]
+    struct Incremented {
+        by: u32,
+    }
+    impl env::Topics<env::DefaultEnvTypes> for Incremented {
+        fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+            Vec::new()
+        }
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        // Emulates a single call to a message emitting the event.
+        let call = |by: u32| {
+            env::test::push_execution_context::<env::DefaultEnvTypes>(
+                accounts.bob.clone(),
+                contract.clone(),
+                1_000_000,
+                0,
+                env::test::CallData::new(Selector::new([0x00; 4])),
+            );
+            env::emit_event::<env::DefaultEnvTypes, _>(Incremented { by });
+            env::test::pop_execution_context();
+        };
+        call(1);
+        call(2);
+        env::emit_event::<env::DefaultEnvTypes, _>(Incremented { by: 3 });
+        env::emit_event::<env::DefaultEnvTypes, _>(Incremented { by: 4 });
+        let events = env::test::emitted_events::<env::DefaultEnvTypes>()?;
+        let recorded = events
+            .iter()
+            .map(|event| Ok((event.sequence, event.call_index, event.decode()?)))
+            .collect::<env::Result<Vec<(u64, u32, Incremented)>>>()?;
+        assert_eq!(
+            recorded,
+            vec![
+                (0, 0, Incremented { by: 1 }),
+                (1, 0, Incremented { by: 2 }),
+                (2, 0, Incremented { by: 3 }),
+                (3, 1, Incremented { by: 4 }),
+            ]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn assert_no_events_only_considers_the_most_recent_call() -> env::Result<()> {
+    use env::call::Selector;
+    #[derive(scale::Encode, scale::Decode)]
+    struct Incremented {
+        by: u32,
+    }
+    impl env::Topics<env::DefaultEnvTypes> for Incremented {
+        fn topics(&self) -> env::TopicHashes<env::DefaultEnvTypes> {
+            Vec::new()
+        }
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        // Emulates a message that only lets alice increment.
+        let call = |caller: env::AccountId, by: u32| -> env::Result<()> {
+            env::test::push_execution_context::<env::DefaultEnvTypes>(
+                caller,
+                contract.clone(),
+                1_000_000,
+                0,
+                env::test::CallData::new(Selector::new([0x00; 4])),
+            );
+            let result = if env::caller::<env::DefaultEnvTypes>()? == accounts.alice {
+                env::emit_event::<env::DefaultEnvTypes, _>(Incremented { by });
+                env::emit_event::<env::DefaultEnvTypes, _>(Incremented { by });
+                Ok(())
+            } else {
+                Err(env::EnvError::CalleeTrapped)
+            };
+            env::test::pop_execution_context();
+            result
+        };
+        env::test::assert_no_events();
+        call(accounts.alice.clone(), 1)?;
+        assert_eq!(env::test::event_count(), 2);
+        // The rejected call emits nothing even though earlier calls did.
+        assert_eq!(call(accounts.bob.clone(), 2), Err(env::EnvError::CalleeTrapped));
+        env::test::assert_no_events();
+        assert_eq!(env::test::recorded_events().count(), 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn constructor_events_are_attributed_to_the_instantiation() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        // Emulates the constructor of a token minting its initial supply.
+        let new = |initial_supply: u32| {
+            env::emit_event::<env::DefaultEnvTypes, _>(Transfer {
+                from: 0x00,
+                to: 0x01,
+                value: initial_supply,
+            });
+        };
+        env::test::execution_context::<env::DefaultEnvTypes>(
+            accounts.alice.clone(),
+            contract.clone(),
+        )
+        .instantiation()
+        .push()?;
+        new(1_000);
+        env::test::pop_execution_context();
+        env::test::execution_context::<env::DefaultEnvTypes>(accounts.alice, contract)
+            .push()?;
+        env::emit_event::<env::DefaultEnvTypes, _>(Transfer {
+            from: 0x01,
+            to: 0x02,
+            value: 10,
+        });
+        env::test::pop_execution_context();
+        let events = env::test::emitted_events::<env::DefaultEnvTypes>()?;
+        let recorded = events
+            .iter()
+            .map(|event| {
+                let transfer = event.decode::<(u8, u8, u32)>()?;
+                Ok((event.during_instantiation, transfer))
+            })
+            .collect::<env::Result<Vec<_>>>()?;
+        assert_eq!(
+            recorded,
+            vec![(true, (0x00, 0x01, 1_000)), (false, (0x01, 0x02, 10))]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn reset_events_clears_earlier_phases() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let transfer = |value| Transfer {
+            from: 0x01,
+            to: 0x02,
+            value,
+        };
+        let recorded = || -> env::Result<Vec<(u64, u32)>> {
+            env::test::emitted_events::<env::DefaultEnvTypes>()?
+                .iter()
+                .map(|event| Ok((event.sequence, event.decode::<(u8, u8, u32)>()?.2)))
+                .collect()
+        };
+        // Phase one sets up the state.
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(1));
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(2));
+        env::test::reset_events(false);
+        assert_eq!(recorded()?, vec![]);
+        // Phase two continues the sequence numbers of phase one.
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(3));
+        assert_eq!(recorded()?, vec![(2, 3)]);
+        // Phase three starts over with the sequence numbers.
+        env::test::reset_events(true);
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(4));
+        env::emit_event::<env::DefaultEnvTypes, _>(transfer(5));
+        assert_eq!(recorded()?, vec![(0, 4), (1, 5)]);
+        Ok(())
+    })
+}
@@ -0,0 +1,205 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+use ink_primitives::Key;
+
+#[test]
+fn execution_context_builder_validates_caller() -> env::Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<DefaultEnvTypes>()?;
+        env::test::execution_context::<DefaultEnvTypes>(accounts.bob.clone(), callee.clone())
+            .transferred_value(100)
+            .push()?;
+        assert_eq!(env::caller::<DefaultEnvTypes>(), Ok(accounts.bob));
+        assert_eq!(env::transferred_balance::<DefaultEnvTypes>(), Ok(100));
+        env::test::pop_execution_context();
+        assert_eq!(
+            env::test::execution_context::<DefaultEnvTypes>(accounts.django, callee.clone())
+                .transferred_value(1)
+                .push(),
+            Err(env::EnvError::TransferFailed),
+        );
+        let unknown = env::AccountId::from([0xFF; 32]);
+        assert!(
+            env::test::execution_context::<DefaultEnvTypes>(unknown, callee)
+                .push()
+                .is_err()
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn decode_input_from_raw_call_data() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut raw_call_data = vec![0xDE, 0xAD, 0xBE, 0xEF];
+        raw_call_data.extend(scale::Encode::encode(&(42_u32, true)));
+        env::test::set_raw_call_data(raw_call_data)?;
+        assert_eq!(
+            env::decode_input::<([u8; 4], u32, bool)>()?,
+            ([0xDE, 0xAD, 0xBE, 0xEF], 42, true)
+        );
+        // Malformed input that is missing the last argument.
+        env::test::set_raw_call_data(vec![0xDE, 0xAD, 0xBE, 0xEF, 42, 0, 0, 0])?;
+        assert!(env::decode_input::<([u8; 4], u32, bool)>().is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn missing_callee_account_is_reported() -> env::Result<()> {
+    use env::call::Selector;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let key = Key::from([0x42; 32]);
+        // Call a callee for which there is no account at all.
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.alice.clone(),
+            [0xFF; 32].into(),
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(
+            env::balance::<env::DefaultEnvTypes>(),
+            Err(env::EnvError::AccountNotFound)
+        );
+        assert_eq!(
+            env::get_contract_storage::<i32>(&key),
+            Err(env::EnvError::AccountNotFound)
+        );
+        env::test::pop_execution_context();
+        // Call a callee that is a user account instead of a contract.
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.alice,
+            accounts.bob,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(
+            env::get_contract_storage::<i32>(&key),
+            Err(env::EnvError::NotAContract)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn encode_call_works() -> env::Result<()> {
+    use env::call::Selector;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let selector = Selector::new([0xDE, 0xAD, 0xBE, 0xEF]);
+        let call_data = env::test::encode_call(selector, &(42_u32, true));
+        assert_eq!(&call_data[..4], &[0xDE, 0xAD, 0xBE, 0xEF]);
+        env::test::set_raw_call_data(call_data)?;
+        assert_eq!(
+            env::decode_input::<([u8; 4], u32, bool)>(),
+            Ok(([0xDE, 0xAD, 0xBE, 0xEF], 42, true)),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn caller_is_origin_for_user_call() -> env::Result<()> {
+    use env::call::Selector;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        assert_eq!(env::caller_is_origin::<env::DefaultEnvTypes>(), Ok(true));
+        env::test::push_execution_context::<env::DefaultEnvTypes>(
+            accounts.bob,
+            accounts.charlie,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(env::caller_is_origin::<env::DefaultEnvTypes>(), Ok(true));
+        env::test::pop_execution_context();
+        Ok(())
+    })
+}
+
+#[test]
+fn caller_is_not_origin_for_contract_call() -> env::Result<()> {
+    use env::call::Selector;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::push_contract_execution_context::<env::DefaultEnvTypes>(
+            callee,
+            accounts.charlie,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(env::caller_is_origin::<env::DefaultEnvTypes>(), Ok(false));
+        env::test::pop_execution_context();
+        assert_eq!(env::caller_is_origin::<env::DefaultEnvTypes>(), Ok(true));
+        Ok(())
+    })
+}
+
+#[test]
+fn current_execution_context_can_be_inspected() -> env::Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        env::test::execution_context::<env::DefaultEnvTypes>(
+            accounts.alice.clone(),
+            accounts.charlie.clone(),
+        )
+        .transferred_value(10)
+        .gas(1234)
+        .push()?;
+        let exec_context =
+            env::test::current_execution_context::<env::DefaultEnvTypes>()?;
+        assert_eq!(exec_context.caller, accounts.alice);
+        assert_eq!(exec_context.callee, accounts.charlie);
+        assert_eq!(exec_context.transferred_value, 10);
+        assert_eq!(exec_context.gas, 1234);
+        // Pop the pushed and the initial execution context.
+        env::test::pop_execution_context();
+        env::test::pop_execution_context();
+        assert_eq!(
+            env::test::current_execution_context::<env::DefaultEnvTypes>().err(),
+            Some(env::EnvError::OffChain(
+                OffChainError::UninitializedExecutionContext
+            )),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn default_caller_persists_across_calls() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let callee = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_default_caller::<env::DefaultEnvTypes>(accounts.bob.clone())?;
+        // Emulates three messages that each record their caller.
+        let mut observed = Vec::new();
+        for _ in 0..3 {
+            observed.push(env::caller::<env::DefaultEnvTypes>()?);
+        }
+        assert_eq!(observed, vec![accounts.bob.clone(); 3]);
+        // Execution contexts of individual calls take precedence.
+        env::test::execution_context::<env::DefaultEnvTypes>(
+            accounts.charlie.clone(),
+            callee,
+        )
+        .push()?;
+        assert_eq!(env::caller::<env::DefaultEnvTypes>(), Ok(accounts.charlie));
+        env::test::pop_execution_context();
+        assert_eq!(env::caller::<env::DefaultEnvTypes>(), Ok(accounts.bob));
+        Ok(())
+    })
+}
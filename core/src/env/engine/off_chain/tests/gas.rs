@@ -0,0 +1,182 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+use ink_primitives::Key;
+
+#[test]
+fn gas_price() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let gas_price= 2u32;
+        env::test::update_chain_spec(|chain_spec| {
+            chain_spec.set_gas_price::<env::DefaultEnvTypes>(gas_price.into())
+        })?;
+
+        assert_eq!(2u128, env::weight_to_fee::<env::DefaultEnvTypes>(1).unwrap());
+        assert_eq!(20u128, env::weight_to_fee::<env::DefaultEnvTypes>(10).unwrap());
+        assert_eq!(6u128, env::weight_to_fee::<env::DefaultEnvTypes>(3).unwrap());
+
+        Ok(())
+    })
+}
+
+#[test]
+fn custom_weight_to_fee() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::update_chain_spec(|chain_spec| {
+            chain_spec.set_weight_to_fee::<env::DefaultEnvTypes, _>(|weight| {
+                let weight = u128::from(weight);
+                weight * weight + 1
+            })
+        })?;
+        assert_eq!(env::weight_to_fee::<env::DefaultEnvTypes>(0), Ok(1));
+        assert_eq!(env::weight_to_fee::<env::DefaultEnvTypes>(3), Ok(10));
+        assert_eq!(env::weight_to_fee::<env::DefaultEnvTypes>(10), Ok(101));
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_metering() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract, 100)?;
+        env::test::set_gas_metering(
+            env::test::GasCosts {
+                storage_read: 10,
+                storage_write: 20,
+                hashing: 5,
+                transfer: 50,
+            },
+            100,
+        );
+        let key = Key::from([0x42; 32]);
+        env::set_contract_storage(&key, &1);
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(80));
+        assert_eq!(env::get_contract_storage::<i32>(&key), Ok(Some(1)));
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(70));
+        let mut output = [0x00; 32];
+        env::hash::blake2_256(&[0x01], &mut output);
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(65));
+        env::transfer::<env::DefaultEnvTypes>(accounts.bob.clone(), 1)?;
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(15));
+        assert_eq!(
+            env::transfer::<env::DefaultEnvTypes>(accounts.bob, 1),
+            Err(env::EnvError::OutOfGas),
+        );
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(15));
+        Ok(())
+    })
+}
+
+#[test]
+fn hashing_without_enough_gas_consumes_all_gas_left() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::set_gas_metering(
+            env::test::GasCosts {
+                hashing: 5,
+                storage_write: 1,
+                ..Default::default()
+            },
+            8,
+        );
+        let mut output = [0x00; 32];
+        env::hash::blake2_256(&[0x01], &mut output);
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(3));
+        env::hash::blake2_256(&[0x01], &mut output);
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(0));
+        assert_eq!(
+            env::try_set_contract_storage(&Key::from([0x42; 32]), &1),
+            Err(env::EnvError::OutOfGas),
+        );
+        // Hashing by call handlers operating on the environment is not charged.
+        env::test::set_gas_metering(
+            env::test::GasCosts {
+                hashing: 5,
+                ..Default::default()
+            },
+            8,
+        );
+        env::test::register_chain_extension(42, |input: u8| {
+            let mut output = [0x00; 32];
+            env::hash::blake2_256(&[input], &mut output);
+            output[0]
+        });
+        env::call_chain_extension::<u8, u8>(42, &0x01)?;
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(8));
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_profiling() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::set_gas_metering(
+            env::test::GasCosts {
+                storage_read: 10,
+                storage_write: 20,
+                hashing: 5,
+                transfer: 50,
+            },
+            100,
+        );
+        env::test::enable_gas_profiling();
+        env::set_contract_storage(&Key::from([0x42; 32]), &1);
+        env::set_contract_storage(&Key::from([0x43; 32]), &2);
+        let mut output = [0x00; 32];
+        env::hash::keccak_256(&[0x01], &mut output);
+        let profile = env::test::take_gas_profile();
+        assert_eq!(profile.len(), 2);
+        assert_eq!(
+            profile.get(&env::test::HostFunction::SetContractStorage),
+            Some(&env::test::GasUsage { count: 2, gas: 40 }),
+        );
+        assert_eq!(
+            profile.get(&env::test::HostFunction::HashKeccak256),
+            Some(&env::test::GasUsage { count: 1, gas: 5 }),
+        );
+        assert_eq!(env::gas_left::<env::DefaultEnvTypes>(), Ok(55));
+        assert!(env::test::take_gas_profile().is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn economic_parameters_can_be_configured() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::minimum_balance::<env::DefaultEnvTypes>(), Ok(42));
+        assert_eq!(env::tombstone_deposit::<env::DefaultEnvTypes>(), Ok(16));
+        env::test::set_minimum_balance::<env::DefaultEnvTypes>(1_000);
+        env::test::set_tombstone_deposit::<env::DefaultEnvTypes>(500);
+        assert_eq!(env::minimum_balance::<env::DefaultEnvTypes>(), Ok(1_000));
+        assert_eq!(env::tombstone_deposit::<env::DefaultEnvTypes>(), Ok(500));
+        Ok(())
+    })
+}
+
+#[test]
+fn weight_to_fee_v2_prices_both_dimensions() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::update_chain_spec(|chain_spec| {
+            chain_spec.set_ref_time_price::<env::DefaultEnvTypes>(3);
+            chain_spec.set_proof_size_price::<env::DefaultEnvTypes>(7);
+        })?;
+        assert_eq!(env::weight_to_fee_v2::<env::DefaultEnvTypes>(10, 0), Ok(30));
+        assert_eq!(env::weight_to_fee_v2::<env::DefaultEnvTypes>(0, 10), Ok(70));
+        assert_eq!(env::weight_to_fee_v2::<env::DefaultEnvTypes>(10, 100), Ok(730));
+        // The scalar conversion keeps using the gas price.
+        assert_eq!(env::weight_to_fee::<env::DefaultEnvTypes>(10), Ok(1_000));
+        Ok(())
+    })
+}
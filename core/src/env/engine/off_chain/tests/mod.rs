@@ -0,0 +1,70 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod balances;
+mod chain;
+mod contracts;
+mod events;
+mod exec_context;
+mod gas;
+mod runtime;
+mod storage;
+
+use crate::env::{
+    self,
+    call::{
+        build_create,
+        ExecutionInput,
+        FromAccountId,
+        Selector,
+    },
+};
+
+type AccountId = <env::DefaultEnvTypes as env::EnvTypes>::AccountId;
+type Balance = <env::DefaultEnvTypes as env::EnvTypes>::Balance;
+type Hash = <env::DefaultEnvTypes as env::EnvTypes>::Hash;
+
+/// The selector of the constructors of contracts instantiated from uploaded code.
+const NEW: Selector = Selector::new([0xDE, 0xAD, 0xBE, 0xEF]);
+
+/// A contract instantiated from uploaded code.
+struct Contract(AccountId);
+
+impl FromAccountId<env::DefaultEnvTypes> for Contract {
+    fn from_account_id(account_id: AccountId) -> Self {
+        Self(account_id)
+    }
+}
+
+/// Instantiates a contract from the uploaded code with the given code hash
+/// and returns its account ID.
+///
+/// An empty salt derives the account ID from the nonce of the creator.
+fn instantiate<Args>(
+    code_hash: Hash,
+    endowment: Balance,
+    exec_input: ExecutionInput<Args>,
+    salt: &[u8],
+) -> env::Result<AccountId>
+where
+    Args: scale::Encode,
+{
+    build_create::<env::DefaultEnvTypes, Contract>()
+        .code_hash(code_hash)
+        .endowment(endowment)
+        .exec_input(exec_input)
+        .salt_bytes(salt)
+        .instantiate()
+        .map(|Contract(account_id)| account_id)
+}
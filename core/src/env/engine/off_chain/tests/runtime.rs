@@ -0,0 +1,111 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+};
+
+#[test]
+fn register_chain_extension_works() -> Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::register_chain_extension(42, |input: u32| input + 1);
+        assert_eq!(env::call_chain_extension::<u32, u32>(42, &41), Ok(42));
+        assert_eq!(env::call_chain_extension::<u32, u32>(42, &0), Ok(1));
+        // Unregistered function IDs are reported with their ID.
+        assert_eq!(
+            env::call_chain_extension::<u32, u32>(7, &41),
+            Err(env::EnvError::OffChain(
+                OffChainError::UnregisteredChainExtension(7)
+            )),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn chain_extension_status_is_surfaced() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::test::register_chain_extension_with_status(42, |input: u32| (7, input + 1));
+        assert_eq!(
+            env::call_chain_extension_with_status::<u32, u32>(42, &41),
+            Ok((7, 42)),
+        );
+        // Non-zero status codes are errors if the status is not surfaced.
+        assert_eq!(
+            env::call_chain_extension::<u32, u32>(42, &41),
+            Err(env::EnvError::UnknownError),
+        );
+        // Plain chain extensions always succeed with status code zero.
+        env::test::register_chain_extension(43, |input: u32| input * 2);
+        assert_eq!(
+            env::call_chain_extension_with_status::<u32, u32>(43, &21),
+            Ok((0, 42)),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn runtime_calls_are_routed_to_their_handler() -> Result<()> {
+    use crate::env::engine::off_chain::OffChainError;
+    type AccountId = <env::DefaultEnvTypes as env::EnvTypes>::AccountId;
+    #[derive(scale::Encode, scale::Decode)]
+    enum BalancesCall {
+        Transfer { dest: AccountId, value: u128 },
+    }
+    #[derive(scale::Encode)]
+    struct UnknownCall;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        env::test::register_runtime_call::<env::DefaultEnvTypes, _, _>(
+            |context, call: BalancesCall| {
+                let BalancesCall::Transfer { dest, value } = call;
+                let origin = context.origin()?;
+                let origin_balance = context.balance(&origin)?;
+                let dest_balance = context.balance(&dest)?;
+                context.set_balance(&origin, origin_balance - value)?;
+                context.set_balance(&dest, dest_balance + value)
+            },
+        );
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract, 100)?;
+        let contract_balance = env::balance::<env::DefaultEnvTypes>()?;
+        let django_balance = env::test::get_account_balance::<env::DefaultEnvTypes>(
+            accounts.django.clone(),
+        )?;
+        env::call_runtime(&BalancesCall::Transfer {
+            dest: accounts.django.clone(),
+            value: 10,
+        })?;
+        assert_eq!(
+            env::balance::<env::DefaultEnvTypes>()?,
+            contract_balance - 10
+        );
+        assert_eq!(
+            env::test::get_account_balance::<env::DefaultEnvTypes>(accounts.django)?,
+            django_balance + 10
+        );
+        // Runtime calls of unregistered types are reported with their type name.
+        assert_eq!(
+            env::call_runtime(&UnknownCall),
+            Err(env::EnvError::OffChain(
+                OffChainError::UnregisteredRuntimeCall(core::any::type_name::<
+                    UnknownCall,
+                >())
+            )),
+        );
+        Ok(())
+    })
+}
@@ -0,0 +1,371 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+};
+use ink_primitives::Key;
+
+#[test]
+fn store_load_clear() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        assert_eq!(env::get_contract_storage::<()>(&key), Ok(None));
+        env::set_contract_storage(&key, &[0x05_u8; 5]);
+        assert_eq!(
+            env::get_contract_storage::<[i8; 5]>(&key),
+            Ok(Some([0x05; 5])),
+        );
+        env::clear_contract_storage(&key);
+        assert_eq!(env::get_contract_storage::<[u8; 5]>(&key), Ok(None));
+        Ok(())
+    })
+}
+
+#[test]
+fn key_add() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key00 = Key::from([0x0; 32]);
+        let key05 = key00 + 05_u64; // -> 5
+        let key10 = key00 + 10_u64; // -> 10         | same as key55
+        let key55 = key05 + 05_u64; // -> 5 + 5 = 10 | same as key10
+        env::set_contract_storage(&key55, &42);
+        assert_eq!(env::get_contract_storage::<i32>(&key10), Ok(Some(42)));
+        env::set_contract_storage(&key10, &1337);
+        assert_eq!(env::get_contract_storage::<i32>(&key55), Ok(Some(1337)));
+        Ok(())
+    })
+}
+
+#[test]
+fn key_add_sub() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key0a = Key::from([0x0; 32]);
+        let key1a = key0a + 1337_u64;
+        let key2a = key0a + 42_u64;
+        // let key3a = key0a + 52_u64;
+        // let key2b = key3a - 10_u64;
+        // let key1b = key2b - 42_u64;
+        // let key0b = key1b + 2000_u64 - 663_u64; // same as key1a
+        env::set_contract_storage(&key0a, &1);
+        env::set_contract_storage(&key1a, &2);
+        env::set_contract_storage(&key2a, &3);
+        // assert_eq!(env::get_contract_storage::<i32>(&key2b), Some(Ok(3)));
+        // assert_eq!(env::get_contract_storage::<i32>(&key1b), Some(Ok(1)));
+        // assert_eq!(env::get_contract_storage::<i32>(&key0b), Some(Ok(2)));
+        Ok(())
+    })
+}
+
+#[test]
+fn clear_contract_storage_reports_cleared_size() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        assert_eq!(env::clear_contract_storage(&key), None);
+        env::set_contract_storage(&key, &[0x05_u8; 5]);
+        assert_eq!(env::clear_contract_storage(&key), Some(5));
+        assert_eq!(env::clear_contract_storage(&key), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn set_contract_storage_reports_overwritten_size() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        assert_eq!(env::set_contract_storage(&key, &[0x05_u8; 5]), None);
+        assert_eq!(env::set_contract_storage(&key, &[0x06_u8; 2]), Some(5));
+        assert_eq!(env::get_contract_storage::<[u8; 2]>(&key), Ok(Some([0x06; 2])));
+        assert_eq!(env::set_contract_storage(&key, &()), Some(2));
+        assert_eq!(env::contains_contract_storage(&key), Some(0));
+        Ok(())
+    })
+}
+
+#[test]
+fn contains_contract_storage() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        let empty_key = Key::from([0x43; 32]);
+        assert_eq!(env::contains_contract_storage(&key), None);
+        env::set_contract_storage(&key, &[0x05_u8; 5]);
+        env::set_contract_storage(&empty_key, &());
+        assert_eq!(env::contains_contract_storage(&key), Some(5));
+        assert_eq!(env::contains_contract_storage(&empty_key), Some(0));
+        env::clear_contract_storage(&key);
+        assert_eq!(env::contains_contract_storage(&key), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_access_log() -> env::Result<()> {
+    use env::test::{
+        StorageAccess,
+        StorageAccessKind,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let key = Key::from([0x42; 32]);
+        // Accesses are not logged unless enabled.
+        env::set_contract_storage(&key, &1);
+        assert_eq!(
+            env::test::storage_access_log::<env::DefaultEnvTypes>(&contract),
+            Ok(Vec::new()),
+        );
+        env::test::enable_storage_access_log::<env::DefaultEnvTypes>(&contract)?;
+        env::set_contract_storage(&key, &2);
+        let _ = env::get_contract_storage::<i32>(&key)?;
+        env::clear_contract_storage(&key);
+        let access = |kind| StorageAccess { key, kind };
+        assert_eq!(
+            env::test::drain_storage_access_log::<env::DefaultEnvTypes>(&contract),
+            Ok(vec![
+                access(StorageAccessKind::Write),
+                access(StorageAccessKind::Read),
+                access(StorageAccessKind::Clear),
+            ]),
+        );
+        assert_eq!(
+            env::test::storage_access_log::<env::DefaultEnvTypes>(&contract),
+            Ok(Vec::new()),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn read_only_mode() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert!(!env::is_read_only());
+        env::test::set_read_only(true)?;
+        assert!(env::is_read_only());
+        env::test::set_read_only(false)?;
+        assert!(!env::is_read_only());
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_keys_are_listed_in_order() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let key_a = Key::from([0x01; 32]);
+        let key_b = Key::from([0x02; 32]);
+        let key_c = Key::from([0x03; 32]);
+        env::set_contract_storage(&key_c, &3);
+        env::set_contract_storage(&key_a, &1);
+        env::set_contract_storage(&key_b, &2);
+        env::clear_contract_storage(&key_b);
+        assert_eq!(
+            env::test::storage_keys::<env::DefaultEnvTypes>(&contract),
+            Ok(vec![key_a, key_c]),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_size_shrinks_when_clearing_storage() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let storage_size = || env::test::storage_size::<env::DefaultEnvTypes>(&contract);
+        assert_eq!(storage_size(), Ok(0));
+        let key_a = Key::from([0x01; 32]);
+        let key_b = Key::from([0x02; 32]);
+        let key_c = Key::from([0x03; 32]);
+        env::set_contract_storage(&key_a, &1_u32);
+        env::set_contract_storage(&key_b, &[0x42_u8; 10]);
+        env::set_contract_storage(&key_c, &true);
+        assert_eq!(storage_size(), Ok(4 + 10 + 1));
+        env::clear_contract_storage(&key_b);
+        assert_eq!(storage_size(), Ok(4 + 1));
+        Ok(())
+    })
+}
+
+#[test]
+fn rent_status_follows_storage_footprint() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract.clone(), 100)?;
+        let status = env::rent_status::<env::DefaultEnvTypes>()?;
+        assert_eq!(status.max_rent, 20);
+        assert_eq!(status.storage_size, 0);
+        assert_eq!(status.current_rent, 0);
+        assert!(!status.below_deposit);
+        env::set_contract_storage(&Key::from([0x01; 32]), &[0x42_u8; 80]);
+        let status = env::rent_status::<env::DefaultEnvTypes>()?;
+        assert_eq!(status.storage_size, 80);
+        assert_eq!(status.current_rent, 80);
+        assert!(!status.below_deposit);
+        // The balance of 100 no longer covers the rent of 88 on top of
+        // the tombstone deposit of 16.
+        env::set_contract_storage(&Key::from([0x02; 32]), &1337_u64);
+        let status = env::rent_status::<env::DefaultEnvTypes>()?;
+        assert_eq!(status.storage_size, 88);
+        assert_eq!(status.current_rent, 88);
+        assert!(status.below_deposit);
+        // Exactly covering both is not below the deposit anymore.
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract.clone(), 104)?;
+        assert!(!env::rent_status::<env::DefaultEnvTypes>()?.below_deposit);
+        env::test::set_account_balance::<env::DefaultEnvTypes>(contract, 103)?;
+        assert!(env::rent_status::<env::DefaultEnvTypes>()?.below_deposit);
+        Ok(())
+    })
+}
+
+#[test]
+fn account_storage_can_be_seeded() -> env::Result<()> {
+    use super::{
+        instantiate,
+        AccountId,
+        Balance,
+    };
+    use env::call::{
+        ExecutionInput,
+        Selector,
+    };
+    /// The storage key of the balance map entry of the owner.
+    fn balance_key(owner: &AccountId) -> Key {
+        let mut key = [0x00; 32];
+        key.copy_from_slice(&scale::Encode::encode(owner));
+        Key::from(key)
+    }
+    /// Mirrors a message reading the balance of the owner from the balance map.
+    fn balance_of(owner: &AccountId) -> env::Result<Balance> {
+        env::get_contract_storage::<Balance>(&balance_key(owner))
+            .map(|balance| balance.unwrap_or(0))
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|accounts| {
+        let code_hash = env::test::upload_code::<env::DefaultEnvTypes, _, _>(|_| {
+            |_: &[u8]| Vec::<u8>::new()
+        })?;
+        let parent = env::account_id::<env::DefaultEnvTypes>()?;
+        let new = Selector::new([0xDE, 0xAD, 0xBE, 0xEF]);
+        let token = instantiate(code_hash, 0, ExecutionInput::new(new), &[])?;
+        env::test::set_account_storage::<env::DefaultEnvTypes, Balance>(
+            &token,
+            &balance_key(&accounts.bob),
+            &1000,
+        )?;
+        assert_eq!(
+            env::test::get_account_storage::<env::DefaultEnvTypes, Balance>(
+                &token,
+                &balance_key(&accounts.bob),
+            ),
+            Ok(Some(1000)),
+        );
+        // The seeded entry is not visible in the storage of the callee.
+        assert_eq!(balance_of(&accounts.bob), Ok(0));
+        env::test::push_contract_execution_context::<env::DefaultEnvTypes>(
+            parent,
+            token,
+            1_000_000,
+            0,
+            env::test::CallData::new(Selector::new([0x00; 4])),
+        );
+        assert_eq!(balance_of(&accounts.bob), Ok(1000));
+        assert_eq!(balance_of(&accounts.alice), Ok(0));
+        env::test::pop_execution_context();
+        assert_eq!(
+            env::test::set_account_storage::<env::DefaultEnvTypes, Balance>(
+                &accounts.bob,
+                &balance_key(&accounts.bob),
+                &1,
+            ),
+            Err(env::EnvError::NotAContract),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_budget_rejects_writes_past_budget() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key_a = Key::from([0x01; 32]);
+        let key_b = Key::from([0x02; 32]);
+        let key_c = Key::from([0x03; 32]);
+        env::test::set_storage_budget(env::test::StorageBudget::bytes(1024));
+        assert_eq!(env::try_set_contract_storage(&key_a, &vec![0x01_u8; 500]), Ok(None));
+        assert_eq!(env::try_set_contract_storage(&key_b, &vec![0x02_u8; 500]), Ok(None));
+        // The encoded entries take 502 bytes each so the write would bring
+        // the storage to 1106 bytes.
+        assert_eq!(
+            env::try_set_contract_storage(&key_c, &vec![0x03_u8; 100]),
+            Err(env::EnvError::OutOfStorage),
+        );
+        assert_eq!(env::contains_contract_storage(&key_c), None);
+        // Shrinking an entry frees up space for further writes.
+        assert_eq!(
+            env::try_set_contract_storage(&key_a, &vec![0x01_u8; 400]),
+            Ok(Some(502)),
+        );
+        assert_eq!(
+            env::try_set_contract_storage(&key_c, &vec![0x03_u8; 100]),
+            Ok(None),
+        );
+        env::test::disable_storage_budget();
+        assert_eq!(
+            env::try_set_contract_storage(&key_c, &vec![0x03_u8; 500]),
+            Ok(Some(102)),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_budget_does_not_read_the_storage() -> env::Result<()> {
+    use env::test::{
+        StorageAccess,
+        StorageAccessKind,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let key = Key::from([0x42; 32]);
+        env::test::set_storage_budget(env::test::StorageBudget::bytes(1024));
+        env::test::enable_storage_access_log::<env::DefaultEnvTypes>(&contract)?;
+        env::set_contract_storage(&key, &1_u32);
+        env::set_contract_storage(&key, &2_u32);
+        assert_eq!(
+            env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract),
+            Ok((0, 2)),
+        );
+        assert_eq!(
+            env::test::storage_access_log::<env::DefaultEnvTypes>(&contract),
+            Ok(vec![
+                StorageAccess { key, kind: StorageAccessKind::Write },
+                StorageAccess { key, kind: StorageAccessKind::Write },
+            ]),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_budget_limits_number_of_writes() -> env::Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        env::test::set_storage_budget(env::test::StorageBudget::writes(2));
+        assert_eq!(env::try_set_contract_storage(&key, &1_u32), Ok(None));
+        assert_eq!(env::try_set_contract_storage(&key, &2_u32), Ok(Some(4)));
+        assert_eq!(
+            env::try_set_contract_storage(&key, &3_u32),
+            Err(env::EnvError::OutOfStorage),
+        );
+        assert_eq!(env::get_contract_storage::<u32>(&key), Ok(Some(2)));
+        Ok(())
+    })
+}
@@ -487,6 +487,15 @@ pub fn call_chain_extension(
     input: &[u8],
     output: &mut &mut [u8],
 ) -> Result {
+    ReturnCode(call_chain_extension_with_status(func_id, input, output)).into()
+}
+
+#[cfg(feature = "ink-unstable-chain-extensions")]
+pub fn call_chain_extension_with_status(
+    func_id: u32,
+    input: &[u8],
+    output: &mut &mut [u8],
+) -> u32 {
     let mut output_len = output.len() as u32;
     let ret_code = {
         unsafe {
@@ -500,7 +509,7 @@ pub fn call_chain_extension(
         }
     };
     extract_from_slice(output, output_len as usize);
-    ret_code.into()
+    ret_code.0
 }
 
 pub fn input(output: &mut &mut [u8]) {
@@ -24,9 +24,21 @@ use crate::env::{
         CreateParams,
         utils::ReturnType,
     },
+    hash::{
+        Blake2x128,
+        Blake2x256,
+        CryptoHash,
+        HashOutput,
+        Keccak256,
+        Sha2x256,
+        Twox128,
+        Twox256,
+        Twox64,
+    },
     Env,
     EnvError,
     EnvTypes,
+    RentStatus,
     Result,
     ReturnFlags,
     Topics,
@@ -95,12 +107,24 @@ impl EnvInstance {
 }
 
 impl Env for EnvInstance {
-    fn set_contract_storage<V>(&mut self, key: &Key, value: &V)
+    fn set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Option<u32>
     where
         V: scale::Encode,
     {
         let buffer = self.scoped_buffer().take_encoded(value);
         ext::set_storage(key.as_bytes(), &buffer[..]);
+        // The host function does not report the size of the overwritten value.
+        // Querying it up front would double the cost of every storage write.
+        None
+    }
+
+    fn try_set_contract_storage<V>(&mut self, key: &Key, value: &V) -> Result<Option<u32>>
+    where
+        V: scale::Encode,
+    {
+        // The host function traps instead of reporting a full storage so
+        // the write either succeeds or does not return at all.
+        Ok(self.set_contract_storage(key, value))
     }
 
     fn get_contract_storage<R>(&mut self, key: &Key) -> Result<Option<R>>
@@ -117,8 +141,22 @@ impl Env for EnvInstance {
         Ok(Some(decoded))
     }
 
-    fn clear_contract_storage(&mut self, key: &Key) {
-        ext::clear_storage(key.as_bytes())
+    fn contains_contract_storage(&mut self, key: &Key) -> Option<u32> {
+        // There is no dedicated host function to query the existence of
+        // a storage entry so we have to read it into the buffer instead.
+        let output = &mut self.scoped_buffer().take_rest();
+        match ext::get_storage(key.as_bytes(), output) {
+            Ok(_) => Some(output.len() as u32),
+            Err(ExtError::KeyNotFound) => None,
+            Err(_) => panic!("encountered unexpected error"),
+        }
+    }
+
+    fn clear_contract_storage(&mut self, key: &Key) -> Option<u32> {
+        ext::clear_storage(key.as_bytes());
+        // The host function does not report the size of the cleared value.
+        // Querying it up front would double the cost of every storage clear.
+        None
     }
 
     fn decode_input<T>(&mut self) -> Result<T>
@@ -128,6 +166,15 @@ impl Env for EnvInstance {
         self.get_property::<T>(ext::input)
     }
 
+    fn with_input<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        let full_scope = &mut self.scoped_buffer().take_rest();
+        ext::input(full_scope);
+        f(full_scope)
+    }
+
     fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
@@ -137,24 +184,29 @@ impl Env for EnvInstance {
         ext::return_value(flags, enc_return_value);
     }
 
-    fn println(&mut self, content: &str) {
-        ext::println(content)
+    fn return_raw(&mut self, flags: ReturnFlags, return_value: &[u8]) -> ! {
+        ext::return_value(flags, return_value);
     }
 
-    fn hash_keccak_256(input: &[u8], output: &mut [u8; 32]) {
-        ext::hash_keccak_256(input, output)
+    fn is_read_only(&mut self) -> bool {
+        // The host does not support read-only calls, yet.
+        false
     }
 
-    fn hash_blake2_256(input: &[u8], output: &mut [u8; 32]) {
-        ext::hash_blake2_256(input, output)
+    fn println(&mut self, content: &str) {
+        ext::println(content)
     }
 
-    fn hash_blake2_128(input: &[u8], output: &mut [u8; 16]) {
-        ext::hash_blake2_128(input, output)
+    fn debug_message(&mut self, message: &str) {
+        // The host has no dedicated debug buffer, yet.
+        ext::println(message)
     }
 
-    fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]) {
-        ext::hash_sha2_256(input, output)
+    fn hash_bytes<H>(input: &[u8], output: &mut <H as HashOutput>::Type)
+    where
+        H: CryptoHash,
+    {
+        <H as CryptoHash>::hash(input, output)
     }
 
     #[cfg(feature = "ink-unstable-chain-extensions")]
@@ -173,6 +225,82 @@ impl Env for EnvInstance {
         ext::call_chain_extension(func_id, enc_input, output)?;
         scale::Decode::decode(&mut &output[..]).map_err(Into::into)
     }
+
+    #[cfg(feature = "ink-unstable-chain-extensions")]
+    fn call_chain_extension_with_status<I, O>(
+        &mut self,
+        func_id: u32,
+        input: &I,
+    ) -> Result<(u32, O)>
+    where
+        I: scale::Encode,
+        O: scale::Decode,
+    {
+        let mut scope = self.scoped_buffer();
+        let enc_input = scope.take_encoded(input);
+        let output = &mut scope.take_rest();
+        let status = ext::call_chain_extension_with_status(func_id, enc_input, output);
+        let output = scale::Decode::decode(&mut &output[..])?;
+        Ok((status, output))
+    }
+}
+
+/// Conducts the TWOX hash computation as done by Substrate within the contract.
+///
+/// # Note
+///
+/// The contracts pallet does not provide host functions for TWOX hashes.
+/// The output is the concatenation of the little endian encoded 64-bit xxHash
+/// of the input seeded with `0`, `1`, etc. for every 8 bytes of output.
+fn twox(input: &[u8], output: &mut [u8]) {
+    use core::hash::Hasher as _;
+    for (seed, chunk) in output.chunks_exact_mut(8).enumerate() {
+        let mut hasher = twox_hash::XxHash64::with_seed(seed as u64);
+        hasher.write(input);
+        chunk.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+}
+
+impl CryptoHash for Sha2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        ext::hash_sha2_256(input, output)
+    }
+}
+
+impl CryptoHash for Keccak256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        ext::hash_keccak_256(input, output)
+    }
+}
+
+impl CryptoHash for Blake2x256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        ext::hash_blake2_256(input, output)
+    }
+}
+
+impl CryptoHash for Blake2x128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        ext::hash_blake2_128(input, output)
+    }
+}
+
+impl CryptoHash for Twox64 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        twox(input, output)
+    }
+}
+
+impl CryptoHash for Twox128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        twox(input, output)
+    }
+}
+
+impl CryptoHash for Twox256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        twox(input, output)
+    }
 }
 
 impl TypedEnv for EnvInstance {
@@ -180,6 +308,16 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::AccountId>(ext::caller)
     }
 
+    fn caller_is_origin<T: EnvTypes>(&mut self) -> Result<bool> {
+        // The contracts pallet does not expose the call stack to contracts.
+        Err(EnvError::UnknownError)
+    }
+
+    fn caller_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        // The contracts pallet does not expose the balances of other accounts.
+        Err(EnvError::UnknownError)
+    }
+
     fn transferred_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
         self.get_property::<T::Balance>(ext::value_transferred)
     }
@@ -188,6 +326,11 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::Balance>(ext::gas_left)
     }
 
+    fn account_nonce<T: EnvTypes>(&mut self) -> Result<u64> {
+        // The contracts pallet does not expose account nonces to contracts.
+        Err(EnvError::UnknownError)
+    }
+
     fn block_timestamp<T: EnvTypes>(&mut self) -> Result<T::Timestamp> {
         self.get_property::<T::Timestamp>(ext::now)
     }
@@ -196,6 +339,12 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::AccountId>(ext::address)
     }
 
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash> {
+        // The contracts pallet does not expose the code hash of the executed
+        // contract to contracts.
+        Err(EnvError::UnknownError)
+    }
+
     fn balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
         self.get_property::<T::Balance>(ext::balance)
     }
@@ -216,6 +365,11 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::Balance>(ext::tombstone_deposit)
     }
 
+    fn rent_status<T: EnvTypes>(&mut self) -> Result<RentStatus<T>> {
+        // The contracts pallet does not expose the storage footprint to contracts.
+        Err(EnvError::UnknownError)
+    }
+
     fn emit_event<T, Event>(&mut self, event: Event)
     where
         T: EnvTypes,
@@ -271,6 +425,8 @@ impl TypedEnv for EnvInstance {
         let enc_code_hash = scoped.take_encoded(params.code_hash());
         let enc_endowment = scoped.take_encoded(params.endowment());
         let enc_input = scoped.take_encoded(params.exec_input());
+        // The `seal_instantiate` of the supported contracts pallet version does
+        // not accept a salt, so `params.salt_bytes()` is ignored on-chain for now.
         // We support `AccountId` types with an encoding that requires up to
         // 1024 bytes. Beyond that limit ink! contracts will trap for now.
         // In the default configuration encoded `AccountId` require 32 bytes.
@@ -313,6 +469,15 @@ impl TypedEnv for EnvInstance {
         );
     }
 
+    fn set_code_hash<T>(&mut self, _code_hash: T::Hash) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        // The contracts pallet does not expose replacing the code of the
+        // executed contract to contracts.
+        Err(EnvError::UnknownError)
+    }
+
     fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
     where
         T: EnvTypes,
@@ -331,12 +496,56 @@ impl TypedEnv for EnvInstance {
         ext::transfer(enc_destination, enc_value).map_err(Into::into)
     }
 
+    fn transfer_keep_alive<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        // The runtime already refuses transfers that would leave the
+        // destination with a balance below the existential deposit.
+        self.transfer::<T>(destination, value)
+    }
+
+    fn transfer_saturating<T>(
+        &mut self,
+        destination: T::AccountId,
+        value: T::Balance,
+    ) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        let available = self.balance::<T>()?;
+        let value = if value < available { value } else { available };
+        self.transfer::<T>(destination, value)?;
+        Ok(value)
+    }
+
+    fn call_runtime<C>(&mut self, _call: &C) -> Result<()>
+    where
+        C: scale::Encode,
+    {
+        // The contracts pallet does not expose runtime calls to contracts.
+        Err(EnvError::UnknownError)
+    }
+
     fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance> {
         let output = &mut self.scoped_buffer().take_rest();
         ext::weight_to_fee(gas, output);
         scale::Decode::decode(&mut &output[..]).map_err(Into::into)
     }
 
+    fn weight_to_fee_v2<T: EnvTypes>(
+        &mut self,
+        _ref_time: u64,
+        _proof_size: u64,
+    ) -> Result<T::Balance> {
+        // The contracts pallet only prices scalar weights.
+        Err(EnvError::UnknownError)
+    }
+
     fn random<T>(&mut self, subject: &[u8]) -> Result<T::Hash>
     where
         T: EnvTypes,
@@ -34,6 +34,9 @@ pub enum EnvError {
     /// Transfer failed because it would have brought the sender's total balance
     /// bwlow the subsistence threshold.
     BelowSubsistenceThreshold,
+    /// Transfer failed because it would have left the destination with a
+    /// balance below the minimum balance so that it would have been reaped.
+    BelowMinimumBalance,
     /// Transfer failed for other not further specified reason. Most probably
     /// reserved or locked balance of the sender that was preventing the transfer.
     TransferFailed,
@@ -44,6 +47,16 @@ pub enum EnvError {
     CodeNotFound,
     /// The account that was called is either no contract (e.g. user account) or is a tombstone.
     NotCallable,
+    /// The contract execution ran out of gas.
+    OutOfGas,
+    /// The contract storage cannot accept any more data.
+    OutOfStorage,
+    /// The call would have exceeded the maximum depth of the call stack.
+    CallStackExhausted,
+    /// There is no account for the queried account ID.
+    AccountNotFound,
+    /// The queried account is no contract (e.g. user account).
+    NotAContract,
     /// An unknown error has occured.
     UnknownError,
 }
@@ -38,7 +38,10 @@ use self::backend::{
 };
 pub use self::{
     api::*,
-    backend::ReturnFlags,
+    backend::{
+        RentStatus,
+        ReturnFlags,
+    },
     error::{
         EnvError,
         Result,
@@ -49,6 +52,8 @@ pub use self::{
         DefaultEnvTypes,
         EnvTypes,
         Hash,
+        TopicFields,
+        TopicHashes,
         Topics,
     },
 };
@@ -64,3 +64,114 @@ fn test_hash_blake2_128() {
         [180, 158, 48, 21, 171, 163, 217, 175, 145, 160, 25, 159, 213, 142, 103, 242]
     );
 }
+
+#[test]
+fn test_hash_twox_64() {
+    let mut output = [0x00_u8; 8];
+    env::hash::twox_64(TEST_INPUT, &mut output);
+    assert_eq!(output, [184, 90, 166, 82, 206, 121, 53, 220]);
+}
+
+#[test]
+fn test_hash_twox_128() {
+    let mut output = [0x00_u8; 16];
+    env::hash::twox_128(TEST_INPUT, &mut output);
+    assert_eq!(
+        output,
+        [184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59]
+    );
+    // Well known storage prefixes of Substrate's `System::Account`.
+    env::hash::twox_128(b"System", &mut output);
+    assert_eq!(
+        output,
+        [
+            0x26, 0xaa, 0x39, 0x4e, 0xea, 0x56, 0x30, 0xe0, 0x7c, 0x48, 0xae, 0x0c,
+            0x95, 0x58, 0xce, 0xf7
+        ]
+    );
+    env::hash::twox_128(b"Account", &mut output);
+    assert_eq!(
+        output,
+        [
+            0xb9, 0x9d, 0x88, 0x0e, 0xc6, 0x81, 0x79, 0x9c, 0x0c, 0xf3, 0x0e, 0x88,
+            0x86, 0x37, 0x1d, 0xa9
+        ]
+    );
+}
+
+#[test]
+fn test_hash_twox_256() {
+    let mut output = [0x00_u8; 32];
+    env::hash::twox_256(TEST_INPUT, &mut output);
+    assert_eq!(
+        output,
+        [
+            184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59,
+            173, 79, 253, 143, 224, 57, 69, 25, 254, 88, 31, 187, 27, 139, 238, 91
+        ]
+    );
+}
+
+#[test]
+fn test_hash_bytes() {
+    use env::hash::{
+        hash_bytes,
+        Blake2x128,
+        Blake2x256,
+        CryptoHash,
+        HashOutput,
+        Keccak256,
+        Sha2x256,
+        Twox128,
+        Twox256,
+        Twox64,
+    };
+    fn hash_of<H>(input: &[u8]) -> <H as HashOutput>::Type
+    where
+        H: CryptoHash,
+    {
+        let mut output = <H as HashOutput>::Type::default();
+        hash_bytes::<H>(input, &mut output);
+        output
+    }
+    assert_eq!(
+        hash_of::<Keccak256>(TEST_INPUT),
+        [
+            24, 230, 209, 59, 127, 30, 158, 244, 60, 177, 132, 150, 167, 244, 64, 69,
+            184, 123, 185, 44, 211, 199, 208, 179, 14, 64, 126, 140, 217, 69, 36, 216
+        ]
+    );
+    assert_eq!(
+        hash_of::<Sha2x256>(TEST_INPUT),
+        [
+            136, 15, 25, 218, 88, 54, 49, 152, 115, 168, 147, 189, 207, 171, 243, 129,
+            161, 76, 15, 141, 197, 106, 111, 213, 19, 197, 133, 219, 181, 233, 195, 120
+        ]
+    );
+    assert_eq!(
+        hash_of::<Blake2x256>(TEST_INPUT),
+        [
+            244, 247, 235, 182, 194, 161, 28, 69, 34, 106, 237, 7, 57, 87, 190, 12, 92,
+            171, 91, 176, 135, 52, 247, 94, 8, 112, 94, 183, 140, 101, 208, 120
+        ]
+    );
+    assert_eq!(
+        hash_of::<Blake2x128>(TEST_INPUT),
+        [180, 158, 48, 21, 171, 163, 217, 175, 145, 160, 25, 159, 213, 142, 103, 242]
+    );
+    assert_eq!(
+        hash_of::<Twox64>(TEST_INPUT),
+        [184, 90, 166, 82, 206, 121, 53, 220]
+    );
+    assert_eq!(
+        hash_of::<Twox128>(TEST_INPUT),
+        [184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59]
+    );
+    assert_eq!(
+        hash_of::<Twox256>(TEST_INPUT),
+        [
+            184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59,
+            173, 79, 253, 143, 224, 57, 69, 25, 254, 88, 31, 187, 27, 139, 238, 91
+        ]
+    );
+}
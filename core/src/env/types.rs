@@ -37,6 +37,7 @@ use core::{
     convert::TryFrom,
 };
 use derive_more::From;
+use ink_prelude::vec::Vec;
 use scale::{
     Decode,
     Encode,
@@ -94,6 +95,19 @@ pub trait EnvTypes {
         + AtLeast32BitUnsigned;
 }
 
+/// Implemented by event types to communicate which fields their topics
+/// originate from.
+pub trait TopicFields {
+    /// The names of the topic fields in the order of their topic hashes.
+    ///
+    /// The topic hash at position `n` returned by [`Topics::topics`] is
+    /// expected to be produced by the field named `TOPIC_FIELDS[n]`.
+    const TOPIC_FIELDS: &'static [&'static str];
+}
+
+/// The topic hashes of an event.
+pub type TopicHashes<T> = Vec<<T as EnvTypes>::Hash>;
+
 /// Implemented by event types to communicate their topic hashes.
 pub trait Topics<T>
 where
@@ -101,8 +115,8 @@ where
 {
     /// Returns the topic hashes of `self`.
     ///
-    /// The length of the slice must be less than or equal to `<T as EvnTypes>::MAX_EVENT_TOPICS`.
-    fn topics(&self) -> &'static [<T as EnvTypes>::Hash];
+    /// The number of topics must be less than or equal to `<T as EvnTypes>::MAX_EVENT_TOPICS`.
+    fn topics(&self) -> TopicHashes<T>;
 }
 
 /// The fundamental types of the default configuration.
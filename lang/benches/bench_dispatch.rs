@@ -0,0 +1,215 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares the linear selector match against the binary search over the
+//! sorted selector table in the `DecodeDispatch` of a generated contract.
+//!
+//! The contract sits right at the `DISPATCH_TABLE_THRESHOLD` of the ink!
+//! codegen: its 16 ink! constructors are matched linearly while its 17 ink!
+//! messages are looked up via binary search. None of them takes any inputs
+//! so that decoding differs only in the selector lookup.
+
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+};
+use dispatch_threshold::DispatchThreshold;
+use ink_lang::{
+    self as ink,
+    ConstructorDispatcher,
+    DecodeDispatch,
+    MessageDispatcher,
+};
+
+criterion_group!(dispatch, bench_dispatch);
+criterion_main!(dispatch);
+
+#[ink::contract]
+mod dispatch_threshold {
+    #[ink(storage)]
+    pub struct DispatchThreshold {}
+
+    impl DispatchThreshold {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new_1() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000002")]
+        pub fn new_2() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000003")]
+        pub fn new_3() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000004")]
+        pub fn new_4() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000005")]
+        pub fn new_5() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000006")]
+        pub fn new_6() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000007")]
+        pub fn new_7() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000008")]
+        pub fn new_8() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000009")]
+        pub fn new_9() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000A")]
+        pub fn new_10() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000B")]
+        pub fn new_11() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000C")]
+        pub fn new_12() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000D")]
+        pub fn new_13() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000E")]
+        pub fn new_14() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x0000000F")]
+        pub fn new_15() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor, selector = "0x00000010")]
+        pub fn new_16() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn message_1(&self) {}
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn message_2(&self) {}
+
+        #[ink(message, selector = "0x00000003")]
+        pub fn message_3(&self) {}
+
+        #[ink(message, selector = "0x00000004")]
+        pub fn message_4(&self) {}
+
+        #[ink(message, selector = "0x00000005")]
+        pub fn message_5(&self) {}
+
+        #[ink(message, selector = "0x00000006")]
+        pub fn message_6(&self) {}
+
+        #[ink(message, selector = "0x00000007")]
+        pub fn message_7(&self) {}
+
+        #[ink(message, selector = "0x00000008")]
+        pub fn message_8(&self) {}
+
+        #[ink(message, selector = "0x00000009")]
+        pub fn message_9(&self) {}
+
+        #[ink(message, selector = "0x0000000A")]
+        pub fn message_10(&self) {}
+
+        #[ink(message, selector = "0x0000000B")]
+        pub fn message_11(&self) {}
+
+        #[ink(message, selector = "0x0000000C")]
+        pub fn message_12(&self) {}
+
+        #[ink(message, selector = "0x0000000D")]
+        pub fn message_13(&self) {}
+
+        #[ink(message, selector = "0x0000000E")]
+        pub fn message_14(&self) {}
+
+        #[ink(message, selector = "0x0000000F")]
+        pub fn message_15(&self) {}
+
+        #[ink(message, selector = "0x00000010")]
+        pub fn message_16(&self) {}
+
+        #[ink(message, selector = "0x00000011")]
+        pub fn message_17(&self) {}
+    }
+}
+
+/// The constructor dispatch enum that matches its selectors linearly.
+type Constructors = <DispatchThreshold as ConstructorDispatcher>::Type;
+
+/// The message dispatch enum that looks its selectors up via binary search.
+type Messages = <DispatchThreshold as MessageDispatcher>::Type;
+
+/// Decodes the constructor dispatch enum.
+fn linear(selector: [u8; 4]) -> bool {
+    <Constructors as DecodeDispatch>::decode_dispatch(&mut &selector[..]).is_ok()
+}
+
+/// Decodes the message dispatch enum.
+fn binary_search(selector: [u8; 4]) -> bool {
+    <Messages as DecodeDispatch>::decode_dispatch(&mut &selector[..]).is_ok()
+}
+
+fn bench_dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Compare: linear and binary search dispatch");
+    // The first and last selectors in sorted order as well as an unknown one.
+    let selectors = [
+        ("first", [0x00, 0x00, 0x00, 0x01], [0x00, 0x00, 0x00, 0x01]),
+        ("last", [0x00, 0x00, 0x00, 0x10], [0x00, 0x00, 0x00, 0x11]),
+        ("unknown", [0xFF, 0xFF, 0xFF, 0xFF], [0xFF, 0xFF, 0xFF, 0xFF]),
+    ];
+    for (name, constructor, message) in selectors.iter() {
+        group.bench_with_input(BenchmarkId::new("linear", name), constructor, |b, s| {
+            b.iter(|| linear(black_box(*s)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("binary_search", name),
+            message,
+            |b, s| b.iter(|| binary_search(black_box(*s))),
+        );
+    }
+    group.finish();
+}
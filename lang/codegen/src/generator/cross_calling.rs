@@ -155,6 +155,34 @@ impl CrossCalling<'_> {
         )
     }
 
+    /// Returns the selector of the call to an ink! callable together with the
+    /// bindings and types of the arguments pushed onto its execution input.
+    ///
+    /// # Note
+    ///
+    /// The ink! message with wildcard selector and the fallback ink! constructor
+    /// receive the selector of the call as their first argument. It is used as
+    /// the selector of the call instead of being encoded a second time.
+    fn generate_call_input<'a, 'b>(
+        composed_selector: ir::Selector,
+        takes_selector: bool,
+        input_bindings: &'a [Ident],
+        input_types: &'a [&'b syn::Type],
+    ) -> (TokenStream2, &'a [Ident], &'a [&'b syn::Type]) {
+        if takes_selector {
+            let selector_binding = &input_bindings[0];
+            let selector = quote! {
+                ::ink_core::env::call::Selector::new(#selector_binding)
+            };
+            return (selector, &input_bindings[1..], &input_types[1..])
+        }
+        let selector_bytes = composed_selector.as_bytes().to_owned();
+        let selector = quote! {
+            ::ink_core::env::call::Selector::new([ #( #selector_bytes ),* ])
+        };
+        (selector, input_bindings, input_types)
+    }
+
     /// Returns the identifier for the generated call forwarder utility.
     fn call_forwarder_ident() -> Ident {
         format_ident!("__ink_CallForwarder")
@@ -220,7 +248,6 @@ impl CrossCalling<'_> {
         let span = message.span();
         let ident = message.ident();
         let output_ident = format_ident!("{}Out", ident.to_string().to_camel_case());
-        let composed_selector = message.composed_selector().as_bytes().to_owned();
         let attrs = message.attrs();
         let input_bindings = message
             .inputs()
@@ -231,7 +258,13 @@ impl CrossCalling<'_> {
             .inputs()
             .map(|pat_type| &*pat_type.ty)
             .collect::<Vec<_>>();
-        let arg_list = Self::generate_arg_list(input_types.iter().cloned());
+        let (selector, arg_bindings, arg_types) = Self::generate_call_input(
+            message.composed_selector(),
+            message.is_wildcard(),
+            &input_bindings,
+            &input_types,
+        );
+        let arg_list = Self::generate_arg_list(arg_types.iter().cloned());
         let output = message.output();
         let output_sig = output.map_or_else(
             || quote! { () },
@@ -263,11 +296,9 @@ impl CrossCalling<'_> {
                 ::ink_core::env::call::build_call::<EnvTypes>()
                     .callee(::ink_lang::ToAccountId::to_account_id(self.contract))
                     .exec_input(
-                        ::ink_core::env::call::ExecutionInput::new(
-                            ::ink_core::env::call::Selector::new([ #( #composed_selector ),* ])
-                        )
+                        ::ink_core::env::call::ExecutionInput::new(#selector)
                         #(
-                            .push_arg(#input_bindings)
+                            .push_arg(#arg_bindings)
                         )*
                     )
                     .returns::<#output_sig>()
@@ -399,7 +430,6 @@ impl CrossCalling<'_> {
     ) -> TokenStream2 {
         let span = message.span();
         let ident = message.ident();
-        let composed_selector = message.composed_selector().as_bytes().to_owned();
         let attrs = message.attrs();
         let input_bindings = message
             .inputs()
@@ -410,8 +440,14 @@ impl CrossCalling<'_> {
             .inputs()
             .map(|pat_type| &*pat_type.ty)
             .collect::<Vec<_>>();
-        let arg_list = Self::generate_arg_list(input_types.iter().cloned());
-        let output = message.output();
+        let (selector, arg_bindings, arg_types) = Self::generate_call_input(
+            message.composed_selector(),
+            message.is_wildcard(),
+            &input_bindings,
+            &input_types,
+        );
+        let arg_list = Self::generate_arg_list(arg_types.iter().cloned());
+        let output = message.encoded_output();
         let output_sig = output.map_or_else(
             || quote! { () },
             |output| quote! { ::ink_core::env::call::utils::ReturnType<#output> },
@@ -437,11 +473,9 @@ impl CrossCalling<'_> {
                 ::ink_core::env::call::build_call::<EnvTypes>()
                     .callee(::ink_lang::ToAccountId::to_account_id(self.contract))
                     .exec_input(
-                        ::ink_core::env::call::ExecutionInput::new(
-                            ::ink_core::env::call::Selector::new([ #( #composed_selector ),* ])
-                        )
+                        ::ink_core::env::call::ExecutionInput::new(#selector)
                         #(
-                            .push_arg(#input_bindings)
+                            .push_arg(#arg_bindings)
                         )*
                     )
                     .returns::<#output_sig>()
@@ -601,7 +635,6 @@ impl CrossCalling<'_> {
         let attrs = constructor.attrs();
         let ident = constructor.ident();
         let output_ident = format_ident!("{}Out", ident.to_string().to_camel_case());
-        let composed_selector = constructor.composed_selector().as_bytes().to_owned();
         let input_bindings = constructor
             .inputs()
             .enumerate()
@@ -611,7 +644,13 @@ impl CrossCalling<'_> {
             .inputs()
             .map(|pat_type| &*pat_type.ty)
             .collect::<Vec<_>>();
-        let arg_list = Self::generate_arg_list(input_types.iter().cloned());
+        let (selector, arg_bindings, arg_types) = Self::generate_call_input(
+            constructor.composed_selector(),
+            constructor.is_fallback(),
+            &input_bindings,
+            &input_types,
+        );
+        let arg_list = Self::generate_arg_list(arg_types.iter().cloned());
         quote_spanned!(span =>
             type #output_ident = ::ink_core::env::call::CreateBuilder<
                 EnvTypes,
@@ -629,11 +668,9 @@ impl CrossCalling<'_> {
             ) -> Self::#output_ident {
                 ::ink_core::env::call::build_create::<EnvTypes, Self>()
                     .exec_input(
-                        ::ink_core::env::call::ExecutionInput::new(
-                            ::ink_core::env::call::Selector::new([ #( #composed_selector ),* ])
-                        )
+                        ::ink_core::env::call::ExecutionInput::new(#selector)
                         #(
-                            .push_arg(#input_bindings)
+                            .push_arg(#arg_bindings)
                         )*
                     )
             }
@@ -699,7 +736,6 @@ impl CrossCalling<'_> {
         let span = constructor.span();
         let attrs = constructor.attrs();
         let ident = constructor.ident();
-        let composed_selector = constructor.composed_selector().as_bytes().to_owned();
         let input_bindings = constructor
             .inputs()
             .enumerate()
@@ -709,7 +745,13 @@ impl CrossCalling<'_> {
             .inputs()
             .map(|pat_type| &*pat_type.ty)
             .collect::<Vec<_>>();
-        let arg_list = Self::generate_arg_list(input_types.iter().cloned());
+        let (selector, arg_bindings, arg_types) = Self::generate_call_input(
+            constructor.composed_selector(),
+            constructor.is_fallback(),
+            &input_bindings,
+            &input_types,
+        );
+        let arg_list = Self::generate_arg_list(arg_types.iter().cloned());
         quote_spanned!(span =>
             #( #attrs )*
             #[inline]
@@ -725,11 +767,9 @@ impl CrossCalling<'_> {
             > {
                 ::ink_core::env::call::build_create::<EnvTypes, Self>()
                     .exec_input(
-                        ::ink_core::env::call::ExecutionInput::new(
-                            ::ink_core::env::call::Selector::new([ #( #composed_selector ),* ])
-                        )
+                        ::ink_core::env::call::ExecutionInput::new(#selector)
                         #(
-                            .push_arg(#input_bindings)
+                            .push_arg(#arg_bindings)
                         )*
                     )
             }
@@ -751,7 +791,7 @@ impl CrossCalling<'_> {
         );
         let inputs_sig = message.inputs();
         let inputs_params = message.inputs().map(|pat_type| &pat_type.pat);
-        let output_sig = message.output().map(|output| quote! { -> #output });
+        let output_sig = message.encoded_output().map(|output| quote! { -> #output });
         let receiver = message.receiver();
         let forward_ident = match receiver {
             ir::Receiver::Ref => format_ident!("call"),
@@ -18,6 +18,7 @@ use crate::{
     GenerateCodeUsing as _,
 };
 use derive_more::From;
+use heck::CamelCase as _;
 use ir::Callable as _;
 use proc_macro2::{
     Ident,
@@ -30,6 +31,13 @@ use quote::{
 };
 use syn::spanned::Spanned as _;
 
+/// The number of selectors of a dispatch enum above which the selector is looked
+/// up via binary search instead of being matched linearly.
+///
+/// The `bench_dispatch` benchmark of `ink_lang` compares both lookups right at
+/// this threshold in the `DecodeDispatch` of a generated contract.
+const DISPATCH_TABLE_THRESHOLD: usize = 16;
+
 /// Generates code for the message and constructor dispatcher.
 ///
 /// This code efficiently selects the dispatched ink! constructor or message
@@ -64,7 +72,19 @@ impl GenerateCode for Dispatch<'_> {
         let dispatch_trait_impls = self.generate_dispatch_trait_impls();
         let message_dispatch_enum = self.generate_message_dispatch_enum();
         let constructor_dispatch_enum = self.generate_constructor_dispatch_enum();
+        let unified_dispatcher = self.generate_unified_dispatcher();
+        let message_selectors = self.generate_message_selectors();
+        let selector_constants = self.generate_selector_constants();
+        let selector_names = self.generate_selector_names();
+        let message_names = self.generate_message_names();
+        let message_infos = self.generate_message_infos();
         quote! {
+            #message_selectors
+            #selector_constants
+            #message_names
+            #selector_names
+            #message_infos
+
             // We do not generate contract dispatch code while the contract
             // is being tested or the contract is a dependency of another
             // since both resulting compilations do not require dispatching.
@@ -77,6 +97,7 @@ impl GenerateCode for Dispatch<'_> {
                 #dispatch_trait_impls
                 #message_dispatch_enum
                 #constructor_dispatch_enum
+                #unified_dispatcher
             };
         }
     }
@@ -133,14 +154,12 @@ impl Dispatch<'_> {
                     match mode {
                         ::ink_lang::DispatchMode::Instantiate => {
                             <<#storage_ident as ::ink_lang::ConstructorDispatcher>::Type as ::ink_lang::Execute>::execute(
-                                ::ink_core::env::decode_input::<<#storage_ident as ::ink_lang::ConstructorDispatcher>::Type>()
-                                    .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                                ::ink_lang::decode_dispatch_input::<<#storage_ident as ::ink_lang::ConstructorDispatcher>::Type>()?
                             )
                         }
                         ::ink_lang::DispatchMode::Call => {
                             <<#storage_ident as ::ink_lang::MessageDispatcher>::Type as ::ink_lang::Execute>::execute(
-                                ::ink_core::env::decode_input::<<#storage_ident as ::ink_lang::MessageDispatcher>::Type>()
-                                    .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                                ::ink_lang::decode_dispatch_input::<<#storage_ident as ::ink_lang::MessageDispatcher>::Type>()?
                             )
                         }
                     }
@@ -149,6 +168,20 @@ impl Dispatch<'_> {
         }
     }
 
+    /// Generates the `UnifiedDispatcher` trait implementation that combines the
+    /// constructor and message dispatch enums into a single dispatch type.
+    fn generate_unified_dispatcher(&self) -> TokenStream2 {
+        let storage_ident = self.contract.module().storage().ident();
+        quote! {
+            impl ::ink_lang::UnifiedDispatcher for #storage_ident {
+                type Type = ::ink_lang::UnifiedDispatch<
+                    <#storage_ident as ::ink_lang::ConstructorDispatcher>::Type,
+                    <#storage_ident as ::ink_lang::MessageDispatcher>::Type,
+                >;
+            }
+        }
+    }
+
     /// Returns the generated ink! namespace identifier for the given callable kind.
     fn dispatch_trait_impl_namespace(kind: ir::CallableKind) -> Ident {
         match kind {
@@ -212,7 +245,12 @@ impl Dispatch<'_> {
             .collect::<Vec<_>>();
         let storage_ident = self.contract.module().storage().ident();
         let namespace = Self::dispatch_trait_impl_namespace(cws.kind());
-        let input_types_tuple = if input_types.len() != 1 {
+        let input_struct = self.generate_input_struct(cws);
+        let input_struct_ident = self.input_struct_ident(cws);
+        let input_types_tuple = if let Some(input_struct_ident) = input_struct_ident {
+            // Use the generated named input struct if enabled.
+            quote! { #input_struct_ident }
+        } else if input_types.len() != 1 {
             // Pack all types into a tuple if they are not exactly 1.
             // This results in `()` for zero input types.
             quote! { ( #( #input_types ),* ) }
@@ -221,6 +259,8 @@ impl Dispatch<'_> {
             quote! { #( #input_types )* }
         };
         let fn_input_impl = quote_spanned!(callable.inputs_span() =>
+            #input_struct
+
             impl ::ink_lang::FnInput for #namespace<[(); #selector_id]> {
                 type Input = #input_types_tuple;
             }
@@ -244,6 +284,108 @@ impl Dispatch<'_> {
         }
     }
 
+    /// Returns the identifier of the generated named input struct of the callable
+    /// if the callable packs its inputs into a named struct.
+    ///
+    /// # Note
+    ///
+    /// Only callables with at least two inputs use a named input struct and
+    /// only if the `named_inputs` ink! config is enabled for ink! messages or
+    /// the `named_constructor_inputs` ink! config for ink! constructors.
+    /// The identifier is derived from the callable identifier, e.g.
+    /// `TransferFromInput` for the `transfer_from` message, and is prefixed
+    /// with the trait identifier for callables of trait implementations.
+    fn input_struct_ident<C>(
+        &self,
+        cws: ir::CallableWithSelector<'_, C>,
+    ) -> Option<Ident>
+    where
+        C: ir::Callable,
+    {
+        let config = self.contract.config();
+        let is_enabled = match cws.kind() {
+            ir::CallableKind::Message => config.is_named_inputs_enabled(),
+            ir::CallableKind::Constructor => {
+                config.is_named_constructor_inputs_enabled()
+            }
+        };
+        if !is_enabled || cws.callable().inputs().count() < 2 {
+            return None
+        }
+        let callable_ident = cws.callable().ident().to_string().to_camel_case();
+        let ident = match cws.item_impl().trait_ident() {
+            Some(trait_ident) => {
+                format_ident!("{}{}Input", trait_ident, callable_ident)
+            }
+            None => format_ident!("{}Input", callable_ident),
+        };
+        Some(ident)
+    }
+
+    /// Returns the field identifiers of the generated named input struct.
+    ///
+    /// Inputs bound to a plain identifier yield a field of the same name,
+    /// all other inputs yield a field named `arg_N` after their position.
+    fn input_struct_fields<C>(callable: &C) -> Vec<Ident>
+    where
+        C: ir::Callable,
+    {
+        callable
+            .inputs()
+            .enumerate()
+            .map(|(n, pat_type)| {
+                match &*pat_type.pat {
+                    syn::Pat::Ident(pat_ident) => pat_ident.ident.clone(),
+                    _ => format_ident!("arg_{}", n),
+                }
+            })
+            .collect()
+    }
+
+    /// Generates the named input struct of the callable and its `scale::Decode`
+    /// and `scale::Encode` implementations if the callable packs its inputs into
+    /// a named struct.
+    fn generate_input_struct<C>(
+        &self,
+        cws: ir::CallableWithSelector<'_, C>,
+    ) -> Option<TokenStream2>
+    where
+        C: ir::Callable,
+    {
+        let ident = self.input_struct_ident(cws)?;
+        let callable = cws.callable();
+        let doc = format!(
+            "The inputs of the `{}` ink! {}.",
+            callable.ident(),
+            cws.kind()
+        );
+        let fields = Self::input_struct_fields(callable);
+        let input_types = callable
+            .inputs()
+            .map(|pat_type| &pat_type.ty)
+            .collect::<Vec<_>>();
+        Some(quote! {
+            #[doc = #doc]
+            pub struct #ident {
+                #( pub #fields: #input_types ),*
+            }
+
+            impl ::scale::Decode for #ident {
+                fn decode<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::scale::Error> {
+                    Ok(Self {
+                        #( #fields: <#input_types as ::scale::Decode>::decode(input)? ),*
+                    })
+                }
+            }
+
+            impl ::scale::Encode for #ident {
+                fn encode_to<T: ::scale::Output>(&self, dest: &mut T) {
+                    #( <#input_types as ::scale::Encode>::encode_to(&self.#fields, dest); )*
+                }
+            }
+        })
+    }
+
     /// Returns a tuple of:
     ///
     /// - Vector over the generated identifier bindings (`__ink_binding_N`) for all inputs.
@@ -307,7 +449,7 @@ impl Dispatch<'_> {
         let selector = cws.composed_selector();
         let selector_id = selector.unique_id();
         let output_tokens = message
-            .output()
+            .encoded_output()
             .map(quote::ToTokens::to_token_stream)
             .unwrap_or_else(|| quote! { () });
         let is_mut = message.receiver().is_ref_mut();
@@ -326,11 +468,25 @@ impl Dispatch<'_> {
                 Some(syn::token::Mut::default()),
                 format_ident!("MessageMut"),
             )
+        } else if message.returns_ref() {
+            (None, format_ident!("LazyMessage"))
         } else {
             (None, format_ident!("MessageRef"))
         };
-        let (input_bindings, inputs_as_tuple_or_wildcard) =
+        // Lazy messages return a reference to their output borrowed from the state.
+        let output_ref = if message.returns_ref() {
+            Some(quote! { & })
+        } else {
+            None
+        };
+        let (input_bindings, mut inputs_as_tuple_or_wildcard) =
             Self::generate_input_bindings(message);
+        if let Some(input_struct_ident) = self.input_struct_ident(cws) {
+            let fields = Self::input_struct_fields(message);
+            inputs_as_tuple_or_wildcard = quote! {
+                #input_struct_ident { #( #fields: #input_bindings ),* }
+            };
+        }
         let as_trait = cws.item_impl().trait_path().map(|trait_path| {
             quote_spanned!(message_span =>
                 as #trait_path
@@ -341,7 +497,7 @@ impl Dispatch<'_> {
                 const CALLABLE: fn(
                     &#mut_token <Self as ::ink_lang::FnState>::State,
                     <Self as ::ink_lang::FnInput>::Input
-                ) -> <Self as ::ink_lang::FnOutput>::Output = |state, #inputs_as_tuple_or_wildcard| {
+                ) -> #output_ref <Self as ::ink_lang::FnOutput>::Output = |state, #inputs_as_tuple_or_wildcard| {
                     <#storage_ident #as_trait>::#message_ident(state, #( #input_bindings ),* )
                 };
             }
@@ -367,22 +523,48 @@ impl Dispatch<'_> {
         let namespace =
             Self::dispatch_trait_impl_namespace(ir::CallableKind::Constructor);
         let callable_impl = self.generate_trait_impls_for_callable(cws);
-        let (input_bindings, inputs_as_tuple_or_wildcard) =
+        let (input_bindings, mut inputs_as_tuple_or_wildcard) =
             Self::generate_input_bindings(constructor);
+        if let Some(input_struct_ident) = self.input_struct_ident(cws) {
+            let fields = Self::input_struct_fields(constructor);
+            inputs_as_tuple_or_wildcard = quote! {
+                #input_struct_ident { #( #fields: #input_bindings ),* }
+            };
+        }
         let as_trait = cws.item_impl().trait_path().map(|trait_path| {
             quote_spanned!(constructor_span =>
                 as #trait_path
             )
         });
-        let constructor_impl = quote_spanned!(constructor_span =>
-            impl ::ink_lang::Constructor for #namespace<[(); #selector_id]> {
-                const CALLABLE: fn(
-                    <Self as ::ink_lang::FnInput>::Input
-                ) -> <Self as ::ink_lang::FnState>::State = |#inputs_as_tuple_or_wildcard| {
-                    <#storage_ident #as_trait>::#constructor_ident(#( #input_bindings ),* )
-                };
+        let constructor_impl = match constructor.error_type() {
+            Some(error_type) => {
+                quote_spanned!(constructor_span =>
+                    impl ::ink_lang::FallibleConstructor for #namespace<[(); #selector_id]> {
+                        type Error = #error_type;
+
+                        const CALLABLE: fn(
+                            <Self as ::ink_lang::FnInput>::Input
+                        ) -> ::core::result::Result<
+                            <Self as ::ink_lang::FnState>::State,
+                            <Self as ::ink_lang::FallibleConstructor>::Error,
+                        > = |#inputs_as_tuple_or_wildcard| {
+                            <#storage_ident #as_trait>::#constructor_ident(#( #input_bindings ),* )
+                        };
+                    }
+                )
             }
-        );
+            None => {
+                quote_spanned!(constructor_span =>
+                    impl ::ink_lang::Constructor for #namespace<[(); #selector_id]> {
+                        const CALLABLE: fn(
+                            <Self as ::ink_lang::FnInput>::Input
+                        ) -> <Self as ::ink_lang::FnState>::State = |#inputs_as_tuple_or_wildcard| {
+                            <#storage_ident #as_trait>::#constructor_ident(#( #input_bindings ),* )
+                        };
+                    }
+                )
+            }
+        };
         quote_spanned!(constructor_span =>
             #callable_impl
             #constructor_impl
@@ -415,6 +597,9 @@ impl Dispatch<'_> {
     ///
     /// This way it is clear that this is an ink! generated identifier and even
     /// encodes the unique selector bytes to make the identifier unique.
+    ///
+    /// Selector aliases of a callable share the variant identified by its
+    /// composed selector.
     fn generate_dispatch_variant_ident<C>(
         &self,
         cws: ir::CallableWithSelector<'_, C>,
@@ -437,12 +622,96 @@ impl Dispatch<'_> {
         )
     }
 
-    /// Generates one match arm of the dispatch `scale::Decode` implementation.
+    /// Returns the dispatch error variants for an unknown selector and for
+    /// invalid parameters respectively of the given callable kind.
+    fn dispatch_decode_errors(kind: ir::CallableKind) -> (Ident, Ident) {
+        match kind {
+            ir::CallableKind::Message => {
+                (
+                    format_ident!("UnknownCallSelector"),
+                    format_ident!("InvalidCallParameters"),
+                )
+            }
+            ir::CallableKind::Constructor => {
+                (
+                    format_ident!("UnknownInstantiateSelector"),
+                    format_ident!("InvalidInstantiateParameters"),
+                )
+            }
+        }
+    }
+
+    /// Returns the identifier of the dispatch error variant reporting the
+    /// position of an invalid argument for the given callable kind.
+    fn dispatch_invalid_argument_error(kind: ir::CallableKind) -> Ident {
+        match kind {
+            ir::CallableKind::Message => format_ident!("InvalidCallArgument"),
+            ir::CallableKind::Constructor => format_ident!("InvalidInstantiateArgument"),
+        }
+    }
+
+    /// Returns the identifier of the dispatch error variant reporting an input
+    /// too short to hold all arguments for the given callable kind.
+    fn dispatch_insufficient_input_error(kind: ir::CallableKind) -> Ident {
+        match kind {
+            ir::CallableKind::Message => format_ident!("InsufficientCallInput"),
+            ir::CallableKind::Constructor => {
+                format_ident!("InsufficientInstantiateInput")
+            }
+        }
+    }
+
+    /// Returns the SCALE encoded size of the given type if it is known to be
+    /// fixed at compile time.
+    ///
+    /// # Note
+    ///
+    /// Only primitive integers, `bool` as well as arrays and tuples of those
+    /// are recognized. Everything else, including type aliases, is treated as
+    /// variable in size.
+    fn fixed_encoded_size(ty: &syn::Type) -> Option<usize> {
+        match ty {
+            syn::Type::Path(type_path) if type_path.qself.is_none() => {
+                let ident = type_path.path.get_ident()?.to_string();
+                match ident.as_str() {
+                    "bool" | "u8" | "i8" => Some(1),
+                    "u16" | "i16" => Some(2),
+                    "u32" | "i32" => Some(4),
+                    "u64" | "i64" => Some(8),
+                    "u128" | "i128" => Some(16),
+                    _ => None,
+                }
+            }
+            syn::Type::Array(type_array) => {
+                let len = match &type_array.len {
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(len),
+                        ..
+                    }) => len.base10_parse::<usize>().ok()?,
+                    _ => return None,
+                };
+                Self::fixed_encoded_size(&type_array.elem)?.checked_mul(len)
+            }
+            syn::Type::Tuple(type_tuple) => {
+                type_tuple.elems.iter().map(Self::fixed_encoded_size).sum()
+            }
+            syn::Type::Paren(type_paren) => Self::fixed_encoded_size(&type_paren.elem),
+            syn::Type::Group(type_group) => Self::fixed_encoded_size(&type_group.elem),
+            _ => None,
+        }
+    }
+
+    /// Generates one match arm of the dispatch `DecodeDispatch` implementation.
     ///
     /// # Note
     ///
     /// There is one match arm per ink! constructor or message for the dispatch
-    /// `scale::Decode` implementation.
+    /// `DecodeDispatch` implementation plus one additional match arm per selector
+    /// alias and per deprecated selector of a versioned ink! message that all
+    /// construct the same dispatch variant.
+    ///
+    /// Malformed arguments yield the invalid parameters dispatch error of the
+    /// respective callable kind.
     fn generate_dispatch_variant_decode<C>(
         &self,
         cws: ir::CallableWithSelector<'_, C>,
@@ -450,18 +719,239 @@ impl Dispatch<'_> {
     where
         C: ir::Callable,
     {
-        let selector_bytes = cws.composed_selector().as_bytes().to_owned();
-        let variant_ident = self.generate_dispatch_variant_ident(cws);
-        let variant_types = cws.callable().inputs().map(|arg| &arg.ty);
+        let arms = self
+            .generate_dispatch_variant_decode_bodies(cws)
+            .into_iter()
+            .map(|(selector, body)| {
+                let selector_bytes = selector.as_bytes().to_owned();
+                quote! {
+                    [ #( #selector_bytes ),* ] => {
+                        #body
+                    }
+                }
+            });
+        quote! {
+            #( #arms )*
+        }
+    }
+
+    /// Generates the decoding bodies of all selectors of the given callable.
+    ///
+    /// # Note
+    ///
+    /// These are the composed selector, the selector aliases and the deprecated
+    /// selectors of the callable that all construct the same dispatch variant.
+    fn generate_dispatch_variant_decode_bodies<C>(
+        &self,
+        cws: ir::CallableWithSelector<'_, C>,
+    ) -> Vec<(ir::Selector, TokenStream2)>
+    where
+        C: ir::Callable,
+    {
+        let selectors = core::iter::once(cws.composed_selector())
+            .chain(cws.selector_aliases().iter().copied())
+            .map(|selector| {
+                (selector, self.generate_dispatch_variant_decode_body(cws, None))
+            });
+        // Deprecated selectors of earlier versions of the callable still
+        // dispatch to it but leave a debug message in debug builds.
+        let deprecated = cws.deprecated_selectors().into_iter().map(|selector| {
+            let body = self.generate_dispatch_variant_decode_body(cws, None);
+            let deprecation = format!(
+                "ink! {} `{}` has been called via its deprecated selector {:02X?}",
+                cws.kind(),
+                cws.ident(),
+                selector.as_bytes(),
+            );
+            let body = quote! {
+                #[cfg(debug_assertions)]
+                ::ink_core::env::debug_message(#deprecation);
+                #body
+            };
+            (selector, body)
+        });
+        selectors.chain(deprecated).collect()
+    }
+
+    /// Generates the body of the `DecodeDispatch` implementation of a dispatch
+    /// enum that reads the selector from the input and decodes the variant of
+    /// the selected ink! callable.
+    ///
+    /// # Note
+    ///
+    /// Dispatch enums with few selectors match the selector linearly. With more
+    /// than [`DISPATCH_TABLE_THRESHOLD`] selectors the selector is instead looked
+    /// up via binary search in the sorted table of all selectors so that the
+    /// dispatch cost grows only logarithmically with the number of selectors.
+    ///
+    /// Unknown selectors are dispatched to the `wildcard` ink! callable if any.
+    fn generate_decode_dispatch<C>(
+        &self,
+        kind: ir::CallableKind,
+        callables: &[ir::CallableWithSelector<'_, C>],
+        wildcard: Option<ir::CallableWithSelector<'_, C>>,
+    ) -> TokenStream2
+    where
+        C: ir::Callable,
+    {
+        let (unknown_selector, _) = Self::dispatch_decode_errors(kind);
+        let mut bodies = callables
+            .iter()
+            .map(|cws| self.generate_dispatch_variant_decode_bodies(*cws))
+            .flatten()
+            .collect::<Vec<_>>();
+        if bodies.len() <= DISPATCH_TABLE_THRESHOLD {
+            let decode = callables
+                .iter()
+                .map(|cws| self.generate_dispatch_variant_decode(*cws));
+            let decode_unknown = wildcard
+                .map(|cws| self.generate_dispatch_wildcard_decode(cws))
+                .unwrap_or_else(|| {
+                    quote! {
+                        _invalid => Err(::ink_lang::DispatchError::#unknown_selector)
+                    }
+                });
+            return quote! {
+                match <[u8; 4] as ::scale::Decode>::decode(input)
+                    .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                {
+                    #( #decode )*
+                    #decode_unknown
+                }
+            }
+        }
+        bodies.sort_by_key(|(selector, _)| *selector.as_bytes());
+        let selector = format_ident!("__ink_selector");
+        let len = bodies.len();
+        let selectors = bodies.iter().map(|(selector, _)| {
+            let selector_bytes = selector.as_bytes().to_owned();
+            quote! { [ #( #selector_bytes ),* ] }
+        });
+        let arms = bodies.iter().enumerate().map(|(index, (_, body))| {
+            quote! {
+                Ok(#index) => {
+                    #body
+                }
+            }
+        });
+        let decode_unknown = match wildcard {
+            Some(cws) => self.generate_dispatch_variant_decode_body(cws, Some(&selector)),
+            None => quote! { Err(::ink_lang::DispatchError::#unknown_selector) },
+        };
+        quote! {
+            let #selector = <[u8; 4] as ::scale::Decode>::decode(input)
+                .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?;
+            const __INK_SELECTORS: [[u8; 4]; #len] = [ #( #selectors ),* ];
+            match __INK_SELECTORS.binary_search(&#selector) {
+                #( #arms )*
+                _ => {
+                    #decode_unknown
+                }
+            }
+        }
+    }
+
+    /// Generates the catch-all match arm of the dispatch `DecodeDispatch`
+    /// implementation for the ink! message with a wildcard selector or the
+    /// fallback ink! constructor.
+    ///
+    /// # Note
+    ///
+    /// The matched selector is passed to the ink! callable as its first argument.
+    fn generate_dispatch_wildcard_decode<C>(
+        &self,
+        cws: ir::CallableWithSelector<'_, C>,
+    ) -> TokenStream2
+    where
+        C: ir::Callable,
+    {
+        let selector = format_ident!("__ink_selector");
+        let body = self.generate_dispatch_variant_decode_body(cws, Some(&selector));
         quote! {
-            [ #( #selector_bytes ),* ] => {
-                Ok(Self::#variant_ident(
-                    #(
-                        <#variant_types as ::scale::Decode>::decode(input)?
-                    ),*
-                ))
+            #selector => {
+                #body
+            }
+        }
+    }
+
+    /// Generates the body of a match arm of the dispatch `DecodeDispatch`
+    /// implementation decoding all inputs of the given callable.
+    ///
+    /// If `selector` is given it is used as the first input of the callable
+    /// instead of decoding it from the input.
+    fn generate_dispatch_variant_decode_body<C>(
+        &self,
+        cws: ir::CallableWithSelector<'_, C>,
+        selector: Option<&Ident>,
+    ) -> TokenStream2
+    where
+        C: ir::Callable,
+    {
+        let variant_ident = self.generate_dispatch_variant_ident(cws);
+        // With strict input decoding we reject inputs that have not been
+        // fully consumed by decoding all arguments of the callable.
+        let (_, invalid_parameters) = Self::dispatch_decode_errors(cws.kind());
+        let ensure_input_exhausted =
+            if self.contract.config().is_strict_input_decoding_enabled() {
+                Some(quote! {
+                    ::ink_lang::ensure_input_exhausted(input)
+                        .map_err(|_| ::ink_lang::DispatchError::#invalid_parameters)?;
+                })
+            } else {
+                None
+            };
+        let skip_inputs = if selector.is_some() { 1 } else { 0 };
+        let selector_arg = selector.map(|selector| quote! { #selector, });
+        // Callables without inputs, e.g. `new()` constructors, construct their
+        // variant right away without emitting any `scale::Decode` calls.
+        if cws.callable().inputs().count() == skip_inputs {
+            return quote! {
+                #ensure_input_exhausted
+                Ok(Self::#variant_ident(#selector_arg))
             }
         }
+        // Malformed arguments report their position among the inputs of the
+        // callable. Only the index is encoded to keep the byte cost minimal.
+        let invalid_argument = Self::dispatch_invalid_argument_error(cws.kind());
+        // Fail fast on inputs that cannot possibly hold all arguments with a
+        // fixed encoded size before decoding any of them.
+        let min_input_len = cws
+            .callable()
+            .inputs()
+            .skip(skip_inputs)
+            .filter_map(|arg| Self::fixed_encoded_size(&arg.ty))
+            .sum::<usize>();
+        let ensure_min_input_len = if min_input_len > 0 {
+            let insufficient_input = Self::dispatch_insufficient_input_error(cws.kind());
+            Some(quote! {
+                ::ink_lang::ensure_min_input_len(input, #min_input_len)
+                    .map_err(|_| ::ink_lang::DispatchError::#insufficient_input)?;
+            })
+        } else {
+            None
+        };
+        let decode_inputs = cws
+            .callable()
+            .inputs()
+            .enumerate()
+            .skip(skip_inputs)
+            .map(|(index, arg)| {
+                let ty = &arg.ty;
+                let index = index as u8;
+                quote! {
+                    <#ty as ::scale::Decode>::decode(input)
+                        .map_err(|_| ::ink_lang::DispatchError::#invalid_argument(#index))?
+                }
+            });
+        quote! {
+            #ensure_min_input_len
+            let variant = Self::#variant_ident(
+                #selector_arg
+                #( #decode_inputs ),*
+            );
+            #ensure_input_exhausted
+            Ok(variant)
+        }
     }
 
     /// Generates one match arm of the dispatch variant enum.
@@ -511,7 +1001,11 @@ impl Dispatch<'_> {
         let ident = self.generate_dispatch_variant_ident(cws);
         let message = cws.callable();
         let arg_pats = message.inputs().map(|arg| &arg.pat).collect::<Vec<_>>();
-        let arg_inputs = if arg_pats.len() == 1 {
+        let input_struct_ident = self.input_struct_ident(cws);
+        let arg_inputs = if let Some(input_struct_ident) = input_struct_ident {
+            let fields = Self::input_struct_fields(message);
+            quote! { #input_struct_ident { #( #fields: #arg_pats ),* } }
+        } else if arg_pats.len() == 1 {
             quote! { #(#arg_pats),* }
         } else {
             quote! { ( #(#arg_pats),* ) }
@@ -524,10 +1018,21 @@ impl Dispatch<'_> {
                     quote! { execute_message_mut },
                 )
             }
+            ir::Receiver::Ref if message.returns_ref() => {
+                (None, quote! { LazyMessage }, quote! { execute_lazy_message })
+            }
             ir::Receiver::Ref => {
                 (None, quote! { MessageRef }, quote! { execute_message })
             }
         };
+        // Only `&mut self` messages can be guarded against reentrant calls.
+        let guards_reentrancy = if message.receiver().is_ref_mut() {
+            let guards_reentrancy =
+                message.is_no_reentrancy() || cws.item_impl().is_no_reentrancy();
+            Some(quote! { ::ink_lang::GuardsReentrancy(#guards_reentrancy), })
+        } else {
+            None
+        };
         let selector_id = cws.composed_selector().unique_id();
         let namespace = Self::dispatch_trait_impl_namespace(ir::CallableKind::Message);
         // If all ink! messages deny payment we can move the payment check to before
@@ -537,17 +1042,63 @@ impl Dispatch<'_> {
             .contract
             .config()
             .is_dynamic_storage_allocator_enabled();
+        // Messages without inputs call into the message directly instead of
+        // threading an empty input tuple through its `CALLABLE`.
+        let call_message = if arg_pats.is_empty() {
+            let message_ident = message.ident();
+            let as_trait = cws
+                .item_impl()
+                .trait_path()
+                .map(|trait_path| quote! { as #trait_path });
+            quote! {
+                |state: &#mut_mod #storage_ident| {
+                    <#storage_ident #as_trait>::#message_ident(state)
+                }
+            }
+        } else {
+            quote! {
+                move |state: &#mut_mod #storage_ident| {
+                    <#namespace<[(); #selector_id]> as ::ink_lang::#msg_trait>::CALLABLE(
+                        state, #arg_inputs
+                    )
+                }
+            }
+        };
+        // Callers below the minimum balance are rejected before the message
+        // is executed so that they cannot spend any of its gas.
+        //
+        // The host does not expose the balance of the caller, yet, so the
+        // guard would reject every call on-chain.
+        let ensure_min_caller_balance =
+            message.min_caller_balance().map(|min_caller_balance| {
+                let min_caller_balance = min_caller_balance.expr();
+                quote! {
+                    #[cfg(all(not(feature = "std"), target_arch = "wasm32"))]
+                    ::core::compile_error!(
+                        "`#[ink(min_caller_balance = ..)]` is only supported off-chain"
+                    );
+                    ::ink_lang::ensure_min_caller_balance::<
+                        <#storage_ident as ::ink_lang::ContractEnv>::Env
+                    >(#min_caller_balance)?;
+                }
+            });
+        let execute = quote! {
+            ::ink_lang::#exec_fn::<<#storage_ident as ::ink_lang::ContractEnv>::Env, #namespace<[(); #selector_id]>, _>(
+                ::ink_lang::AcceptsPayments(#accepts_payments),
+                ::ink_lang::EnablesDynamicStorageAllocator(#is_dynamic_storage_allocation_enabled),
+                #guards_reentrancy
+                #call_message
+            )
+        };
+        let execute = if self.contract.config().is_catch_panics_enabled() {
+            quote! { ::ink_lang::catch_panic(move || #execute) }
+        } else {
+            execute
+        };
         quote! {
             Self::#ident(#(#arg_pats),*) => {
-                ::ink_lang::#exec_fn::<<#storage_ident as ::ink_lang::ContractEnv>::Env, #namespace<[(); #selector_id]>, _>(
-                    ::ink_lang::AcceptsPayments(#accepts_payments),
-                    ::ink_lang::EnablesDynamicStorageAllocator(#is_dynamic_storage_allocation_enabled),
-                    move |state: &#mut_mod #storage_ident| {
-                        <#namespace<[(); #selector_id]> as ::ink_lang::#msg_trait>::CALLABLE(
-                            state, #arg_inputs
-                        )
-                    }
-                )
+                #ensure_min_caller_balance
+                #execute
             }
         }
     }
@@ -563,18 +1114,264 @@ impl Dispatch<'_> {
             .flatten()
     }
 
+    /// Returns all ink! messages of the ink! contract sorted by their selectors.
+    ///
+    /// # Note
+    ///
+    /// The dispatch code is generated in this order so that the resulting
+    /// Wasm is byte-identical regardless of the order of the ink! messages
+    /// in the source. This is important to verify contracts on-chain.
+    fn sorted_contract_messages(&self) -> Vec<ir::CallableWithSelector<ir::Message>> {
+        let mut messages = self.contract_messages().collect::<Vec<_>>();
+        messages.sort_by_key(|message| *message.composed_selector().as_bytes());
+        messages
+    }
+
+    /// Generates a table of the selectors of all ink! messages.
+    ///
+    /// # Note
+    ///
+    /// The table is an associated constant of the storage type which lists
+    /// the composed selector of every ink! message. Unlike the rest of the
+    /// dispatch code it is also generated for tests so that those can drive
+    /// their inputs over all valid selectors.
+    fn generate_message_selectors(&self) -> TokenStream2 {
+        let storage_ident = self.contract.module().storage().ident();
+        let selectors = self.contract_messages().map(|message| {
+            let selector_bytes = message.composed_selector().as_bytes().to_owned();
+            quote! {
+                [ #( #selector_bytes ),* ]
+            }
+        });
+        quote! {
+            impl #storage_ident {
+                /// The selectors of all ink! messages.
+                #[doc(hidden)]
+                pub const __INK_MESSAGE_SELECTORS: &'static [[u8; 4]] = &[
+                    #( #selectors ),*
+                ];
+            }
+        }
+    }
+
+    /// Returns the name of the callable as it is reported for its selector.
+    ///
+    /// # Note
+    ///
+    /// Callables of trait implementations are qualified by their trait,
+    /// e.g. `Getter::get`.
+    fn callable_name<C>(cws: ir::CallableWithSelector<'_, C>) -> String
+    where
+        C: ir::Callable,
+    {
+        let ident = cws.callable().ident().to_string();
+        match cws.item_impl().trait_ident() {
+            Some(trait_ident) => format!("{}::{}", trait_ident, ident),
+            None => ident,
+        }
+    }
+
+    /// Generates the reverse maps from selectors to the names of all ink!
+    /// messages and constructors respectively.
+    ///
+    /// # Note
+    ///
+    /// The maps are associated constants of the storage type. They allow to
+    /// translate a received selector into the intended callable, e.g. in
+    /// order to report which callable a malformed call was meant for.
+    /// Like the table of message selectors they are also generated for tests.
+    fn generate_selector_names(&self) -> TokenStream2 {
+        let storage_ident = self.contract.module().storage().ident();
+        let message_names = self.contract_messages().map(|message| {
+            let selector_bytes = message.composed_selector().as_bytes().to_owned();
+            let name = Self::callable_name(message);
+            quote! {
+                ([ #( #selector_bytes ),* ], #name)
+            }
+        });
+        let constructor_names = self.contract_constructors().map(|constructor| {
+            let selector_bytes = constructor.composed_selector().as_bytes().to_owned();
+            let name = Self::callable_name(constructor);
+            quote! {
+                ([ #( #selector_bytes ),* ], #name)
+            }
+        });
+        quote! {
+            impl #storage_ident {
+                /// The selectors of all ink! messages and their names.
+                #[doc(hidden)]
+                pub const __INK_MESSAGE_NAMES: &'static [([u8; 4], &'static str)] = &[
+                    #( #message_names ),*
+                ];
+
+                /// The selectors of all ink! constructors and their names.
+                #[doc(hidden)]
+                pub const __INK_CONSTRUCTOR_NAMES: &'static [([u8; 4], &'static str)] = &[
+                    #( #constructor_names ),*
+                ];
+            }
+        }
+    }
+
+    /// Generates a table of the ABI relevant attributes of all ink! messages.
+    ///
+    /// # Note
+    ///
+    /// Like the selector tables this is also generated for tests so that
+    /// off-chain tooling and tests can validate invariants of the ABI.
+    fn generate_message_infos(&self) -> TokenStream2 {
+        let storage_ident = self.contract.module().storage().ident();
+        let message_infos = self.contract_messages().map(|message| {
+            let selector_bytes = message.composed_selector().as_bytes().to_owned();
+            let mutates = message.callable().receiver().is_ref_mut();
+            let payable = message.is_payable();
+            let args = message.callable().inputs().count();
+            quote! {
+                ::ink_lang::MessageInfo {
+                    selector: [ #( #selector_bytes ),* ],
+                    mutates: #mutates,
+                    payable: #payable,
+                    args: #args,
+                }
+            }
+        });
+        quote! {
+            impl #storage_ident {
+                /// The ABI relevant attributes of all ink! messages.
+                #[doc(hidden)]
+                pub const __INK_MESSAGE_INFOS: &'static [::ink_lang::MessageInfo] = &[
+                    #( #message_infos ),*
+                ];
+            }
+        }
+    }
+
+    /// Generates a selector constant for every ink! message.
+    ///
+    /// # Note
+    ///
+    /// The constants are named after their messages, e.g. the selector of the
+    /// `flip` message is `INK_SELECTOR_FLIP` and the selector of the `get`
+    /// message of the `Getter` trait implementation is `INK_SELECTOR_GETTER_GET`.
+    /// This allows tests and cross-contract callers to refer to selectors by
+    /// name. Messages whose constant names would be ambiguous get no constant.
+    fn generate_selector_constants(&self) -> TokenStream2 {
+        let constants = self
+            .unambiguous_message_names(|trait_ident, message_ident| {
+                let name = match trait_ident {
+                    Some(trait_ident) => format!("{}_{}", trait_ident, message_ident),
+                    None => message_ident.to_string(),
+                };
+                format!("INK_SELECTOR_{}", name.to_uppercase())
+            })
+            .into_iter()
+            .map(|(name, selector_bytes)| {
+                let ident = format_ident!("{}", name);
+                quote! {
+                    /// The selector of the ink! message.
+                    pub const #ident: [u8; 4] = [ #( #selector_bytes ),* ];
+                }
+            });
+        quote! {
+            #( #constants )*
+        }
+    }
+
+    /// Generates the `InkMessage` enum naming all ink! messages.
+    ///
+    /// # Note
+    ///
+    /// The variants are the camel cased names of their messages, e.g. the
+    /// `get` message of the `Getter` trait implementation is `GetterGet`.
+    /// Its `const fn selector` yields the selector computed during codegen so
+    /// that callers can use selectors in `const` contexts, e.g. as patterns.
+    /// Like for the selector constants ambiguous messages get no variant.
+    fn generate_message_names(&self) -> TokenStream2 {
+        let (variants, selectors): (Vec<_>, Vec<_>) = self
+            .unambiguous_message_names(|trait_ident, message_ident| {
+                let name = message_ident.to_string().to_camel_case();
+                match trait_ident {
+                    Some(trait_ident) => format!("{}{}", trait_ident, name),
+                    None => name,
+                }
+            })
+            .into_iter()
+            .map(|(name, selector_bytes)| {
+                (format_ident!("{}", name), quote! { [ #( #selector_bytes ),* ] })
+            })
+            .unzip();
+        quote! {
+            /// The names of all ink! messages of the contract.
+            #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+            pub enum InkMessage {
+                #( #variants ),*
+            }
+
+            impl InkMessage {
+                /// Returns the selector of the ink! message.
+                pub const fn selector(self) -> [u8; 4] {
+                    match self {
+                        #( Self::#variants => #selectors ),*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the names of all ink! messages in order together with their
+    /// selectors where the name is computed from the optional trait identifier
+    /// and the identifier of the message.
+    ///
+    /// Names shared by more than one ink! message are omitted.
+    fn unambiguous_message_names<F>(&self, name_of: F) -> Vec<(String, [u8; 4])>
+    where
+        F: Fn(Option<&Ident>, &Ident) -> String,
+    {
+        use std::collections::HashMap;
+        let mut selectors = <HashMap<String, Option<[u8; 4]>>>::new();
+        let mut names = Vec::new();
+        for message in self.contract_messages() {
+            let name =
+                name_of(message.item_impl().trait_ident(), message.callable().ident());
+            let selector_bytes = message.composed_selector().as_bytes().to_owned();
+            selectors
+                .entry(name.clone())
+                .and_modify(|selector| *selector = None)
+                .or_insert_with(|| {
+                    names.push(name);
+                    Some(selector_bytes)
+                });
+        }
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let selector_bytes = selectors[&name]?;
+                Some((name, selector_bytes))
+            })
+            .collect()
+    }
+
     /// Generates the entire dispatch variant enum for all ink! messages.
     fn generate_message_dispatch_enum(&self) -> TokenStream2 {
         let storage_ident = self.contract.module().storage().ident();
-        let message_variants = self
-            .contract_messages()
-            .map(|message| self.generate_dispatch_variant_arm(message));
-        let decode_message = self
-            .contract_messages()
-            .map(|message| self.generate_dispatch_variant_decode(message));
-        let execute_variants = self
-            .contract_messages()
-            .map(|message| self.generate_dispatch_execute_message_arm(message));
+        let messages = self.sorted_contract_messages();
+        let message_variants = messages
+            .iter()
+            .map(|message| self.generate_dispatch_variant_arm(*message));
+        let (wildcard, selected): (Vec<_>, Vec<_>) = messages
+            .iter()
+            .copied()
+            .partition(|message| message.callable().is_wildcard());
+        // Calls with unknown selectors are dispatched to the ink! message with
+        // a wildcard selector if any.
+        let decode_dispatch = self.generate_decode_dispatch(
+            ir::CallableKind::Message,
+            &selected,
+            wildcard.first().copied(),
+        );
+        let execute_variants = messages
+            .iter()
+            .map(|message| self.generate_dispatch_execute_message_arm(*message));
         quote! {
             const _: () = {
                 #[doc(hidden)]
@@ -586,12 +1383,16 @@ impl Dispatch<'_> {
                     type Type = __ink_MessageDispatchEnum;
                 }
 
+                impl ::ink_lang::DecodeDispatch for __ink_MessageDispatchEnum {
+                    fn decode_dispatch<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::ink_lang::DispatchError> {
+                        #decode_dispatch
+                    }
+                }
+
                 impl ::scale::Decode for __ink_MessageDispatchEnum {
                     fn decode<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::scale::Error> {
-                        match <[u8; 4] as ::scale::Decode>::decode(input)? {
-                            #( #decode_message )*
-                            _invalid => Err(::scale::Error::from("encountered unknown ink! message selector"))
-                        }
+                        <Self as ::ink_lang::DecodeDispatch>::decode_dispatch(input)
+                            .map_err(Into::into)
                     }
                 }
 
@@ -619,7 +1420,10 @@ impl Dispatch<'_> {
         let ident = self.generate_dispatch_variant_ident(cws);
         let constructor = cws.callable();
         let arg_pats = constructor.inputs().map(|arg| &arg.pat).collect::<Vec<_>>();
-        let arg_inputs = if arg_pats.len() == 1 {
+        let arg_inputs = if let Some(input_struct_ident) = self.input_struct_ident(cws) {
+            let fields = Self::input_struct_fields(constructor);
+            quote! { #input_struct_ident { #( #fields: #arg_pats ),* } }
+        } else if arg_pats.len() == 1 {
             quote! { #(#arg_pats),* }
         } else {
             quote! { ( #(#arg_pats),* ) }
@@ -631,12 +1435,27 @@ impl Dispatch<'_> {
             .contract
             .config()
             .is_dynamic_storage_allocator_enabled();
+        // Fallible constructors abort the deployment upon returning an error
+        // which is returned with the revert flag set if requested.
+        let (constructor_trait, exec_fn) = if constructor.is_revert_on_error() {
+            (
+                quote! { FallibleConstructor },
+                quote! { execute_reverting_constructor },
+            )
+        } else if constructor.is_fallible() {
+            (
+                quote! { FallibleConstructor },
+                quote! { execute_fallible_constructor },
+            )
+        } else {
+            (quote! { Constructor }, quote! { execute_constructor })
+        };
         quote! {
             Self::#ident(#(#arg_pats),*) => {
-                ::ink_lang::execute_constructor::<#namespace<[(); #selector_id]>, _>(
+                ::ink_lang::#exec_fn::<#namespace<[(); #selector_id]>, _>(
                     ::ink_lang::EnablesDynamicStorageAllocator(#is_dynamic_storage_allocation_enabled),
                     move || {
-                        <#namespace<[(); #selector_id]> as ::ink_lang::Constructor>::CALLABLE(
+                        <#namespace<[(); #selector_id]> as ::ink_lang::#constructor_trait>::CALLABLE(
                             #arg_inputs
                         )
                     }
@@ -656,18 +1475,41 @@ impl Dispatch<'_> {
             .flatten()
     }
 
+    /// Returns all ink! constructors of the ink! contract sorted by their selectors.
+    ///
+    /// # Note
+    ///
+    /// See [`Dispatch::sorted_contract_messages`] for why this is required.
+    fn sorted_contract_constructors(
+        &self,
+    ) -> Vec<ir::CallableWithSelector<ir::Constructor>> {
+        let mut constructors = self.contract_constructors().collect::<Vec<_>>();
+        constructors
+            .sort_by_key(|constructor| *constructor.composed_selector().as_bytes());
+        constructors
+    }
+
     /// Generates the entire dispatch variant enum for all ink! messages.
     fn generate_constructor_dispatch_enum(&self) -> TokenStream2 {
         let storage_ident = self.contract.module().storage().ident();
-        let message_variants = self
-            .contract_constructors()
-            .map(|message| self.generate_dispatch_variant_arm(message));
-        let decode_message = self
-            .contract_constructors()
-            .map(|message| self.generate_dispatch_variant_decode(message));
-        let execute_variants = self
-            .contract_constructors()
-            .map(|cws| self.generate_dispatch_execute_constructor_arm(cws));
+        let constructors = self.sorted_contract_constructors();
+        let message_variants = constructors
+            .iter()
+            .map(|message| self.generate_dispatch_variant_arm(*message));
+        let (fallback, selected): (Vec<_>, Vec<_>) = constructors
+            .iter()
+            .copied()
+            .partition(|constructor| constructor.callable().is_fallback());
+        // Instantiations with unknown selectors are dispatched to the fallback
+        // ink! constructor if any.
+        let decode_dispatch = self.generate_decode_dispatch(
+            ir::CallableKind::Constructor,
+            &selected,
+            fallback.first().copied(),
+        );
+        let execute_variants = constructors
+            .iter()
+            .map(|cws| self.generate_dispatch_execute_constructor_arm(*cws));
         quote! {
             const _: () = {
                 #[doc(hidden)]
@@ -679,12 +1521,16 @@ impl Dispatch<'_> {
                     type Type = __ink_ConstructorDispatchEnum;
                 }
 
+                impl ::ink_lang::DecodeDispatch for __ink_ConstructorDispatchEnum {
+                    fn decode_dispatch<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::ink_lang::DispatchError> {
+                        #decode_dispatch
+                    }
+                }
+
                 impl ::scale::Decode for __ink_ConstructorDispatchEnum {
                     fn decode<I: ::scale::Input>(input: &mut I) -> ::core::result::Result<Self, ::scale::Error> {
-                        match <[u8; 4] as ::scale::Decode>::decode(input)? {
-                            #( #decode_message )*
-                            _invalid => Err(::scale::Error::from("encountered unknown ink! constructor selector"))
-                        }
+                        <Self as ::ink_lang::DecodeDispatch>::decode_dispatch(input)
+                            .map_err(Into::into)
                     }
                 }
 
@@ -699,3 +1545,406 @@ impl Dispatch<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_reentrancy_works() {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod flipper {
+                    #[ink(storage)]
+                    pub struct Flipper {
+                        value: bool,
+                    }
+
+                    impl Flipper {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self { value: false }
+                        }
+
+                        #[ink(message, no_reentrancy)]
+                        pub fn flip(&mut self) {
+                            self.value = !self.value;
+                        }
+
+                        #[ink(message)]
+                        pub fn set(&mut self, value: bool) {
+                            self.value = value;
+                        }
+
+                        #[ink(message)]
+                        pub fn get(&self) -> bool {
+                            self.value
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let dispatch = Dispatch::from(&contract);
+        let execute_arms = dispatch
+            .contract_messages()
+            .map(|cws| {
+                dispatch
+                    .generate_dispatch_execute_message_arm(cws)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        let guards = |value: bool| {
+            quote! { ::ink_lang::GuardsReentrancy(#value) }.to_string()
+        };
+        assert_eq!(execute_arms.len(), 3);
+        assert!(execute_arms[0].contains(&guards(true)));
+        assert!(execute_arms[1].contains(&guards(false)));
+        assert!(!execute_arms[2].contains("GuardsReentrancy"));
+    }
+
+    #[test]
+    fn zero_arg_message_fast_path_works() {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod flipper {
+                    #[ink(storage)]
+                    pub struct Flipper {
+                        value: bool,
+                    }
+
+                    impl Flipper {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self { value: false }
+                        }
+
+                        #[ink(message)]
+                        pub fn get(&self) -> bool {
+                            self.value
+                        }
+
+                        #[ink(message)]
+                        pub fn set(&mut self, value: bool) {
+                            self.value = value;
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let dispatch = Dispatch::from(&contract);
+        let execute_arms = dispatch
+            .contract_messages()
+            .map(|cws| {
+                dispatch
+                    .generate_dispatch_execute_message_arm(cws)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(execute_arms.len(), 2);
+        assert!(execute_arms[0].contains(
+            &quote! {
+                |state: &Flipper| {
+                    <Flipper>::get(state)
+                }
+            }
+            .to_string()
+        ));
+        assert!(!execute_arms[0].contains("CALLABLE"));
+        assert!(execute_arms[1].contains("CALLABLE"));
+    }
+
+    #[test]
+    fn zero_arg_constructor_decode_fast_path_works() {
+        let constructor_bodies = |config: TokenStream2| {
+            let contract = ir::Contract::new(
+                config,
+                quote! {
+                    mod flipper {
+                        #[ink(storage)]
+                        pub struct Flipper {
+                            value: bool,
+                        }
+
+                        impl Flipper {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {
+                                Self { value: false }
+                            }
+
+                            #[ink(constructor)]
+                            pub fn with_value(value: bool) -> Self {
+                                Self { value }
+                            }
+
+                            #[ink(message)]
+                            pub fn get(&self) -> bool {
+                                self.value
+                            }
+                        }
+                    }
+                },
+            )
+            .unwrap();
+            let dispatch = Dispatch::from(&contract);
+            dispatch
+                .contract_constructors()
+                .map(|cws| {
+                    let bodies = dispatch.generate_dispatch_variant_decode_bodies(cws);
+                    assert_eq!(bodies.len(), 1);
+                    let variant_ident = dispatch.generate_dispatch_variant_ident(cws);
+                    (variant_ident, bodies[0].1.to_string())
+                })
+                .collect::<Vec<_>>()
+        };
+        let bodies = constructor_bodies(quote! {});
+        assert_eq!(bodies.len(), 2);
+        // The `new()` constructor does not decode anything from the input.
+        let (new_ident, new_body) = &bodies[0];
+        assert_eq!(new_body, &quote! { Ok(Self::#new_ident()) }.to_string());
+        assert!(!new_body.contains("Decode"));
+        assert!(bodies[1].1.contains("Decode"));
+        // Strict input decoding still rejects trailing input bytes.
+        let strict_bodies = constructor_bodies(quote! { strict_input_decoding = true });
+        assert!(strict_bodies[0].1.contains("ensure_input_exhausted"));
+        assert!(!strict_bodies[0].1.contains("Decode"));
+    }
+
+    #[test]
+    fn constructor_only_contract_works() {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod constructor_only {
+                    #[ink(storage)]
+                    pub struct ConstructorOnly {}
+
+                    impl ConstructorOnly {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self {}
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let generated = Dispatch::from(&contract)
+            .generate_message_dispatch_enum()
+            .to_string();
+        assert!(generated.contains(
+            &quote! {
+                pub enum __ink_MessageDispatchEnum {}
+            }
+            .to_string()
+        ));
+        // All selectors are rejected since there are no ink! messages.
+        assert!(generated.contains(
+            &quote! {
+                .map_err(|_| ::ink_lang::DispatchError::CouldNotReadInput)?
+                {
+                    _invalid => Err(::ink_lang::DispatchError::UnknownCallSelector)
+                }
+            }
+            .to_string()
+        ));
+        assert!(generated.contains(&quote! { match self {} }.to_string()));
+    }
+
+    #[test]
+    fn dispatch_is_independent_of_source_order() {
+        let constructors = [
+            quote! {
+                #[ink(constructor)]
+                pub fn new(init_value: bool) -> Self {
+                    Self { value: init_value }
+                }
+            },
+            quote! {
+                #[ink(constructor)]
+                pub fn default() -> Self {
+                    Self::new(false)
+                }
+            },
+        ];
+        let messages = [
+            quote! {
+                #[ink(message)]
+                pub fn flip(&mut self) {
+                    self.value = !self.value;
+                }
+            },
+            quote! {
+                #[ink(message)]
+                pub fn set(&mut self, value: bool) {
+                    self.value = value;
+                }
+            },
+            quote! {
+                #[ink(message)]
+                pub fn get(&self) -> bool {
+                    self.value
+                }
+            },
+        ];
+        let generate = |order: &[usize]| {
+            let constructors = order.iter().filter_map(|&n| constructors.get(n));
+            let messages = order.iter().filter_map(|&n| messages.get(n));
+            let contract = ir::Contract::new(
+                quote! {},
+                quote! {
+                    mod flipper {
+                        #[ink(storage)]
+                        pub struct Flipper {
+                            value: bool,
+                        }
+
+                        impl Flipper {
+                            #( #constructors )*
+                            #( #messages )*
+                        }
+                    }
+                },
+            )
+            .unwrap();
+            let dispatch = Dispatch::from(&contract);
+            (
+                dispatch.generate_constructor_dispatch_enum().to_string(),
+                dispatch.generate_message_dispatch_enum().to_string(),
+            )
+        };
+        assert_eq!(generate(&[0, 1, 2]), generate(&[2, 1, 0]));
+        assert_eq!(generate(&[0, 1, 2]), generate(&[1, 2, 0]));
+    }
+
+    #[test]
+    fn same_message_in_different_traits_routes_to_its_trait() {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod getters {
+                    #[ink(storage)]
+                    pub struct Getters {
+                        a: bool,
+                        b: bool,
+                    }
+
+                    impl Getters {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self { a: true, b: false }
+                        }
+                    }
+
+                    impl GetterA for Getters {
+                        #[ink(message)]
+                        fn get(&self) -> bool {
+                            self.a
+                        }
+                    }
+
+                    impl GetterB for Getters {
+                        #[ink(message)]
+                        fn get(&self) -> bool {
+                            self.b
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let dispatch = Dispatch::from(&contract);
+        let messages = dispatch.contract_messages().collect::<Vec<_>>();
+        assert_eq!(messages.len(), 2);
+        assert_ne!(messages[0].composed_selector(), messages[1].composed_selector());
+        let routes = messages
+            .iter()
+            .map(|cws| {
+                dispatch
+                    .generate_dispatch_execute_message_arm(*cws)
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        let call = |trait_ident: &str| {
+            let trait_ident = format_ident!("{}", trait_ident);
+            quote! { <Getters as #trait_ident>::get(state) }.to_string()
+        };
+        assert!(routes[0].contains(&call("GetterA")));
+        assert!(routes[1].contains(&call("GetterB")));
+        let constants = dispatch.generate_selector_constants().to_string();
+        assert!(constants.contains("INK_SELECTOR_GETTERA_GET"));
+        assert!(constants.contains("INK_SELECTOR_GETTERB_GET"));
+    }
+
+    #[test]
+    fn many_selectors_are_looked_up_via_binary_search() {
+        let contract_with = |num_messages: u8| {
+            // Messages are declared in descending order of their selectors.
+            let messages = (0..num_messages).rev().map(|index| {
+                let ident = format_ident!("message_{}", index);
+                let selector = format!("0x{:02X}000000", index);
+                quote! {
+                    #[ink(message, selector = #selector)]
+                    pub fn #ident(&self) {}
+                }
+            });
+            ir::Contract::new(
+                quote! {},
+                quote! {
+                    mod many {
+                        #[ink(storage)]
+                        pub struct Many {}
+
+                        impl Many {
+                            #[ink(constructor)]
+                            pub fn new() -> Self {
+                                Self {}
+                            }
+
+                            #( #messages )*
+                        }
+                    }
+                },
+            )
+            .unwrap()
+        };
+        let lookup = quote! { __INK_SELECTORS.binary_search }.to_string();
+        let few = contract_with(DISPATCH_TABLE_THRESHOLD as u8);
+        let generated = Dispatch::from(&few)
+            .generate_message_dispatch_enum()
+            .to_string();
+        assert!(!generated.contains(&lookup));
+        let num_messages = DISPATCH_TABLE_THRESHOLD as u8 + 1;
+        let many = contract_with(num_messages);
+        let generated = Dispatch::from(&many)
+            .generate_message_dispatch_enum()
+            .to_string();
+        assert!(generated.contains(&lookup));
+        // All selectors are listed in ascending order and resolve to the
+        // decoding of their respective ink! message.
+        let selectors = (0..num_messages).map(|index| {
+            let selector_bytes = [index, 0x00, 0x00, 0x00];
+            quote! { [ #( #selector_bytes ),* ] }
+        });
+        let len = num_messages as usize;
+        let table = quote! {
+            const __INK_SELECTORS: [[u8; 4]; #len] = [ #( #selectors ),* ];
+        }
+        .to_string();
+        assert!(generated.contains(&table));
+        for index in 0..num_messages {
+            let position = index as usize;
+            let variant = format_ident!("__ink_Message_0x{:02X}000000", index);
+            let arm = quote! {
+                Ok(#position) => {
+                    Ok(Self::#variant())
+                }
+            }
+            .to_string();
+            assert!(generated.contains(&arm));
+        }
+    }
+}
@@ -129,7 +129,7 @@ impl<'a> Events<'a> {
             const _: () = {
                 #no_cross_calling_cfg
                 impl ::ink_core::env::Topics<EnvTypes> for #base_event_ident {
-                    fn topics(&self) -> &'static [Hash] {
+                    fn topics(&self) -> ::ink_core::env::TopicHashes<EnvTypes> {
                         match self {
                             #(
                                 Self::#event_idents(event) => {
@@ -194,22 +194,50 @@ impl<'a> Events<'a> {
         })
     }
 
-    /// Generates the `Topics` trait implementations for the user defined events.
+    /// Generates the `Topics` and `TopicFields` trait implementations for the
+    /// user defined events.
+    ///
+    /// # Note
+    ///
+    /// The topics of an event are the hashes of its `#[ink(topic)]` fields.
+    /// The `TopicFields` implementation lists the names of those fields in
+    /// the order of their topics so that tests can tell which field produced
+    /// which topic.
     fn generate_topics_impls(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         let no_cross_calling_cfg =
             self.generate_code_using::<generator::CrossCallingConflictCfg>();
         self.contract.module().events().map(move |event| {
             let span = event.span();
             let ident = event.ident();
+            let topic_fields = event
+                .fields()
+                .filter(|event_field| event_field.is_topic)
+                .filter_map(|event_field| event_field.ident())
+                .collect::<Vec<_>>();
+            let topic_names = topic_fields.iter().map(ToString::to_string);
             quote_spanned!(span =>
                 #no_cross_calling_cfg
                 const _: () = {
                     impl ::ink_core::env::Topics<EnvTypes> for #ident {
-                        fn topics(&self) -> &'static [Hash] {
-                            // Issue: https://github.com/paritytech/ink/issues/105
-                            &[]
+                        fn topics(&self) -> ::ink_core::env::TopicHashes<EnvTypes> {
+                            [
+                                #(
+                                    ::ink_core::env::topic_hash::<EnvTypes, _>(
+                                        &self.#topic_fields
+                                    )
+                                ),*
+                            ]
+                            .iter()
+                            .copied()
+                            .collect()
                         }
                     }
+
+                    impl ::ink_core::env::TopicFields for #ident {
+                        const TOPIC_FIELDS: &'static [&'static str] = &[
+                            #( #topic_names ),*
+                        ];
+                    }
                 };
             )
         })
@@ -245,3 +273,65 @@ impl<'a> Events<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topic_fields_work() {
+        let contract = ir::Contract::new(
+            quote! {},
+            quote! {
+                mod erc20 {
+                    #[ink(storage)]
+                    pub struct Erc20 {
+                        total_supply: Balance,
+                    }
+
+                    #[ink(event)]
+                    pub struct Transfer {
+                        #[ink(topic)]
+                        from: Option<AccountId>,
+                        #[ink(topic)]
+                        to: Option<AccountId>,
+                        value: Balance,
+                    }
+
+                    impl Erc20 {
+                        #[ink(constructor)]
+                        pub fn new() -> Self {
+                            Self { total_supply: 0 }
+                        }
+
+                        #[ink(message)]
+                        pub fn total_supply(&self) -> Balance {
+                            self.total_supply
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let generated = Events::from(&contract)
+            .generate_topics_impls()
+            .map(|topics_impl| topics_impl.to_string())
+            .collect::<String>();
+        assert!(generated.contains(
+            &quote! {
+                const TOPIC_FIELDS: &'static [&'static str] = &["from", "to"];
+            }
+            .to_string()
+        ));
+        // The topics are hashed from the topic fields in the same order.
+        assert!(generated.contains(
+            &quote! {
+                [
+                    ::ink_core::env::topic_hash::<EnvTypes, _>(&self.from),
+                    ::ink_core::env::topic_hash::<EnvTypes, _>(&self.to)
+                ]
+            }
+            .to_string()
+        ));
+    }
+}
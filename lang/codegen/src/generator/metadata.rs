@@ -118,8 +118,11 @@ impl Metadata<'_> {
                     .trait_path()
                     .map(|path| path.segments.last().map(|seg| &seg.ident))
                     .flatten();
+                // The fallback ink! constructor has no fixed selector that the
+                // metadata could advertise.
                 impl_block
                     .iter_constructors()
+                    .filter(|constructor| !constructor.is_fallback())
                     .map(move |constructor| (trait_ident, constructor))
             })
             .map(|(trait_ident, constructor)| {
@@ -209,8 +212,11 @@ impl Metadata<'_> {
                     .trait_path()
                     .map(|path| path.segments.last().map(|seg| &seg.ident))
                     .flatten();
+                // The ink! message with wildcard selector has no fixed selector
+                // that the metadata could advertise.
                 impl_block
                     .iter_messages()
+                    .filter(|message| !message.is_wildcard())
                     .map(move |message| (trait_ident, message))
             })
             .map(|(trait_ident, message)| {
@@ -227,7 +233,7 @@ impl Metadata<'_> {
                 let args = message
                     .inputs()
                     .map(|arg| Self::generate_message_param(arg));
-                let ret_ty = Self::generate_return_type(message.output());
+                let ret_ty = Self::generate_return_type(message.encoded_output());
                 let constr = match trait_ident {
                     Some(trait_ident) => {
                         let trait_ident_lit = trait_ident.to_string();
@@ -230,11 +230,72 @@ impl InkAttribute {
         })
     }
 
+    /// Returns all selectors of the ink! attribute in order of appearance.
+    ///
+    /// # Note
+    ///
+    /// The first selector is the one returned by [`InkAttribute::selector`].
+    /// All following selectors are aliases of it.
+    pub fn selectors(&self) -> impl Iterator<Item = ir::Selector> + '_ {
+        self.args().filter_map(|arg| {
+            if let ir::AttributeArgKind::Selector(selector) = arg.kind() {
+                return Some(*selector)
+            }
+            None
+        })
+    }
+
     /// Returns `true` if the ink! attribute contains the `payable` argument.
     pub fn is_payable(&self) -> bool {
         self.args()
             .any(|arg| matches!(arg.kind(), AttributeArgKind::Payable))
     }
+
+    /// Returns `true` if the ink! attribute contains the wildcard selector argument.
+    pub fn has_wildcard_selector(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArgKind::WildcardSelector))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `no_reentrancy` argument.
+    pub fn is_no_reentrancy(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArgKind::NoReentrancy))
+    }
+
+    /// Returns the version of the ink! attribute if any.
+    pub fn version(&self) -> Option<u32> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArgKind::Version(version) = arg.kind() {
+                return Some(*version)
+            }
+            None
+        })
+    }
+
+    /// Returns the minimum caller balance of the ink! attribute if any.
+    pub fn min_caller_balance(&self) -> Option<ir::MinCallerBalance> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArgKind::MinCallerBalance(min_caller_balance) =
+                arg.kind()
+            {
+                return Some(min_caller_balance.clone())
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if the ink! attribute contains the `fallback` argument.
+    pub fn is_fallback(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArgKind::Fallback))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `revert_on_error` argument.
+    pub fn is_revert_on_error(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArgKind::RevertOnError))
+    }
 }
 
 /// An ink! specific attribute argument.
@@ -288,11 +349,56 @@ pub enum AttributeArgKind {
     /// Applied on ink! constructors or messages in order to specify that they
     /// can receive funds from callers.
     Payable,
+    /// `#[ink(no_reentrancy)]`
+    ///
+    /// Applied on ink! messages or ink! implementation blocks in order to
+    /// reject calls to `&mut self` messages while they are already executed.
+    /// The guard keeps a flag in the contract storage under the `[0xFD; 32]`
+    /// key which costs a storage write and clear per guarded call.
+    NoReentrancy,
     /// `#[ink(selector = "0xDEADBEEF")]`
     ///
     /// Applied on ink! constructors or messages to manually control their
     /// selectors.
+    ///
+    /// ink! messages may have multiple selectors where all but the first
+    /// are aliases of it, e.g. `#[ink(selector = "0xDEADBEEF", selector = "0xC0DECAFE")]`.
     Selector(Selector),
+    /// `#[ink(selector = "_")]`
+    ///
+    /// Applied on a single ink! message in order to dispatch all calls with
+    /// selectors not matching any other ink! message to it. The message
+    /// receives the selector of the call as its first `[u8; 4]` argument.
+    WildcardSelector,
+    /// `#[ink(fallback)]`
+    ///
+    /// Applied on a single ink! constructor in order to dispatch all
+    /// instantiations with selectors not matching any other ink! constructor
+    /// to it. The constructor receives the selector of the instantiation as
+    /// its first `[u8; 4]` argument.
+    Fallback,
+    /// `#[ink(revert_on_error)]`
+    ///
+    /// Applied on fallible ink! constructors in order to return their error
+    /// as output of the reverted instantiation. The error type must implement
+    /// `scale::Encode`.
+    RevertOnError,
+    /// `#[ink(version = 2)]`
+    ///
+    /// Applied on ink! messages in order to version them. The selectors of
+    /// all earlier versions of the ink! message are kept as deprecated
+    /// aliases of its current selector.
+    Version(u32),
+    /// `#[ink(min_caller_balance = 1000)]`
+    ///
+    /// Applied on ink! messages in order to reject calls from callers whose
+    /// balance is below the given minimum. The minimum is either an integer
+    /// literal or a string literal containing an expression, e.g.
+    /// `#[ink(min_caller_balance = "MIN_BALANCE")]`.
+    ///
+    /// Only supported off-chain since the host does not expose the balance of
+    /// the caller, yet.
+    MinCallerBalance(MinCallerBalance),
     /// `#[ink(namespace = "my_namespace")]`
     ///
     /// Applied on ink! trait implementation blocks to disambiguate other trait
@@ -321,7 +427,15 @@ impl core::fmt::Display for AttributeArgKind {
             Self::Message => write!(f, "message"),
             Self::Constructor => write!(f, "constructor"),
             Self::Payable => write!(f, "payable"),
+            Self::NoReentrancy => write!(f, "no_reentrancy"),
             Self::Selector(selector) => write!(f, "selector = {:?}", selector.as_bytes()),
+            Self::WildcardSelector => write!(f, "selector = _"),
+            Self::Fallback => write!(f, "fallback"),
+            Self::RevertOnError => write!(f, "revert_on_error"),
+            Self::Version(version) => write!(f, "version = {}", version),
+            Self::MinCallerBalance(min_caller_balance) => {
+                write!(f, "min_caller_balance = {}", min_caller_balance.as_str())
+            }
             Self::Namespace(namespace) => {
                 write!(f, "namespace = {:?}", namespace.as_bytes())
             }
@@ -350,6 +464,26 @@ impl Namespace {
     }
 }
 
+/// The minimum balance that callers of an ink! message must have.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MinCallerBalance {
+    /// The tokens of the minimum balance expression.
+    expr: String,
+}
+
+impl MinCallerBalance {
+    /// Returns the minimum balance expression as string.
+    pub fn as_str(&self) -> &str {
+        &self.expr
+    }
+
+    /// Returns the minimum balance expression.
+    pub fn expr(&self) -> syn::Expr {
+        syn::parse_str(&self.expr)
+            .expect("encountered invalid minimum caller balance expression")
+    }
+}
+
 /// Returns `true` if the given iterator yields at least one attribute of the form
 /// `#[ink(..)]` or `#[ink]`.
 ///
@@ -560,6 +694,12 @@ impl TryFrom<syn::NestedMeta> for AttributeArg {
                     syn::Meta::NameValue(name_value) => {
                         if name_value.path.is_ident("selector") {
                             if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                if lit_str.value() == "_" {
+                                    return Ok(AttributeArg {
+                                        ast: meta,
+                                        kind: AttributeArgKind::WildcardSelector,
+                                    })
+                                }
                                 let regex = Regex::new(
                                     r"0x([\da-fA-F]{2})([\da-fA-F]{2})([\da-fA-F]{2})([\da-fA-F]{2})"
                                 ).map_err(|_| {
@@ -603,6 +743,51 @@ impl TryFrom<syn::NestedMeta> for AttributeArg {
                                 })
                             }
                         }
+                        if name_value.path.is_ident("version") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let version = lit_int
+                                    .base10_parse::<u32>()
+                                    .ok()
+                                    .filter(|version| *version >= 1)
+                                    .ok_or_else(|| {
+                                        format_err_spanned!(
+                                            lit_int,
+                                            "ink! versions must be integers starting at 1"
+                                        )
+                                    })?;
+                                return Ok(AttributeArg {
+                                    ast: meta,
+                                    kind: AttributeArgKind::Version(version),
+                                })
+                            }
+                        }
+                        if name_value.path.is_ident("min_caller_balance") {
+                            let expr = match &name_value.lit {
+                                syn::Lit::Int(lit_int) => lit_int.to_string(),
+                                syn::Lit::Str(lit_str) => {
+                                    lit_str.parse::<syn::Expr>().map_err(|_| {
+                                        format_err_spanned!(
+                                            lit_str,
+                                            "invalid minimum caller balance expression"
+                                        )
+                                    })?;
+                                    lit_str.value()
+                                }
+                                _ => {
+                                    return Err(format_err_spanned!(
+                                        name_value.lit,
+                                        "expected an integer or string literal \
+                                         for the minimum caller balance"
+                                    ))
+                                }
+                            };
+                            return Ok(AttributeArg {
+                                ast: meta,
+                                kind: AttributeArgKind::MinCallerBalance(
+                                    MinCallerBalance { expr },
+                                ),
+                            })
+                        }
                         Err(format_err_spanned!(
                             meta,
                             "unknown ink! attribute argument (name = value)",
@@ -618,6 +803,11 @@ impl TryFrom<syn::NestedMeta> for AttributeArg {
                                     "event" => Some(AttributeArgKind::Event),
                                     "topic" => Some(AttributeArgKind::Topic),
                                     "payable" => Some(AttributeArgKind::Payable),
+                                    "no_reentrancy" => Some(AttributeArgKind::NoReentrancy),
+                                    "fallback" => Some(AttributeArgKind::Fallback),
+                                    "revert_on_error" => {
+                                        Some(AttributeArgKind::RevertOnError)
+                                    }
                                     "impl" => Some(AttributeArgKind::Implementation),
                                     _ => None,
                                 }
@@ -814,6 +1004,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn wildcard_selector_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(selector = "_")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArgKind::WildcardSelector])),
+        );
+    }
+
     #[test]
     fn namespace_works() {
         assert_attribute_try_from(
@@ -826,6 +1026,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn version_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(version = 2)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArgKind::Version(2)])),
+        );
+    }
+
+    #[test]
+    fn version_zero_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(version = 0)]
+            },
+            Err("ink! versions must be integers starting at 1"),
+        );
+    }
+
+    #[test]
+    fn min_caller_balance_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(min_caller_balance = 1000)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArgKind::MinCallerBalance(
+                MinCallerBalance {
+                    expr: "1000".to_string(),
+                },
+            )])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(min_caller_balance = "MIN_BALANCE * 2")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArgKind::MinCallerBalance(
+                MinCallerBalance {
+                    expr: "MIN_BALANCE * 2".to_string(),
+                },
+            )])),
+        );
+    }
+
+    #[test]
+    fn min_caller_balance_invalid_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(min_caller_balance = "1000 +")]
+            },
+            Err("invalid minimum caller balance expression"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(min_caller_balance = true)]
+            },
+            Err("expected an integer or string literal for the minimum caller balance"),
+        );
+    }
+
     #[test]
     fn compound_mixed_works() {
         assert_attribute_try_from(
@@ -852,6 +1112,9 @@ mod tests {
                     event,
                     topic,
                     payable,
+                    no_reentrancy,
+                    fallback,
+                    revert_on_error,
                     impl,
                 )]
             },
@@ -862,6 +1125,9 @@ mod tests {
                 AttributeArgKind::Event,
                 AttributeArgKind::Topic,
                 AttributeArgKind::Payable,
+                AttributeArgKind::NoReentrancy,
+                AttributeArgKind::Fallback,
+                AttributeArgKind::RevertOnError,
                 AttributeArgKind::Implementation,
             ])),
         );
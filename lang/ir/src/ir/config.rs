@@ -15,6 +15,7 @@
 use crate::{
     ast,
     error::ExtError as _,
+    ir,
 };
 use core::convert::TryFrom;
 use syn::spanned::Spanned;
@@ -38,6 +39,30 @@ pub struct Config {
     /// be used to change the underlying environmental types of an ink! smart
     /// contract.
     env_types: Option<EnvTypes>,
+    /// If `true` the dispatch of the ink! smart contract rejects
+    /// inputs that have trailing bytes after all arguments of the
+    /// dispatched ink! message or constructor have been decoded.
+    /// The default is `false`.
+    strict_input_decoding: Option<bool>,
+    /// If `true` the inputs of ink! messages with multiple arguments are
+    /// packed into a generated named struct instead of an anonymous tuple.
+    /// The default is `false`.
+    named_inputs: Option<bool>,
+    /// If `true` the inputs of ink! constructors with multiple arguments are
+    /// packed into a generated named struct instead of an anonymous tuple.
+    /// The default is `false`.
+    named_constructor_inputs: Option<bool>,
+    /// The strategy to derive the selectors of ink! messages and constructors
+    /// without user provided selector. The default is `"qualified"`.
+    selector_derivation: Option<ir::SelectorDerivation>,
+    /// If `true` the selectors of ink! constructors and messages must be
+    /// unique across both kinds instead of only within each kind.
+    /// The default is `false`.
+    unique_selectors: Option<bool>,
+    /// If `true` panics of ink! messages are converted into a dispatch
+    /// error instead of aborting the contract execution in the off-chain
+    /// environment. The default is `false`.
+    catch_panics: Option<bool>,
 }
 
 /// Return an error to notify about duplicate ink! config arguments.
@@ -65,6 +90,15 @@ impl TryFrom<ast::AttributeArgs> for Config {
         let mut dynamic_storage_allocator: Option<(bool, ast::MetaNameValue)> = None;
         let mut as_dependency: Option<(bool, ast::MetaNameValue)> = None;
         let mut env_types: Option<(EnvTypes, ast::MetaNameValue)> = None;
+        let mut strict_input_decoding: Option<(bool, ast::MetaNameValue)> = None;
+        let mut named_inputs: Option<(bool, ast::MetaNameValue)> = None;
+        let mut named_constructor_inputs: Option<(bool, ast::MetaNameValue)> = None;
+        let mut selector_derivation: Option<(
+            ir::SelectorDerivation,
+            ast::MetaNameValue,
+        )> = None;
+        let mut unique_selectors: Option<(bool, ast::MetaNameValue)> = None;
+        let mut catch_panics: Option<(bool, ast::MetaNameValue)> = None;
         for arg in args.into_iter() {
             if arg.name.is_ident("dynamic_storage_allocator") {
                 if let Some((_, ast)) = dynamic_storage_allocator {
@@ -106,6 +140,92 @@ impl TryFrom<ast::AttributeArgs> for Config {
                         "expected a path for `env_types` ink! config argument",
                     ))
                 }
+            } else if arg.name.is_ident("strict_input_decoding") {
+                if let Some((_, ast)) = strict_input_decoding {
+                    return Err(duplicate_config_err(ast, arg, "strict_input_decoding"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    strict_input_decoding = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `strict_input_decoding` ink! config argument",
+                    ))
+                }
+            } else if arg.name.is_ident("named_inputs") {
+                if let Some((_, ast)) = named_inputs {
+                    return Err(duplicate_config_err(ast, arg, "named_inputs"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    named_inputs = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `named_inputs` ink! config argument",
+                    ))
+                }
+            } else if arg.name.is_ident("named_constructor_inputs") {
+                if let Some((_, ast)) = named_constructor_inputs {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "named_constructor_inputs",
+                    ))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    named_constructor_inputs = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `named_constructor_inputs` ink! config argument",
+                    ))
+                }
+            } else if arg.name.is_ident("selector_derivation") {
+                if let Some((_, ast)) = selector_derivation {
+                    return Err(duplicate_config_err(ast, arg, "selector_derivation"))
+                }
+                let derivation = match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str)) => {
+                        match lit_str.value().as_str() {
+                            "qualified" => Some(ir::SelectorDerivation::Qualified),
+                            "name_only" => Some(ir::SelectorDerivation::NameOnly),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(derivation) = derivation {
+                    selector_derivation = Some((derivation, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected either \"qualified\" or \"name_only\" for `selector_derivation` ink! config argument",
+                    ))
+                }
+            } else if arg.name.is_ident("unique_selectors") {
+                if let Some((_, ast)) = unique_selectors {
+                    return Err(duplicate_config_err(ast, arg, "unique_selectors"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    unique_selectors = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `unique_selectors` ink! config argument",
+                    ))
+                }
+            } else if arg.name.is_ident("catch_panics") {
+                if let Some((_, ast)) = catch_panics {
+                    return Err(duplicate_config_err(ast, arg, "catch_panics"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    catch_panics = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `catch_panics` ink! config argument",
+                    ))
+                }
             } else {
                 return Err(format_err_spanned!(
                     arg,
@@ -117,6 +237,12 @@ impl TryFrom<ast::AttributeArgs> for Config {
             dynamic_storage_allocator: dynamic_storage_allocator.map(|(value, _)| value),
             as_dependency: as_dependency.map(|(value, _)| value),
             env_types: env_types.map(|(value, _)| value),
+            strict_input_decoding: strict_input_decoding.map(|(value, _)| value),
+            named_inputs: named_inputs.map(|(value, _)| value),
+            named_constructor_inputs: named_constructor_inputs.map(|(value, _)| value),
+            selector_derivation: selector_derivation.map(|(value, _)| value),
+            unique_selectors: unique_selectors.map(|(value, _)| value),
+            catch_panics: catch_panics.map(|(value, _)| value),
         })
     }
 }
@@ -149,6 +275,55 @@ impl Config {
     pub fn is_compile_as_dependency_enabled(&self) -> bool {
         self.as_dependency.unwrap_or(false)
     }
+
+    /// Returns `true` if the dispatch of this ink! smart contract shall reject
+    /// inputs with trailing bytes, returns `false` otherwise.
+    ///
+    /// If nothing has been specified returns the default which is `false`.
+    pub fn is_strict_input_decoding_enabled(&self) -> bool {
+        self.strict_input_decoding.unwrap_or(false)
+    }
+
+    /// Returns `true` if the inputs of ink! messages with multiple arguments
+    /// shall be packed into generated named structs, returns `false` otherwise.
+    ///
+    /// If nothing has been specified returns the default which is `false`.
+    pub fn is_named_inputs_enabled(&self) -> bool {
+        self.named_inputs.unwrap_or(false)
+    }
+
+    /// Returns `true` if the inputs of ink! constructors with multiple arguments
+    /// shall be packed into generated named structs, returns `false` otherwise.
+    ///
+    /// If nothing has been specified returns the default which is `false`.
+    pub fn is_named_constructor_inputs_enabled(&self) -> bool {
+        self.named_constructor_inputs.unwrap_or(false)
+    }
+
+    /// Returns the strategy to derive the selectors of ink! messages and
+    /// constructors without user provided selector.
+    ///
+    /// If nothing has been specified returns the default which is
+    /// [`ir::SelectorDerivation::Qualified`].
+    pub fn selector_derivation(&self) -> ir::SelectorDerivation {
+        self.selector_derivation.unwrap_or_default()
+    }
+
+    /// Returns `true` if the selectors of ink! constructors and messages
+    /// must not overlap with each other, returns `false` otherwise.
+    ///
+    /// If nothing has been specified returns the default which is `false`.
+    pub fn is_unique_selectors_enabled(&self) -> bool {
+        self.unique_selectors.unwrap_or(false)
+    }
+
+    /// Returns `true` if panics of ink! messages shall be converted into
+    /// dispatch errors, returns `false` otherwise.
+    ///
+    /// If nothing has been specified returns the default which is `false`.
+    pub fn is_catch_panics_enabled(&self) -> bool {
+        self.catch_panics.unwrap_or(false)
+    }
 }
 
 /// The environmental types definition.
@@ -198,6 +373,12 @@ mod tests {
                 dynamic_storage_allocator: Some(true),
                 as_dependency: None,
                 env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
             }),
         )
     }
@@ -220,6 +401,12 @@ mod tests {
                 dynamic_storage_allocator: None,
                 as_dependency: Some(false),
                 env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
             }),
         )
     }
@@ -246,6 +433,12 @@ mod tests {
                 env_types: Some(EnvTypes {
                     path: syn::parse_quote! { ::my::env::Types },
                 }),
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
             }),
         )
     }
@@ -258,6 +451,176 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strict_input_decoding_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                strict_input_decoding = true
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: Some(true),
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn strict_input_decoding_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { strict_input_decoding = "invalid" },
+            Err("expected a bool literal for `strict_input_decoding` ink! config argument"),
+        )
+    }
+
+    #[test]
+    fn named_inputs_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                named_inputs = true
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: None,
+                named_inputs: Some(true),
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn named_inputs_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { named_inputs = "invalid" },
+            Err("expected a bool literal for `named_inputs` ink! config argument"),
+        )
+    }
+
+    #[test]
+    fn named_constructor_inputs_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                named_constructor_inputs = true
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: Some(true),
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn named_constructor_inputs_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { named_constructor_inputs = "invalid" },
+            Err(
+                "expected a bool literal for `named_constructor_inputs` ink! config argument",
+            ),
+        )
+    }
+
+    #[test]
+    fn selector_derivation_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                selector_derivation = "name_only"
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: Some(ir::SelectorDerivation::NameOnly),
+                unique_selectors: None,
+                catch_panics: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn selector_derivation_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { selector_derivation = "invalid" },
+            Err("expected either \"qualified\" or \"name_only\" for `selector_derivation` ink! config argument"),
+        )
+    }
+
+    #[test]
+    fn unique_selectors_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                unique_selectors = true
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: Some(true),
+                catch_panics: None,
+            }),
+        )
+    }
+
+    #[test]
+    fn unique_selectors_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { unique_selectors = "invalid" },
+            Err("expected a bool literal for `unique_selectors` ink! config argument"),
+        )
+    }
+
+    #[test]
+    fn catch_panics_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                catch_panics = true
+            },
+            Ok(Config {
+                dynamic_storage_allocator: None,
+                as_dependency: None,
+                env_types: None,
+                strict_input_decoding: None,
+                named_inputs: None,
+                named_constructor_inputs: None,
+                selector_derivation: None,
+                unique_selectors: None,
+                catch_panics: Some(true),
+            }),
+        )
+    }
+
+    #[test]
+    fn catch_panics_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { catch_panics = "invalid" },
+            Err("expected a bool literal for `catch_panics` ink! config argument"),
+        )
+    }
+
     #[test]
     fn unknown_arg_fails() {
         assert_try_from(
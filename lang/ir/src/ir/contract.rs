@@ -66,7 +66,10 @@ impl Contract {
         let config = syn::parse2::<ast::AttributeArgs>(ink_config)?;
         let module = syn::parse2::<syn::ItemMod>(ink_module)?;
         let ink_config = ir::Config::try_from(config)?;
-        let ink_module = ir::ItemMod::try_from(module)?;
+        let ink_module = ir::ItemMod::new(module, ink_config.selector_derivation())?;
+        if ink_config.is_unique_selectors_enabled() {
+            ink_module.ensure_globally_unique_selectors()?;
+        }
         Ok(Self {
             item: ink_module,
             config: ink_config,
@@ -108,6 +111,12 @@ impl Contract {
     ///                    if it was a dependency of another smart contract.
     ///                    This configuration is mainly needed for testing and
     ///                    the default is `false`.
+    /// - `selector_derivation`: Either `"qualified"` or `"name_only"` to select
+    ///                          the strategy to derive selectors of ink!
+    ///                          messages and constructors. The default is
+    ///                          `"qualified"`. See [`ir::SelectorDerivation`].
+    /// - `unique_selectors`: If `true` ink! messages must not share selectors
+    ///                       with ink! constructors. The default is `false`.
     ///
     /// Note that we might add more configuration fields in the future if
     /// necessary.
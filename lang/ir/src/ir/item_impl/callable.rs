@@ -95,6 +95,26 @@ impl<'a, C> CallableWithSelector<'a, C> {
     }
 }
 
+impl<'a, C> CallableWithSelector<'a, C>
+where
+    C: Callable,
+{
+    /// Returns the selectors of all earlier versions of the ink! callable.
+    ///
+    /// # Note
+    ///
+    /// Those selectors are kept as deprecated aliases of the composed selector
+    /// so that callers of earlier versions keep working.
+    pub fn deprecated_selectors(&self) -> Vec<ir::Selector> {
+        let version = self.callable.version().unwrap_or(1);
+        (1..version)
+            .map(|version| {
+                compose_versioned_selector(self.item_impl, self.callable, version)
+            })
+            .collect()
+    }
+}
+
 impl<'a, C> Callable for CallableWithSelector<'a, C>
 where
     C: Callable,
@@ -111,6 +131,14 @@ where
         <C as Callable>::user_provided_selector(&self.callable)
     }
 
+    fn selector_aliases(&self) -> &[ir::Selector] {
+        <C as Callable>::selector_aliases(&self.callable)
+    }
+
+    fn version(&self) -> Option<u32> {
+        <C as Callable>::version(&self.callable)
+    }
+
     fn is_payable(&self) -> bool {
         <C as Callable>::is_payable(&self.callable)
     }
@@ -154,6 +182,21 @@ pub trait Callable {
     /// Returns the selector of the ink! callable if any has been manually set.
     fn user_provided_selector(&self) -> Option<&ir::Selector>;
 
+    /// Returns the additional selectors aliasing the selector of the ink! callable.
+    ///
+    /// # Note
+    ///
+    /// Aliases are set using multiple `selector` arguments, e.g.
+    /// `#[ink(selector = "0xDEADBEEF", selector = "0xC0DECAFE")]`.
+    fn selector_aliases(&self) -> &[ir::Selector];
+
+    /// Returns the version of the ink! callable if any.
+    ///
+    /// # Note
+    ///
+    /// Versions are set using the `version` argument, e.g. `#[ink(version = 2)]`.
+    fn version(&self) -> Option<u32>;
+
     /// Returns `true` if the ink! callable is flagged as payable.
     ///
     /// # Note
@@ -195,6 +238,8 @@ pub trait Callable {
 ///   its last segment `p` (e.g. the trait's identifier) into consideration
 ///   and use it instead of `P` in the above concatenation.
 ///   In the following we refer to the resulting concatenation as `C`.
+/// - If the callable has a version `v` greater than 1 we append `@v` to `C`,
+///   e.g. `MyTrait::my_message@v2`.
 /// - Now we take the BLAKE-2 hash of `C` which results in 32 bytes of output
 ///   and take the first 4 bytes that are returned in order as the composed
 ///   selector.
@@ -284,6 +329,12 @@ pub trait Callable {
 ///
 /// All above examples work similarly for ink! constructors interchangeably.
 ///
+/// The above describes the default `"qualified"` selector derivation. With the
+/// `"name_only"` selector derivation configured for the ink! smart contract
+/// the selector is composed from the identifier alone, e.g.
+/// `BLAKE2("my_message".to_string().as_bytes())[0..4]`, regardless of any
+/// namespace or trait path.
+///
 /// ## Usage Recommendations
 ///
 /// These recommendation mainly apply to trait implementation blocks:
@@ -303,13 +354,45 @@ where
     if let Some(selector) = callable.user_provided_selector() {
         return *selector
     }
+    compose_versioned_selector(item_impl, callable, callable.version().unwrap_or(1))
+}
+
+/// Returns the composed selector of the given version of the ink! callable.
+///
+/// # Note
+///
+/// The selector of the first version is the unversioned composed selector.
+fn compose_versioned_selector<C>(
+    item_impl: &ir::ItemImpl,
+    callable: &C,
+    version: u32,
+) -> ir::Selector
+where
+    C: Callable,
+{
+    let mut joined = compose_selector_preimage(item_impl, callable);
+    if version > 1 {
+        joined.extend(format!("@v{}", version).into_bytes());
+    }
+    let hash = <blake2::Blake2b as blake2::Digest>::digest(&joined);
+    ir::Selector::new([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Returns the bytes that are hashed to compose the selector of the ink! callable.
+fn compose_selector_preimage<C>(item_impl: &ir::ItemImpl, callable: &C) -> Vec<u8>
+where
+    C: Callable,
+{
     let callable_ident = callable.ident().to_string().into_bytes();
+    if item_impl.selector_derivation() == ir::SelectorDerivation::NameOnly {
+        return callable_ident
+    }
     let namespace_bytes = item_impl
         .namespace()
         .map(|namespace| namespace.as_bytes().to_vec())
         .unwrap_or_default();
     let separator = &b"::"[..];
-    let joined = match item_impl.trait_path() {
+    match item_impl.trait_path() {
         None => {
             // Inherent implementation block:
             if namespace_bytes.is_empty() {
@@ -341,9 +424,7 @@ where
                 [namespace_bytes, path_bytes, callable_ident].join(separator)
             }
         }
-    };
-    let hash = <blake2::Blake2b as blake2::Digest>::digest(&joined);
-    ir::Selector::new([hash[0], hash[1], hash[2], hash[3]])
+    }
 }
 
 /// Ensures that common invariants of externally callable ink! entities are met.
@@ -358,6 +439,23 @@ where
 ///  - unsafe (caller provided assertions not yet stable)
 /// - Furthermore this is `true` if the externally callable is defined for a
 ///   non default ABI (e.g. `extern "C"`) or doesn't have valid visibility.
+/// Returns `true` if the given type is the `[u8; 4]` selector type.
+///
+/// # Note
+///
+/// This is the type of the first argument of the ink! message with wildcard
+/// selector and of the fallback ink! constructor.
+pub(super) fn is_selector_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Array(array) = ty {
+        if let (syn::Type::Path(elem), syn::Expr::Lit(len)) = (&*array.elem, &array.len) {
+            if let syn::Lit::Int(len) = &len.lit {
+                return elem.path.is_ident("u8") && len.base10_digits() == "4"
+            }
+        }
+    }
+    false
+}
+
 pub(super) fn ensure_callable_invariants(
     method_item: &syn::ImplItemMethod,
     kind: CallableKind,
@@ -604,5 +702,84 @@ mod tests {
             },
             b"MyTrait::my_message".to_vec(),
         );
+        assert_compose_selector::<ir::Message, _>(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl MyTrait for MyStorage {}
+            },
+            syn::parse_quote! {
+                #[ink(message, version = 1)]
+                fn my_message(&self) {}
+            },
+            b"MyTrait::my_message".to_vec(),
+        );
+        assert_compose_selector::<ir::Message, _>(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl MyTrait for MyStorage {}
+            },
+            syn::parse_quote! {
+                #[ink(message, version = 3)]
+                fn my_message(&self) {}
+            },
+            b"MyTrait::my_message@v3".to_vec(),
+        );
+    }
+
+    #[test]
+    fn deprecated_selectors_works() {
+        let item_impl = <ir::ItemImpl as TryFrom<syn::ItemImpl>>::try_from(
+            syn::parse_quote! {
+                impl MyTrait for MyStorage {
+                    #[ink(message, version = 3)]
+                    fn my_message(&self) {}
+                }
+            },
+        )
+        .unwrap();
+        let message = item_impl.iter_messages().next().unwrap();
+        let expected = [
+            b"MyTrait::my_message".to_vec(),
+            b"MyTrait::my_message@v2".to_vec(),
+        ]
+        .iter()
+        .cloned()
+        .map(|blake2_input| ExpectedSelector::from(blake2_input).expected_selector())
+        .collect::<Vec<_>>();
+        assert_eq!(message.deprecated_selectors(), expected);
+    }
+
+    #[test]
+    fn compose_selector_name_only_works() {
+        let assert_name_only = |item_impl: syn::ItemImpl, expected: ExpectedSelector| {
+            let mut item_impl =
+                <ir::ItemImpl as TryFrom<syn::ItemImpl>>::try_from(item_impl).unwrap();
+            item_impl.set_selector_derivation(ir::SelectorDerivation::NameOnly);
+            let message = <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                compose_selector(&item_impl, &message),
+                expected.expected_selector(),
+            )
+        };
+        assert_name_only(
+            syn::parse_quote! {
+                #[ink(impl, namespace = "my_namespace")]
+                impl MyTrait for MyStorage {}
+            },
+            b"my_message".to_vec().into(),
+        );
+        assert_name_only(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl ::my::full::path::MyTrait for MyStorage {}
+            },
+            b"my_message".to_vec().into(),
+        );
     }
 }
@@ -14,6 +14,7 @@
 
 use super::{
     ensure_callable_invariants,
+    is_selector_type,
     Callable,
     CallableKind,
     InputsIter,
@@ -71,6 +72,12 @@ pub struct Constructor {
     /// This overrides the computed selector, even when using a manual namespace
     /// for the parent implementation block.
     selector: Option<ir::Selector>,
+    /// The error type `E` if the ink! constructor returns `Result<Self, E>`.
+    error_type: Option<syn::Type>,
+    /// If the ink! constructor is the fallback for unknown selectors.
+    is_fallback: bool,
+    /// If the fallible ink! constructor returns its error on revert.
+    is_revert_on_error: bool,
 }
 
 impl quote::ToTokens for Constructor {
@@ -90,34 +97,65 @@ impl Constructor {
         }) if path.is_ident("Self"))
     }
 
-    /// Ensures that the return type of the ink! constructor is `Self`.
+    /// Returns the error type `E` if the given type is `Result<Self, E>`.
+    fn result_error_type(ty: &syn::Type) -> Option<&syn::Type> {
+        let path = match ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path,
+            _ => return None,
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != "Result" {
+            return None
+        }
+        let args = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => &args.args,
+            _ => return None,
+        };
+        let mut args = args.iter();
+        match (args.next(), args.next(), args.next()) {
+            (
+                Some(syn::GenericArgument::Type(ok_type)),
+                Some(syn::GenericArgument::Type(error_type)),
+                None,
+            ) if Self::type_is_self_val(ok_type) => Some(error_type),
+            _ => None,
+        }
+    }
+
+    /// Ensures that the return type of the ink! constructor is either `Self`
+    /// or `Result<Self, E>`.
     ///
-    /// Returns an appropriate error otherwise.
+    /// Returns the error type `E` for fallible ink! constructors.
     ///
     /// # Errors
     ///
-    /// If the ink! constructor does not return `Self` or is missing a return
-    /// type entirely.
+    /// If the ink! constructor does neither return `Self` nor `Result<Self, E>`
+    /// or is missing a return type entirely.
     fn ensure_valid_return_type(
         method_item: &syn::ImplItemMethod,
-    ) -> Result<(), syn::Error> {
+    ) -> Result<Option<syn::Type>, syn::Error> {
         match &method_item.sig.output {
             syn::ReturnType::Default => {
-                return Err(format_err_spanned!(
+                Err(format_err_spanned!(
                     &method_item.sig,
                     "missing return for ink! constructor",
                 ))
             }
             syn::ReturnType::Type(_, return_type) => {
-                if !Self::type_is_self_val(return_type.as_ref()) {
-                    return Err(format_err_spanned!(
-                        return_type,
-                        "ink! constructors must return Self",
-                    ))
+                if Self::type_is_self_val(return_type.as_ref()) {
+                    return Ok(None)
+                }
+                match Self::result_error_type(return_type.as_ref()) {
+                    Some(error_type) => Ok(Some(error_type.clone())),
+                    None => {
+                        Err(format_err_spanned!(
+                            return_type,
+                            "ink! constructors must return Self or Result<Self, E>",
+                        ))
+                    }
                 }
             }
         }
-        Ok(())
     }
 
     /// Ensures that the ink! constructor has no `self` receiver.
@@ -143,6 +181,41 @@ impl Constructor {
         Ok(())
     }
 
+    /// Ensures that a fallback ink! constructor has no selector and receives
+    /// the selector as its first `[u8; 4]` argument.
+    ///
+    /// # Errors
+    ///
+    /// - If the fallback ink! constructor has a selector.
+    /// - If the first argument of the ink! constructor is not of type `[u8; 4]`.
+    fn ensure_valid_fallback(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.is_fallback() {
+            return Ok(())
+        }
+        if let Some(arg) = ink_attrs
+            .args()
+            .find(|arg| matches!(arg.kind(), ir::AttributeArgKind::Selector(_)))
+        {
+            return Err(format_err!(
+                arg.span(),
+                "fallback ink! constructors cannot have a selector",
+            ))
+        }
+        match method_item.sig.inputs.iter().next() {
+            Some(syn::FnArg::Typed(pat_type)) if is_selector_type(&pat_type.ty) => Ok(()),
+            _ => {
+                Err(format_err!(
+                    method_item.sig.inputs.span(),
+                    "fallback ink! constructors must receive the selector \
+                     as their first `[u8; 4]` argument",
+                ))
+            }
+        }
+    }
+
     /// Sanitizes the attributes for the ink! constructor.
     ///
     /// Returns a tuple of ink! attributes and non-ink! attributes.
@@ -157,6 +230,8 @@ impl Constructor {
                 !matches!(kind,
                     ir::AttributeArgKind::Constructor
                     | ir::AttributeArgKind::Selector(_)
+                    | ir::AttributeArgKind::Fallback
+                    | ir::AttributeArgKind::RevertOnError
                 )
             },
         )
@@ -168,12 +243,30 @@ impl TryFrom<syn::ImplItemMethod> for Constructor {
 
     fn try_from(method_item: syn::ImplItemMethod) -> Result<Self, Self::Error> {
         ensure_callable_invariants(&method_item, CallableKind::Constructor)?;
-        Self::ensure_valid_return_type(&method_item)?;
+        let error_type = Self::ensure_valid_return_type(&method_item)?;
         Self::ensure_no_self_receiver(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
+        if ink_attrs.selectors().count() > 1 {
+            return Err(format_err!(
+                ink_attrs.span(),
+                "ink! constructors do not support selector aliases",
+            ))
+        }
+        Self::ensure_valid_fallback(&method_item, &ink_attrs)?;
         let selector = ink_attrs.selector();
+        let is_fallback = ink_attrs.is_fallback();
+        let is_revert_on_error = ink_attrs.is_revert_on_error();
+        if is_revert_on_error && error_type.is_none() {
+            return Err(format_err!(
+                method_item.sig.output.span(),
+                "only fallible ink! constructors can revert on error",
+            ))
+        }
         Ok(Constructor {
             selector,
+            error_type,
+            is_fallback,
+            is_revert_on_error,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
                 ..method_item
@@ -195,6 +288,14 @@ impl Callable for Constructor {
         self.selector.as_ref()
     }
 
+    fn selector_aliases(&self) -> &[ir::Selector] {
+        &[]
+    }
+
+    fn version(&self) -> Option<u32> {
+        None
+    }
+
     fn is_payable(&self) -> bool {
         true
     }
@@ -225,6 +326,37 @@ impl Constructor {
     pub fn attrs(&self) -> &[syn::Attribute] {
         &self.item.attrs
     }
+
+    /// Returns the error type `E` if the ink! constructor returns `Result<Self, E>`.
+    pub fn error_type(&self) -> Option<&syn::Type> {
+        self.error_type.as_ref()
+    }
+
+    /// Returns `true` if the ink! constructor returns `Result<Self, E>`.
+    ///
+    /// # Note
+    ///
+    /// Deployment fails if a fallible ink! constructor returns an error.
+    pub fn is_fallible(&self) -> bool {
+        self.error_type.is_some()
+    }
+
+    /// Returns `true` if the ink! constructor is the fallback for
+    /// instantiations with unknown selectors.
+    pub fn is_fallback(&self) -> bool {
+        self.is_fallback
+    }
+
+    /// Returns `true` if the fallible ink! constructor returns its error as
+    /// output of the reverted instantiation.
+    ///
+    /// # Note
+    ///
+    /// Otherwise the error is dropped and the instantiation fails with
+    /// `DispatchError::ConstructorFailed`.
+    pub fn is_revert_on_error(&self) -> bool {
+        self.is_revert_on_error
+    }
 }
 
 #[cfg(test)]
@@ -328,12 +460,49 @@ mod tests {
                 #[ink(constructor)]
                 fn my_constructor(input1: i32, input2: i64, input3: u32, input4: u64) -> Self {}
             },
+            // fallible
+            syn::parse_quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Result<Self, ()> {}
+            },
         ];
         for item_method in item_methods {
             assert!(<ir::Constructor as TryFrom<_>>::try_from(item_method).is_ok());
         }
     }
 
+    #[test]
+    fn error_type_works() {
+        let test_inputs: Vec<(Option<syn::Type>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor() -> Self {}
+                },
+            ),
+            (
+                Some(syn::parse_quote! { () }),
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor() -> Result<Self, ()> {}
+                },
+            ),
+            (
+                Some(syn::parse_quote! { MyError }),
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor() -> core::result::Result<Self, MyError> {}
+                },
+            ),
+        ];
+        for (expected_error_type, item_method) in test_inputs {
+            let constructor = <ir::Constructor as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(constructor.error_type(), expected_error_type.as_ref());
+            assert_eq!(constructor.is_fallible(), expected_error_type.is_some());
+        }
+    }
+
     fn assert_try_from_fails(item_method: syn::ImplItemMethod, expected_err: &str) {
         assert_eq!(
             <ir::Constructor as TryFrom<_>>::try_from(item_method)
@@ -376,11 +545,30 @@ mod tests {
             },
             syn::parse_quote! {
                 #[ink(constructor)]
-                pub fn my_constructor() -> Result<Self, ()> {}
+                pub fn my_constructor() -> Result<i32, ()> {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Result<Self> {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> u32 {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Option<Self> {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Result<Self, (), ()> {}
             },
         ];
         for item_method in item_methods {
-            assert_try_from_fails(item_method, "ink! constructors must return Self")
+            assert_try_from_fails(
+                item_method,
+                "ink! constructors must return Self or Result<Self, E>",
+            )
         }
     }
 
@@ -567,4 +755,119 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn selector_aliases_fails() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(constructor, selector = "0xDEADBEEF", selector = "0xC0DECAFE")]
+            fn my_constructor() -> Self {}
+        };
+        assert_try_from_fails(
+            item_method,
+            "ink! constructors do not support selector aliases",
+        )
+    }
+
+    #[test]
+    fn is_fallback_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor(selector: [u8; 4]) -> Self {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(constructor, fallback)]
+                    fn my_constructor(selector: [u8; 4]) -> Self {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    #[ink(fallback)]
+                    fn my_constructor(selector: [u8; 4], input: i32) -> Self {}
+                },
+            ),
+        ];
+        for (expect_fallback, item_method) in test_inputs {
+            let is_fallback = <ir::Constructor as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .is_fallback();
+            assert_eq!(is_fallback, expect_fallback);
+        }
+    }
+
+    #[test]
+    fn fallback_with_selector_fails() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(constructor, fallback, selector = "0xDEADBEEF")]
+            fn my_constructor(selector: [u8; 4]) -> Self {}
+        };
+        assert_try_from_fails(
+            item_method,
+            "fallback ink! constructors cannot have a selector",
+        )
+    }
+
+    #[test]
+    fn fallback_without_selector_argument_fails() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(constructor, fallback)]
+                fn my_constructor() -> Self {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor, fallback)]
+                fn my_constructor(input: i32) -> Self {}
+            },
+        ];
+        for item_method in item_methods {
+            assert_try_from_fails(
+                item_method,
+                "fallback ink! constructors must receive the selector \
+                 as their first `[u8; 4]` argument",
+            )
+        }
+    }
+
+    #[test]
+    fn is_revert_on_error_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor() -> Result<Self, u8> {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(constructor, revert_on_error)]
+                    fn my_constructor() -> Result<Self, u8> {}
+                },
+            ),
+        ];
+        for (expect_revert_on_error, item_method) in test_inputs {
+            let constructor =
+                <ir::Constructor as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(constructor.is_revert_on_error(), expect_revert_on_error);
+        }
+    }
+
+    #[test]
+    fn infallible_revert_on_error_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(constructor, revert_on_error)]
+                fn my_constructor() -> Self {}
+            },
+            "only fallible ink! constructors can revert on error",
+        )
+    }
 }
@@ -14,6 +14,7 @@
 
 use super::{
     ensure_callable_invariants,
+    is_selector_type,
     Callable,
     CallableKind,
     InputsIter,
@@ -97,6 +98,14 @@ pub struct Message {
     pub(super) item: syn::ImplItemMethod,
     /// If the ink! message can receive funds.
     is_payable: bool,
+    /// If the ink! message rejects reentrant calls.
+    is_no_reentrancy: bool,
+    /// If the ink! message receives all calls with unknown selectors.
+    is_wildcard: bool,
+    /// The minimum balance required from callers of the ink! message if any.
+    min_caller_balance: Option<ir::MinCallerBalance>,
+    /// The version of the ink! message if any.
+    version: Option<u32>,
     /// An optional user provided selector.
     ///
     /// # Note
@@ -104,6 +113,8 @@ pub struct Message {
     /// This overrides the computed selector, even when using a manual namespace
     /// for the parent implementation block.
     selector: Option<ir::Selector>,
+    /// Additional user provided selectors aliasing the selector of the message.
+    selector_aliases: Vec<ir::Selector>,
 }
 
 impl quote::ToTokens for Message {
@@ -146,6 +157,120 @@ impl Message {
         Ok(())
     }
 
+    /// Ensures that all user provided selectors of the ink! message are distinct.
+    ///
+    /// # Errors
+    ///
+    /// If the same selector has been provided more than once.
+    fn ensure_distinct_selectors(ink_attrs: &ir::InkAttribute) -> Result<(), syn::Error> {
+        use crate::error::ExtError as _;
+        use std::collections::HashMap;
+        let mut seen = <HashMap<ir::Selector, &ir::AttributeArg>>::new();
+        for arg in ink_attrs.args() {
+            if let ir::AttributeArgKind::Selector(selector) = arg.kind() {
+                if let Some(first) = seen.insert(*selector, arg) {
+                    return Err(format_err!(
+                        arg.span(),
+                        "encountered duplicate ink! message selector",
+                    )
+                    .into_combine(format_err!(first.span(), "first equal selector here")))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! message with a wildcard selector has no other
+    /// selectors and receives the selector as its first `[u8; 4]` argument.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! message has a wildcard selector as well as other selectors.
+    /// - If the first argument of the ink! message is not of type `[u8; 4]`.
+    fn ensure_valid_wildcard(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.has_wildcard_selector() {
+            return Ok(())
+        }
+        if let Some(arg) = ink_attrs
+            .args()
+            .find(|arg| matches!(arg.kind(), ir::AttributeArgKind::Selector(_)))
+        {
+            return Err(format_err!(
+                arg.span(),
+                "ink! messages with a wildcard selector cannot have other selectors",
+            ))
+        }
+        match method_item.sig.inputs.iter().nth(1) {
+            Some(syn::FnArg::Typed(pat_type)) if is_selector_type(&pat_type.ty) => Ok(()),
+            _ => {
+                Err(format_err!(
+                    method_item.sig.inputs.span(),
+                    "ink! messages with a wildcard selector must receive the \
+                     selector as their first `[u8; 4]` argument",
+                ))
+            }
+        }
+    }
+
+    /// Ensures that a versioned ink! message derives its selectors.
+    ///
+    /// # Errors
+    ///
+    /// If the versioned ink! message has a manual or wildcard selector.
+    fn ensure_valid_version(ink_attrs: &ir::InkAttribute) -> Result<(), syn::Error> {
+        if ink_attrs.version().is_none() {
+            return Ok(())
+        }
+        if let Some(arg) = ink_attrs.args().find(|arg| {
+            matches!(
+                arg.kind(),
+                ir::AttributeArgKind::Selector(_) | ir::AttributeArgKind::WildcardSelector
+            )
+        }) {
+            return Err(format_err!(
+                arg.span(),
+                "versioned ink! messages cannot have a manual selector",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! message returning a reference borrows it from
+    /// its `&self` receiver.
+    ///
+    /// # Errors
+    ///
+    /// - If the ink! message returns a mutable reference.
+    /// - If the ink! message returns a reference but has a `&mut self` receiver.
+    fn ensure_valid_ref_output(
+        method_item: &syn::ImplItemMethod,
+    ) -> Result<(), syn::Error> {
+        let output = match &method_item.sig.output {
+            syn::ReturnType::Type(_, output) => output,
+            syn::ReturnType::Default => return Ok(()),
+        };
+        if let syn::Type::Reference(type_reference) = &**output {
+            if type_reference.mutability.is_some() {
+                return Err(format_err!(
+                    output.span(),
+                    "ink! messages cannot return mutable references",
+                ))
+            }
+            if let Some(syn::FnArg::Receiver(receiver)) = method_item.sig.inputs.first() {
+                if receiver.mutability.is_some() {
+                    return Err(format_err!(
+                        output.span(),
+                        "only ink! messages with `&self` receiver can return references",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Sanitizes the attributes for the ink! message.
     ///
     /// Returns a tuple of ink! attributes and non-ink! attributes.
@@ -160,7 +285,11 @@ impl Message {
                 !matches!(kind,
                     ir::AttributeArgKind::Message
                     | ir::AttributeArgKind::Payable
+                    | ir::AttributeArgKind::NoReentrancy
                     | ir::AttributeArgKind::Selector(_)
+                    | ir::AttributeArgKind::WildcardSelector
+                    | ir::AttributeArgKind::MinCallerBalance(_)
+                    | ir::AttributeArgKind::Version(_)
                 )
             },
         )
@@ -174,16 +303,37 @@ impl TryFrom<syn::ImplItemMethod> for Message {
         ensure_callable_invariants(&method_item, CallableKind::Message)?;
         Self::ensure_receiver_is_self_ref(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
+        Self::ensure_distinct_selectors(&ink_attrs)?;
+        Self::ensure_valid_wildcard(&method_item, &ink_attrs)?;
+        Self::ensure_valid_version(&ink_attrs)?;
+        Self::ensure_valid_ref_output(&method_item)?;
         let is_payable = ink_attrs.is_payable();
+        let is_no_reentrancy = ink_attrs.is_no_reentrancy();
+        let is_wildcard = ink_attrs.has_wildcard_selector();
+        let min_caller_balance = ink_attrs.min_caller_balance();
+        let version = ink_attrs.version();
         let selector = ink_attrs.selector();
-        Ok(Self {
+        let selector_aliases = ink_attrs.selectors().skip(1).collect();
+        let message = Self {
             is_payable,
+            is_no_reentrancy,
+            is_wildcard,
+            min_caller_balance,
+            version,
             selector,
+            selector_aliases,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
                 ..method_item
             },
-        })
+        };
+        if message.is_no_reentrancy && !message.receiver().is_ref_mut() {
+            return Err(format_err!(
+                message.item.sig.inputs.span(),
+                "only ink! messages with `&mut self` receiver can reject reentrant calls",
+            ))
+        }
+        Ok(message)
     }
 }
 
@@ -200,6 +350,14 @@ impl Callable for Message {
         self.selector.as_ref()
     }
 
+    fn selector_aliases(&self) -> &[ir::Selector] {
+        &self.selector_aliases
+    }
+
+    fn version(&self) -> Option<u32> {
+        self.version
+    }
+
     fn is_payable(&self) -> bool {
         self.is_payable
     }
@@ -253,6 +411,59 @@ impl Message {
             syn::ReturnType::Type(_, return_type) => Some(return_type),
         }
     }
+
+    /// Returns `true` if the ink! message returns a reference into its storage.
+    ///
+    /// # Note
+    ///
+    /// The referenced value is encoded directly from the contract storage
+    /// into the return buffer instead of being cloned beforehand.
+    pub fn returns_ref(&self) -> bool {
+        matches!(self.output(), Some(syn::Type::Reference(_)))
+    }
+
+    /// Returns the type of the value returned to the caller of the ink! message if any.
+    ///
+    /// # Note
+    ///
+    /// This is the referenced type for ink! messages returning a reference and
+    /// the return type of the ink! message otherwise.
+    pub fn encoded_output(&self) -> Option<&syn::Type> {
+        match self.output() {
+            Some(syn::Type::Reference(type_reference)) => Some(&type_reference.elem),
+            output => output,
+        }
+    }
+
+    /// Returns `true` if the ink! message itself rejects reentrant calls.
+    ///
+    /// # Note
+    ///
+    /// Reentrant calls are also rejected if the parent ink! implementation
+    /// block has been annotated with `#[ink(no_reentrancy)]`.
+    pub fn is_no_reentrancy(&self) -> bool {
+        self.is_no_reentrancy
+    }
+
+    /// Returns `true` if the ink! message has a wildcard selector.
+    ///
+    /// # Note
+    ///
+    /// All calls with selectors that do not match any other ink! message
+    /// are dispatched to such a message.
+    pub fn is_wildcard(&self) -> bool {
+        self.is_wildcard
+    }
+
+    /// Returns the minimum balance required from callers of the ink! message if any.
+    ///
+    /// # Note
+    ///
+    /// Calls from callers with a lower balance are rejected before the
+    /// ink! message is executed.
+    pub fn min_caller_balance(&self) -> Option<&ir::MinCallerBalance> {
+        self.min_caller_balance.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -296,6 +507,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn returns_ref_works() {
+        let test_inputs: Vec<(bool, Option<syn::Type>, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                Some(syn::parse_quote! { Vec<u8> }),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> Vec<u8> {}
+                },
+            ),
+            (
+                true,
+                Some(syn::parse_quote! { Vec<u8> }),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> &Vec<u8> {}
+                },
+            ),
+            (
+                false,
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+        ];
+        for (expected_returns_ref, expected_output, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.returns_ref(), expected_returns_ref);
+            assert_eq!(message.encoded_output().cloned(), expected_output);
+        }
+    }
+
+    #[test]
+    fn invalid_ref_output_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&mut self) -> &Vec<u8> {}
+            },
+            "only ink! messages with `&self` receiver can return references",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self) -> &mut Vec<u8> {}
+            },
+            "ink! messages cannot return mutable references",
+        );
+    }
+
     #[test]
     fn inputs_works() {
         macro_rules! expected_inputs {
@@ -392,6 +656,256 @@ mod tests {
         }
     }
 
+    #[test]
+    fn is_no_reentrancy_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, no_reentrancy)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(no_reentrancy)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expect_no_reentrancy, item_method) in test_inputs {
+            let is_no_reentrancy = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .is_no_reentrancy();
+            assert_eq!(is_no_reentrancy, expect_no_reentrancy);
+        }
+    }
+
+    #[test]
+    fn no_reentrancy_on_ref_receiver_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, no_reentrancy)]
+                fn my_message(&self) {}
+            },
+            "only ink! messages with `&mut self` receiver can reject reentrant calls",
+        )
+    }
+
+    #[test]
+    fn is_wildcard_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self, selector: [u8; 4]) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, selector = "_")]
+                    fn my_message(&self, selector: [u8; 4]) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, selector = "_")]
+                    fn my_message(&mut self, selector: [u8; 4], value: i32) {}
+                },
+            ),
+        ];
+        for (expect_wildcard, item_method) in test_inputs {
+            let is_wildcard = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .is_wildcard();
+            assert_eq!(is_wildcard, expect_wildcard);
+        }
+    }
+
+    #[test]
+    fn min_caller_balance_works() {
+        let test_inputs: Vec<(Option<&str>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                Some("1000"),
+                syn::parse_quote! {
+                    #[ink(message, min_caller_balance = 1000)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                Some("MIN_BALANCE"),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(min_caller_balance = "MIN_BALANCE")]
+                    fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            let min_caller_balance = message
+                .min_caller_balance()
+                .map(|min_caller_balance| min_caller_balance.as_str());
+            assert_eq!(min_caller_balance, expected);
+        }
+    }
+
+    #[test]
+    fn version_works() {
+        let test_inputs: Vec<(Option<u32>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                Some(2),
+                syn::parse_quote! {
+                    #[ink(message, version = 2)]
+                    fn my_message(&self) {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let version = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .version();
+            assert_eq!(version, expected);
+        }
+    }
+
+    #[test]
+    fn versioned_with_selector_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, version = 2, selector = "0xDEADBEEF")]
+                fn my_message(&self) {}
+            },
+            "versioned ink! messages cannot have a manual selector",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, version = 2, selector = "_")]
+                fn my_message(&self, selector: [u8; 4]) {}
+            },
+            "versioned ink! messages cannot have a manual selector",
+        );
+    }
+
+    #[test]
+    fn wildcard_with_other_selector_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, selector = "_", selector = "0xDEADBEEF")]
+                fn my_message(&self, selector: [u8; 4]) {}
+            },
+            "ink! messages with a wildcard selector cannot have other selectors",
+        )
+    }
+
+    #[test]
+    fn wildcard_without_selector_argument_fails() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(message, selector = "_")]
+                fn my_message(&self) {}
+            },
+            syn::parse_quote! {
+                #[ink(message, selector = "_")]
+                fn my_message(&self, selector: [u8; 32]) {}
+            },
+            syn::parse_quote! {
+                #[ink(message, selector = "_")]
+                fn my_message(&self, value: i32, selector: [u8; 4]) {}
+            },
+        ];
+        for item_method in item_methods {
+            assert_try_from_fails(
+                item_method,
+                "ink! messages with a wildcard selector must receive the \
+                 selector as their first `[u8; 4]` argument",
+            )
+        }
+    }
+
+    #[test]
+    fn selector_aliases_works() {
+        let test_inputs: Vec<(Vec<ir::Selector>, syn::ImplItemMethod)> = vec![
+            // No user provided selector.
+            (
+                vec![],
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            // Single user provided selector.
+            (
+                vec![],
+                syn::parse_quote! {
+                    #[ink(message, selector = "0xDEADBEEF")]
+                    fn my_message(&self) {}
+                },
+            ),
+            // Normalized ink! attribute.
+            (
+                vec![ir::Selector::new([0xC0, 0xDE, 0xCA, 0xFE])],
+                syn::parse_quote! {
+                    #[ink(message, selector = "0xDEADBEEF", selector = "0xC0DECAFE")]
+                    fn my_message(&self) {}
+                },
+            ),
+            // Different ink! attributes.
+            (
+                vec![
+                    ir::Selector::new([0xC0, 0xDE, 0xCA, 0xFE]),
+                    ir::Selector::new([0x12, 0x34, 0x56, 0x78]),
+                ],
+                syn::parse_quote! {
+                    #[ink(message, selector = "0xDEADBEEF")]
+                    #[ink(selector = "0xC0DECAFE")]
+                    #[ink(selector = "0x12345678")]
+                    fn my_message(&self) {}
+                },
+            ),
+        ];
+        for (expected_aliases, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.selector_aliases(), &expected_aliases[..]);
+        }
+    }
+
+    #[test]
+    fn duplicate_selector_alias_fails() {
+        let item_method: syn::ImplItemMethod = syn::parse_quote! {
+            #[ink(message, selector = "0xDEADBEEF")]
+            #[ink(selector = "0xdeadbeef")]
+            fn my_message(&self) {}
+        };
+        assert_try_from_fails(item_method, "encountered duplicate ink! message selector")
+    }
+
     #[test]
     fn receiver_works() {
         let test_inputs: Vec<(Receiver, syn::ImplItemMethod)> = vec![
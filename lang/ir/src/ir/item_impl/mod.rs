@@ -32,7 +32,10 @@ mod message;
 #[cfg(test)]
 mod tests;
 
-use self::callable::ensure_callable_invariants;
+use self::callable::{
+    ensure_callable_invariants,
+    is_selector_type,
+};
 pub use self::{
     callable::{
         Callable,
@@ -80,6 +83,16 @@ pub struct ItemImpl {
     /// names. Generally can be used to change computation of message and
     /// constructor selectors of the implementation block.
     namespace: Option<ir::Namespace>,
+    /// If all `&mut self` ink! messages of the implementation block reject
+    /// reentrant calls.
+    is_no_reentrancy: bool,
+    /// The strategy to derive the selectors of the ink! messages and
+    /// constructors of the implementation block.
+    ///
+    /// # Note
+    ///
+    /// This is configured for the whole ink! smart contract.
+    selector_derivation: ir::SelectorDerivation,
 }
 
 impl quote::ToTokens for ItemImpl {
@@ -278,6 +291,14 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                         "message",
                         is_trait_impl,
                     )?;
+                    // Trait messages return their value via associated output
+                    // types which cannot borrow from the contract storage.
+                    if is_trait_impl && message.returns_ref() {
+                        return Err(format_err!(
+                            message.item.sig.output.span(),
+                            "ink! messages in trait impl blocks cannot return references",
+                        ))
+                    }
                 }
                 ir::ImplItem::Constructor(constructor) => {
                     ensure_valid_visibility(
@@ -292,15 +313,22 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
         }
         let (ink_attrs, other_attrs) = ir::partition_attributes(item_impl.attrs)?;
         let mut namespace = None;
+        let mut is_no_reentrancy = false;
         if !ink_attrs.is_empty() {
             let normalized =
                 ir::InkAttribute::from_expanded(ink_attrs).map_err(|err| {
                     err.into_combine(format_err!(impl_block_span, "at this invokation",))
                 })?;
             normalized.ensure_no_conflicts(|arg| {
-                !matches!(arg.kind(), ir::AttributeArgKind::Implementation | ir::AttributeArgKind::Namespace(_))
+                !matches!(
+                    arg.kind(),
+                    ir::AttributeArgKind::Implementation
+                        | ir::AttributeArgKind::Namespace(_)
+                        | ir::AttributeArgKind::NoReentrancy
+                )
             })?;
             namespace = normalized.namespace();
+            is_no_reentrancy = normalized.is_no_reentrancy();
         }
         Ok(Self {
             attrs: other_attrs,
@@ -313,6 +341,8 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
             brace_token: item_impl.brace_token,
             items: impl_items,
             namespace,
+            is_no_reentrancy,
+            selector_derivation: Default::default(),
         })
     }
 }
@@ -350,6 +380,27 @@ impl ItemImpl {
         self.namespace.as_ref()
     }
 
+    /// Returns the strategy to derive the selectors of the ink! messages and
+    /// constructors of the implementation block.
+    pub fn selector_derivation(&self) -> ir::SelectorDerivation {
+        self.selector_derivation
+    }
+
+    /// Sets the strategy to derive the selectors of the ink! messages and
+    /// constructors of the implementation block.
+    pub(crate) fn set_selector_derivation(
+        &mut self,
+        selector_derivation: ir::SelectorDerivation,
+    ) {
+        self.selector_derivation = selector_derivation;
+    }
+
+    /// Returns `true` if the `&mut self` ink! messages of the implementation
+    /// block reject reentrant calls.
+    pub fn is_no_reentrancy(&self) -> bool {
+        self.is_no_reentrancy
+    }
+
     /// Returns an iterator yielding the ink! messages of the implementation block.
     pub fn iter_messages(&self) -> IterMessages {
         IterMessages::new(self)
@@ -207,6 +207,19 @@ fn visibility_fails() {
     );
 }
 
+#[test]
+fn trait_message_returning_ref_fails() {
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl MyTrait for MyStorage {
+                #[ink(message)]
+                fn my_message(&self) -> &Vec<u8> {}
+            }
+        },
+        "ink! messages in trait impl blocks cannot return references",
+    );
+}
+
 #[test]
 fn try_from_works() {
     let item_impls: Vec<syn::ItemImpl> = vec![
@@ -254,3 +267,17 @@ fn namespace_works() {
         ))
     )
 }
+
+#[test]
+fn no_reentrancy_works() {
+    let impl_block: ir::ItemImpl =
+        <ir::ItemImpl as TryFrom<syn::ItemImpl>>::try_from(syn::parse_quote! {
+            #[ink(no_reentrancy)]
+            impl MyStorage {
+                #[ink(message)]
+                pub fn my_message(&mut self) {}
+            }
+        })
+        .unwrap();
+    assert!(impl_block.is_no_reentrancy());
+}
@@ -118,28 +118,6 @@ impl ItemMod {
         Ok(())
     }
 
-    /// Ensures that the given slice of items contains at least one ink! message.
-    fn ensure_contains_message(
-        module_span: Span,
-        items: &[ir::Item],
-    ) -> Result<(), syn::Error> {
-        let found_message = items
-            .iter()
-            .filter_map(|item| {
-                match item {
-                    ir::Item::Ink(ir::InkItem::ImplBlock(item_impl)) => {
-                        Some(item_impl.iter_messages())
-                    }
-                    _ => None,
-                }
-            })
-            .any(|mut messages| messages.next().is_some());
-        if !found_message {
-            return Err(format_err!(module_span, "missing ink! message"))
-        }
-        Ok(())
-    }
-
     /// Ensures that the given slice of items contains at least one ink! constructor.
     fn ensure_contains_constructor(
         module_span: Span,
@@ -177,6 +155,7 @@ impl ItemMod {
             .filter_map(ir::Item::map_ink_item)
             .filter_map(ir::InkItem::filter_map_impl_block)
         {
+            use ir::Callable as _;
             use std::collections::hash_map::Entry;
             /// Kind is either `"message"` or `"constructor"`.
             fn compose_error(
@@ -202,18 +181,25 @@ impl ItemMod {
                 ))
             }
             for message in item_impl.iter_messages() {
-                let selector = message.composed_selector();
-                match messages.entry(selector) {
-                    Entry::Occupied(overlap) => {
-                        return Err(compose_error(
-                            overlap.get().span(),
-                            message.callable().span(),
-                            selector,
-                            "message",
-                        ))
-                    }
-                    Entry::Vacant(vacant) => {
-                        vacant.insert(message.callable());
+                // Selector aliases and deprecated selectors are registered for
+                // their message just like the composed selector so that they may
+                // not overlap with the selectors of any other ink! message.
+                let selectors = core::iter::once(message.composed_selector())
+                    .chain(message.selector_aliases().iter().copied())
+                    .chain(message.deprecated_selectors());
+                for selector in selectors {
+                    match messages.entry(selector) {
+                        Entry::Occupied(overlap) => {
+                            return Err(compose_error(
+                                overlap.get().span(),
+                                message.callable().span(),
+                                selector,
+                                "message",
+                            ))
+                        }
+                        Entry::Vacant(vacant) => {
+                            vacant.insert(message.callable());
+                        }
                     }
                 }
             }
@@ -237,6 +223,129 @@ impl ItemMod {
         Ok(())
     }
 
+    /// Ensures that at most one ink! message has a wildcard selector.
+    fn ensure_single_wildcard_message(items: &[ir::Item]) -> Result<(), syn::Error> {
+        use crate::error::ExtError as _;
+        let mut wildcards = items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(ir::ItemImpl::iter_messages)
+            .map(|message| message.callable())
+            .filter(|message| message.is_wildcard());
+        if let (Some(first), Some(second)) = (wildcards.next(), wildcards.next()) {
+            return Err(format_err!(
+                second.span(),
+                "encountered multiple ink! messages with wildcard selector",
+            )
+            .into_combine(format_err!(
+                first.span(),
+                "first ink! message with wildcard selector here",
+            )))
+        }
+        Ok(())
+    }
+
+    /// Ensures that no ink! message selector overlaps with the selector of
+    /// any ink! constructor.
+    ///
+    /// # Note
+    ///
+    /// This check is stricter than [`ItemMod::ensure_no_overlapping_selectors`]
+    /// and only applied if the ink! smart contract opted into it via the
+    /// `unique_selectors` ink! config argument.
+    pub(crate) fn ensure_globally_unique_selectors(&self) -> Result<(), syn::Error> {
+        use crate::error::ExtError as _;
+        use ir::Callable as _;
+        let constructors = self
+            .impls()
+            .flat_map(ir::ItemImpl::iter_constructors)
+            .map(|constructor| (constructor.composed_selector(), constructor.callable()))
+            .collect::<HashMap<ir::Selector, &ir::Constructor>>();
+        for message in self.impls().flat_map(ir::ItemImpl::iter_messages) {
+            let selectors = core::iter::once(message.composed_selector())
+                .chain(message.selector_aliases().iter().copied())
+                .chain(message.deprecated_selectors());
+            for selector in selectors {
+                if let Some(constructor) = constructors.get(&selector) {
+                    return Err(format_err!(
+                        message.callable().span(),
+                        "encountered ink! constructor and message with overlapping \
+                         selectors (= {:02X?})\n\
+                         hint: selectors must be unique across ink! constructors and \
+                         messages since `unique_selectors` is enabled.",
+                        selector.as_bytes(),
+                    )
+                    .into_combine(format_err!(
+                        constructor.span(),
+                        "first ink! constructor with overlapping selector here",
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures that at most one ink! constructor is the fallback constructor.
+    fn ensure_single_fallback_constructor(items: &[ir::Item]) -> Result<(), syn::Error> {
+        use crate::error::ExtError as _;
+        let mut fallbacks = items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(ir::ItemImpl::iter_constructors)
+            .map(|constructor| constructor.callable())
+            .filter(|constructor| constructor.is_fallback());
+        if let (Some(first), Some(second)) = (fallbacks.next(), fallbacks.next()) {
+            return Err(format_err!(
+                second.span(),
+                "encountered multiple fallback ink! constructors",
+            )
+            .into_combine(format_err!(
+                first.span(),
+                "first fallback ink! constructor here",
+            )))
+        }
+        Ok(())
+    }
+
+    /// Ensures that an ink! message with wildcard selector and a fallback
+    /// ink! constructor are not both present.
+    ///
+    /// # Note
+    ///
+    /// Both receive the selectors that are unknown to the dispatch so that
+    /// contracts having both would route unknown selectors ambiguously.
+    fn ensure_no_wildcard_and_fallback(items: &[ir::Item]) -> Result<(), syn::Error> {
+        use crate::error::ExtError as _;
+        let impls = || {
+            items
+                .iter()
+                .filter_map(ir::Item::map_ink_item)
+                .filter_map(ir::InkItem::filter_map_impl_block)
+        };
+        let wildcard = impls()
+            .flat_map(ir::ItemImpl::iter_messages)
+            .map(|message| message.callable())
+            .find(|message| message.is_wildcard());
+        let fallback = impls()
+            .flat_map(ir::ItemImpl::iter_constructors)
+            .map(|constructor| constructor.callable())
+            .find(|constructor| constructor.is_fallback());
+        if let (Some(wildcard), Some(fallback)) = (wildcard, fallback) {
+            return Err(format_err!(
+                wildcard.span(),
+                "encountered ink! message with wildcard selector and fallback \
+                 ink! constructor in the same ink! smart contract",
+            )
+            .into_combine(format_err!(
+                fallback.span(),
+                "fallback ink! constructor here",
+            )))
+        }
+        Ok(())
+    }
+
     /// Returns `Ok` if there are no occurrences of identifiers starting with `__ink_`.
     ///
     /// # Errors
@@ -284,6 +393,21 @@ impl TryFrom<syn::ItemMod> for ItemMod {
     type Error = syn::Error;
 
     fn try_from(module: syn::ItemMod) -> Result<Self, Self::Error> {
+        Self::new(module, Default::default())
+    }
+}
+
+impl ItemMod {
+    /// Creates a new ink! module from the given Rust module deriving the
+    /// selectors of its ink! messages and constructors with the given strategy.
+    ///
+    /// # Errors
+    ///
+    /// If the Rust module is not a valid ink! module.
+    pub fn new(
+        module: syn::ItemMod,
+        selector_derivation: ir::SelectorDerivation,
+    ) -> Result<Self, syn::Error> {
         let module_span = module.span();
         Self::ensure_no_ink_identifiers(&module)?;
         let (brace, items) = match module.content {
@@ -309,14 +433,23 @@ impl TryFrom<syn::ItemMod> for ItemMod {
             }
             return Err(error)
         }
-        let items = items
+        let mut items = items
             .into_iter()
             .map(<ir::Item as TryFrom<syn::Item>>::try_from)
             .collect::<Result<Vec<_>, syn::Error>>()?;
+        // The selectors have to be derived with the configured strategy
+        // before checking them for overlaps.
+        for item in &mut items {
+            if let ir::Item::Ink(ir::InkItem::ImplBlock(item_impl)) = item {
+                item_impl.set_selector_derivation(selector_derivation);
+            }
+        }
         Self::ensure_storage_struct_quantity(module_span, &items)?;
-        Self::ensure_contains_message(module_span, &items)?;
         Self::ensure_contains_constructor(module_span, &items)?;
         Self::ensure_no_overlapping_selectors(&items)?;
+        Self::ensure_single_wildcard_message(&items)?;
+        Self::ensure_single_fallback_constructor(&items)?;
+        Self::ensure_no_wildcard_and_fallback(&items)?;
         Ok(Self {
             attrs: other_attrs,
             vis: module.vis,
@@ -693,9 +826,10 @@ mod tests {
     }
 
     #[test]
-    fn missing_message_fails() {
-        assert_fail(
-            syn::parse_quote! {
+    fn missing_message_works() {
+        // Contracts without ink! messages reject all calls.
+        assert!(
+            <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(syn::parse_quote! {
                 mod my_module {
                     #[ink(storage)]
                     pub struct MyStorage {}
@@ -705,9 +839,9 @@ mod tests {
                         pub fn my_constructor() -> Self {}
                     }
                 }
-            },
-            "missing ink! message",
-        )
+            })
+            .is_ok()
+        );
     }
 
     #[test]
@@ -769,6 +903,186 @@ mod tests {
         );
     }
 
+    #[test]
+    fn selector_aliases_works() {
+        assert!(
+            <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, selector = "0xDEADBEEF", selector = "0xC0DECAFE")]
+                        pub fn my_message_1(&self) {}
+
+                        #[ink(message, selector = "0x12345678")]
+                        pub fn my_message_2(&self) {}
+                    }
+                }
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn overlapping_selector_aliases_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, selector = "0xDEADBEEF")]
+                        pub fn my_message_1(&self) {}
+
+                        #[ink(message, selector = "0xC0DECAFE", selector = "0xDEADBEEF")]
+                        pub fn my_message_2(&self) {}
+                    }
+                }
+            },
+            "encountered ink! messages with overlapping selectors (= [DE, AD, BE, EF])\n\
+                hint: use #[ink(selector = \"0x...\")] on the callable or \
+                #[ink(namespace = \"...\")] on the implementation block to \
+                disambiguate overlapping selectors.",
+        );
+    }
+
+    #[test]
+    fn overlapping_deprecated_selector_fails() {
+        let hash = <blake2::Blake2b as blake2::Digest>::digest(b"my_message_1");
+        let selector = format!(
+            "0x{:02X}{:02X}{:02X}{:02X}",
+            hash[0], hash[1], hash[2], hash[3]
+        );
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, version = 2)]
+                        pub fn my_message_1(&self) {}
+
+                        #[ink(message, selector = #selector)]
+                        pub fn my_message_2(&self) {}
+                    }
+                }
+            },
+            &format!(
+                "encountered ink! messages with overlapping selectors (= {:02X?})\n\
+                 hint: use #[ink(selector = \"0x...\")] on the callable or \
+                 #[ink(namespace = \"...\")] on the implementation block to \
+                 disambiguate overlapping selectors.",
+                &hash[0..4],
+            ),
+        );
+    }
+
+    #[test]
+    fn multiple_wildcard_messages_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, selector = "_")]
+                        pub fn my_message_1(&self, selector: [u8; 4]) {}
+
+                        #[ink(message, selector = "_")]
+                        pub fn my_message_2(&self, selector: [u8; 4]) {}
+                    }
+                }
+            },
+            "encountered multiple ink! messages with wildcard selector",
+        );
+    }
+
+    #[test]
+    fn multiple_fallback_constructors_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor, fallback)]
+                        pub fn my_constructor_1(selector: [u8; 4]) -> Self {}
+
+                        #[ink(constructor, fallback)]
+                        pub fn my_constructor_2(selector: [u8; 4]) -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&self) {}
+                    }
+                }
+            },
+            "encountered multiple fallback ink! constructors",
+        );
+    }
+
+    #[test]
+    fn fallback_constructor_and_wildcard_message_in_other_impl_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor, fallback)]
+                        pub fn my_constructor(selector: [u8; 4]) -> Self {}
+                    }
+
+                    impl MyStorage {
+                        #[ink(message, selector = "_")]
+                        pub fn my_message(&self, selector: [u8; 4]) {}
+                    }
+                }
+            },
+            "encountered ink! message with wildcard selector and fallback \
+             ink! constructor in the same ink! smart contract",
+        );
+    }
+
+    #[test]
+    fn wildcard_message_and_fallback_constructor_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor, fallback)]
+                        pub fn my_constructor(selector: [u8; 4]) -> Self {}
+
+                        #[ink(message, selector = "_")]
+                        pub fn my_message(&self, selector: [u8; 4]) {}
+                    }
+                }
+            },
+            "encountered ink! message with wildcard selector and fallback \
+             ink! constructor in the same ink! smart contract",
+        );
+    }
+
     #[test]
     fn overlapping_constructors_fails() {
         assert_fail(
@@ -827,6 +1141,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn same_message_in_different_traits_works() {
+        let item_mod = <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(syn::parse_quote! {
+            mod my_module {
+                #[ink(storage)]
+                pub struct MyStorage {}
+
+                impl MyStorage {
+                    #[ink(constructor)]
+                    pub fn my_constructor() -> Self {}
+                }
+
+                impl GetterA for MyStorage {
+                    #[ink(message)]
+                    fn get(&self) {}
+                }
+
+                impl GetterB for MyStorage {
+                    #[ink(message)]
+                    fn get(&self) {}
+                }
+            }
+        })
+        .unwrap();
+        let selectors = item_mod
+            .impls()
+            .flat_map(ir::ItemImpl::iter_messages)
+            .map(|message| message.composed_selector())
+            .collect::<Vec<_>>();
+        let expected = |path: &str| {
+            let hash = <blake2::Blake2b as blake2::Digest>::digest(path.as_bytes());
+            ir::Selector::new([hash[0], hash[1], hash[2], hash[3]])
+        };
+        assert_eq!(selectors, vec![expected("GetterA::get"), expected("GetterB::get")]);
+    }
+
     #[test]
     fn namespaced_overlapping_trait_impls_works() {
         assert!(
@@ -874,4 +1224,53 @@ mod tests {
             .is_ok()
         );
     }
+
+    #[test]
+    fn globally_unique_selectors_fails() {
+        let item_mod = <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(syn::parse_quote! {
+            mod my_module {
+                #[ink(storage)]
+                pub struct MyStorage {}
+
+                impl MyStorage {
+                    #[ink(constructor, selector = "0xDEADBEEF")]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message, selector = "0xDEADBEEF")]
+                    pub fn my_message(&self) {}
+                }
+            }
+        })
+        .unwrap();
+        assert_eq!(
+            item_mod
+                .ensure_globally_unique_selectors()
+                .map_err(|err| err.to_string()),
+            Err("encountered ink! constructor and message with overlapping selectors \
+                 (= [DE, AD, BE, EF])\n\
+                 hint: selectors must be unique across ink! constructors and \
+                 messages since `unique_selectors` is enabled."
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn globally_unique_selectors_works() {
+        let item_mod = <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(syn::parse_quote! {
+            mod my_module {
+                #[ink(storage)]
+                pub struct MyStorage {}
+
+                impl MyStorage {
+                    #[ink(constructor, selector = "0xDEADBEEF")]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message, selector = "0xC0DECAFE")]
+                    pub fn my_message(&self) {}
+                }
+            }
+        })
+        .unwrap();
+        assert!(item_mod.ensure_globally_unique_selectors().is_ok());
+    }
 }
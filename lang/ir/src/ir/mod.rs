@@ -37,7 +37,10 @@ use self::attrs::{
     InkAttribute,
 };
 pub use self::{
-    attrs::Namespace,
+    attrs::{
+        MinCallerBalance,
+        Namespace,
+    },
     config::Config,
     contract::Contract,
     item::{
@@ -65,7 +68,10 @@ pub use self::{
         IterEvents,
         IterItemImpls,
     },
-    selector::Selector,
+    selector::{
+        Selector,
+        SelectorDerivation,
+    },
     trait_def::{
         InkTrait,
         InkTraitConstructor,
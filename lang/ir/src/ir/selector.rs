@@ -34,11 +34,47 @@ impl Selector {
     }
 
     /// Returns a unique identifier as `usize`.
+    ///
+    /// # Note
+    ///
+    /// Distinct selectors yield distinct identifiers. Since overlapping
+    /// selectors of ink! messages or constructors are rejected upon parsing
+    /// the identifiers are unique among them as well.
     pub fn unique_id(self) -> usize {
         u32::from_le_bytes(self.bytes) as usize
     }
 }
 
+/// The strategy to derive the composed selectors of ink! messages and
+/// constructors that have no user provided selector.
+///
+/// # Note
+///
+/// The strategy is chosen per contract with the `selector_derivation`
+/// ink! config argument. The available strategies are:
+///
+/// - `"qualified"`: The default strategy. The selector is the first four
+///   bytes of the BLAKE-2 hash of the callable identifier qualified by the
+///   namespace and trait path of its implementation block if any,
+///   e.g. `BLAKE2("my_namespace::MyTrait::my_message")[0..4]`.
+/// - `"name_only"`: The selector is the first four bytes of the BLAKE-2 hash
+///   of the callable identifier alone, e.g. `BLAKE2("my_message")[0..4]`.
+///   Namespaces and trait paths are ignored which allows to match the
+///   selectors of an existing ABI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectorDerivation {
+    /// Qualifies the callable identifier by namespace and trait path.
+    Qualified,
+    /// Uses only the callable identifier.
+    NameOnly,
+}
+
+impl Default for SelectorDerivation {
+    fn default() -> Self {
+        Self::Qualified
+    }
+}
+
 impl From<[u8; 4]> for Selector {
     fn from(bytes: [u8; 4]) -> Self {
         Self::new(bytes)
@@ -57,6 +57,7 @@ pub use self::ir::{
     IterItemImpls,
     IterMessages,
     Message,
+    MinCallerBalance,
     Namespace,
     Receiver,
     Selector,
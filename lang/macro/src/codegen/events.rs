@@ -126,7 +126,7 @@ impl EventHelpers<'_> {
             const _: () = {
                 #cfg
                 impl ::ink_core::env::Topics<EnvTypes> for Event {
-                    fn topics(&self) -> &'static [Hash] {
+                    fn topics(&self) -> ::ink_core::env::TopicHashes<EnvTypes> {
                         match self {
                             #(
                                 Event::#event_idents(event) => event.topics(),
@@ -138,20 +138,56 @@ impl EventHelpers<'_> {
         }
     }
 
+    /// Generates the `Topics` and `TopicFields` implementations of the events.
+    ///
+    /// # Note
+    ///
+    /// The topics of an event are the hashes of its `#[ink(topic)]` fields.
     fn generate_topics_impls<'a>(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
         let cfg = self.generate_code_using::<CrossCallingConflictCfg>();
         self.contract.events.iter().map(move |item_event| {
             let span = item_event.span();
             let ident = &item_event.ident;
+            let topic_fields = item_event
+                .fields
+                .named
+                .iter()
+                .filter(|field| {
+                    use core::convert::TryFrom as _;
+                    field
+                        .attrs
+                        .iter()
+                        .cloned()
+                        .filter_map(|attr| ir::Marker::try_from(attr).ok())
+                        .any(|marker| marker.ident() == "topic")
+                })
+                .filter_map(|field| field.ident.as_ref())
+                .collect::<Vec<_>>();
+            let topic_names = topic_fields.iter().map(ToString::to_string);
 
             quote_spanned!(span =>
                 #cfg
                 const _: () = {
                     impl ::ink_core::env::Topics<EnvTypes> for #ident {
-                        fn topics(&self) -> &'static [Hash] {
-                            &[]
+                        fn topics(&self) -> ::ink_core::env::TopicHashes<EnvTypes> {
+                            [
+                                #(
+                                    ::ink_core::env::topic_hash::<EnvTypes, _>(
+                                        &self.#topic_fields
+                                    )
+                                ),*
+                            ]
+                            .iter()
+                            .copied()
+                            .collect()
                         }
                     }
+
+                    impl ::ink_core::env::TopicFields for #ident {
+                        const TOPIC_FIELDS: &'static [&'static str] = &[
+                            #( #topic_names ),*
+                        ];
+                    }
                 };
             )
         })
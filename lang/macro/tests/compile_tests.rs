@@ -25,12 +25,36 @@ fn compile_tests() {
     t.pass("tests/ui/pass/07-flipper-as-dependency.rs");
     t.pass("tests/ui/pass/08-static-env.rs");
     t.pass("tests/ui/pass/09-derive-for-storage.rs");
+    t.pass("tests/ui/pass/10-constructor-only-contract.rs");
+    t.pass("tests/ui/pass/11-trait-qualified-selectors.rs");
+    t.pass("tests/ui/pass/12-fallback-constructor.rs");
+    t.pass("tests/ui/pass/13-min-caller-balance.rs");
+    t.pass("tests/ui/pass/14-versioned-selectors.rs");
+    t.pass("tests/ui/pass/15-many-messages.rs");
+    t.pass("tests/ui/pass/16-catch-panics.rs");
+    t.pass("tests/ui/pass/17-named-constructor-inputs.rs");
+    t.pass("tests/ui/pass/18-insufficient-input-length.rs");
+    t.pass("tests/ui/pass/19-lazy-message.rs");
+    t.pass("tests/ui/pass/20-selector-by-name.rs");
+    t.pass("tests/ui/pass/21-reverting-constructor.rs");
+    t.pass("tests/ui/pass/22-dispatch-raw.rs");
+    t.pass("tests/ui/pass/23-wildcard-message.rs");
+    t.pass("tests/ui/pass/24-event-topic-fields.rs");
+    t.pass("tests/ui/pass/25-selector-aliases.rs");
+    t.pass("tests/ui/pass/26-message-tables.rs");
+    t.pass("tests/ui/pass/27-strict-input-decoding.rs");
+    t.pass("tests/ui/pass/28-fallible-constructor.rs");
+    t.pass("tests/ui/pass/29-decode-errors.rs");
+    t.pass("tests/ui/pass/30-unified-dispatcher.rs");
+    t.pass("tests/ui/pass/31-named-inputs.rs");
+    t.pass("tests/ui/pass/32-selector-derivation.rs");
 
     t.compile_fail("tests/ui/fail/C-00-constructor-self-ref.rs");
     t.compile_fail("tests/ui/fail/C-01-constructor-self-mut.rs");
     t.compile_fail("tests/ui/fail/C-02-constructor-self-val.rs");
     t.compile_fail("tests/ui/fail/C-03-constructor-missing-return.rs");
     t.compile_fail("tests/ui/fail/C-04-missing-constructor.rs");
+    t.compile_fail("tests/ui/fail/C-05-constructor-invalid-return.rs");
     t.compile_fail("tests/ui/fail/C-10-async-constructor.rs");
     t.compile_fail("tests/ui/fail/C-11-unsafe-constructor.rs");
     t.compile_fail("tests/ui/fail/C-12-const-constructor.rs");
@@ -39,8 +63,8 @@ fn compile_tests() {
     t.compile_fail("tests/ui/fail/H-01-invalid-version.rs");
     t.compile_fail("tests/ui/fail/H-02-invalid-as-dependency.rs");
     t.compile_fail("tests/ui/fail/H-03-use-forbidden-idents.rs");
+    t.compile_fail("tests/ui/fail/H-04-unique-selectors-overlap.rs");
 
-    t.compile_fail("tests/ui/fail/M-01-missing-message.rs");
     t.compile_fail("tests/ui/fail/M-02-message-missing-self-arg.rs");
     t.compile_fail("tests/ui/fail/M-03-message-returns-self.rs");
     t.compile_fail("tests/ui/fail/M-10-method-unknown-ink-marker.rs");
@@ -1,15 +1,18 @@
 use ink_lang as ink;
 
 #[ink::contract(version = "0.1.0")]
-mod missing_message {
+mod noop {
     #[ink(storage)]
-    struct MissingMessage {}
+    struct Noop {}
 
-    impl MissingMessage {
+    impl Noop {
         #[ink(constructor)]
-        fn constructor() -> Self {
-            Self {}
+        fn new() -> u32 {
+            42
         }
+
+        #[ink(message)]
+        fn noop(&self) {}
     }
 }
 
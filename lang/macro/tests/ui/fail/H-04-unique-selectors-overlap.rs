@@ -0,0 +1,19 @@
+use ink_lang as ink;
+
+#[ink::contract(unique_selectors = true)]
+mod unique_selectors_overlap {
+    #[ink(storage)]
+    pub struct UniqueSelectorsOverlap {}
+
+    impl UniqueSelectorsOverlap {
+        #[ink(constructor, selector = "0xDEADBEEF")]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = "0xDEADBEEF")]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
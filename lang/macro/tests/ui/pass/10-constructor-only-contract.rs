@@ -0,0 +1,37 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod constructor_only {
+    #[ink(storage)]
+    pub struct ConstructorOnly {}
+
+    impl ConstructorOnly {
+        #[ink(constructor)]
+        fn new() -> Self {
+            Self {}
+        }
+    }
+}
+
+fn main() {
+    use constructor_only::ConstructorOnly;
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        assert_eq!(
+            ink_lang::assert_dispatch_err::<ConstructorOnly>(
+                DispatchMode::Call,
+                vec![0xDE, 0xAD, 0xBE, 0xEF],
+            ),
+            DispatchError::UnknownCallSelector,
+        );
+        Ok(())
+    })
+    .unwrap();
+}
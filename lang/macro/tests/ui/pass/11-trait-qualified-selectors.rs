@@ -0,0 +1,74 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod trait_qualified_selectors {
+    pub trait GetterA {
+        fn get(&self) -> bool;
+    }
+
+    pub trait GetterB {
+        fn get(&self) -> bool;
+    }
+
+    #[ink(storage)]
+    pub struct TraitQualifiedSelectors {
+        a: bool,
+        b: bool,
+    }
+
+    impl TraitQualifiedSelectors {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self { a: true, b: false }
+        }
+    }
+
+    impl GetterA for TraitQualifiedSelectors {
+        #[ink(message)]
+        fn get(&self) -> bool {
+            self.a
+        }
+    }
+
+    impl GetterB for TraitQualifiedSelectors {
+        #[ink(message)]
+        fn get(&self) -> bool {
+            self.b
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use trait_qualified_selectors::{
+        TraitQualifiedSelectors,
+        INK_SELECTOR_GETTERA_GET,
+        INK_SELECTOR_GETTERB_GET,
+    };
+    // Both `get` messages are told apart by their trait.
+    assert_ne!(INK_SELECTOR_GETTERA_GET, INK_SELECTOR_GETTERB_GET);
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let dispatch = |mode, call_data: Vec<u8>| {
+            test::set_raw_call_data(call_data)?;
+            test::catch_return(|| {
+                <TraitQualifiedSelectors as DispatchUsingMode>::dispatch_using_mode(mode)
+                    .expect("encountered failed dispatch")
+            });
+            Ok(())
+        };
+        dispatch(DispatchMode::Instantiate, vec![0x00, 0x00, 0x00, 0x01])?;
+        dispatch(DispatchMode::Call, INK_SELECTOR_GETTERA_GET.to_vec())?;
+        assert_eq!(test::last_return_value::<bool>()?, Some(true));
+        dispatch(DispatchMode::Call, INK_SELECTOR_GETTERB_GET.to_vec())?;
+        assert_eq!(test::last_return_value::<bool>()?, Some(false));
+        Ok(())
+    })
+    .unwrap();
+}
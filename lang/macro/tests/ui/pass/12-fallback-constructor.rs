@@ -0,0 +1,59 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod fallback_constructor {
+    #[ink(storage)]
+    pub struct FallbackConstructor {
+        selector: [u8; 4],
+    }
+
+    impl FallbackConstructor {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self {
+                selector: [0x00; 4],
+            }
+        }
+
+        #[ink(constructor, fallback)]
+        pub fn fallback(selector: [u8; 4]) -> Self {
+            Self { selector }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn get(&self) -> [u8; 4] {
+            self.selector
+        }
+    }
+}
+
+fn main() {
+    use fallback_constructor::FallbackConstructor;
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let dispatch = |mode, call_data: Vec<u8>| {
+            test::set_raw_call_data(call_data)?;
+            test::catch_return(|| {
+                <FallbackConstructor as DispatchUsingMode>::dispatch_using_mode(mode)
+                    .expect("encountered failed dispatch")
+            });
+            Ok(())
+        };
+        // Unknown instantiation selectors are routed to the fallback constructor.
+        dispatch(DispatchMode::Instantiate, vec![0xDE, 0xAD, 0xBE, 0xEF])?;
+        dispatch(DispatchMode::Call, vec![0x00, 0x00, 0x00, 0x02])?;
+        assert_eq!(
+            test::last_return_value::<[u8; 4]>()?,
+            Some([0xDE, 0xAD, 0xBE, 0xEF])
+        );
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,76 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod min_caller_balance {
+    /// The balance that callers of `guarded` need to have at least.
+    const MIN_CALLER_BALANCE: Balance = 1000;
+
+    #[ink(storage)]
+    pub struct MinCallerBalance {}
+
+    impl MinCallerBalance {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        #[ink(min_caller_balance = "MIN_CALLER_BALANCE")]
+        pub fn guarded(&self) -> bool {
+            true
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::Selector,
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use min_caller_balance::MinCallerBalance;
+    test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        let call_as = |caller| -> env::Result<_> {
+            test::push_execution_context::<DefaultEnvTypes>(
+                caller,
+                contract.clone(),
+                1_000_000,
+                0,
+                test::CallData::new(Selector::new([0x00, 0x00, 0x00, 0x02])),
+            );
+            test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x02])?;
+            let mut result = Ok(());
+            test::catch_return(|| {
+                result = <MinCallerBalance as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Call,
+                );
+            });
+            let output = test::last_return_value::<bool>()?;
+            test::pop_execution_context();
+            Ok((result, output))
+        };
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <MinCallerBalance as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        // Django has no balance and is rejected before `guarded` is executed.
+        assert_eq!(
+            call_as(accounts.django.clone())?,
+            (Err(DispatchError::CallerBalanceTooLow), None)
+        );
+        test::set_account_balance::<DefaultEnvTypes>(accounts.django.clone(), 1000)?;
+        assert_eq!(call_as(accounts.django)?, (Ok(()), Some(true)));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,72 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod versioned_selectors {
+    #[ink(storage)]
+    pub struct VersionedSelectors {}
+
+    impl VersionedSelectors {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message, version = 2)]
+        pub fn get(&self) -> bool {
+            true
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use versioned_selectors::VersionedSelectors;
+    // The selectors of `get` in its current version 2 and its deprecated version 1.
+    let current = [0x90, 0xF6, 0x73, 0x30];
+    let deprecated = [0x1E, 0x5C, 0xA4, 0x56];
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let call = |selector: [u8; 4]| -> env::Result<_> {
+            test::set_raw_call_data(selector.to_vec())?;
+            test::catch_return(|| {
+                <VersionedSelectors as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Call,
+                )
+                .expect("encountered failed dispatch")
+            });
+            test::last_return_value::<bool>()
+        };
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <VersionedSelectors as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        // The current selector dispatches silently.
+        assert_eq!(call(current)?, Some(true));
+        assert!(test::drain_debug_messages::<DefaultEnvTypes>()?.is_empty());
+        // The deprecated selector still dispatches but leaves a debug message.
+        assert_eq!(call(deprecated)?, Some(true));
+        let messages = test::drain_debug_messages::<DefaultEnvTypes>()?
+            .into_iter()
+            .map(|debug_message| debug_message.message)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            vec![format!(
+                "ink! message `get` has been called via its deprecated selector {:02X?}",
+                deprecated
+            )]
+        );
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,154 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod many_messages {
+    #[ink(storage)]
+    pub struct ManyMessages {}
+
+    impl ManyMessages {
+        #[ink(constructor, selector = "0xFFFFFFFF")]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = "0x00000013")]
+        pub fn message_19(&self) -> u8 {
+            19
+        }
+
+        #[ink(message, selector = "0x00000012")]
+        pub fn message_18(&self) -> u8 {
+            18
+        }
+
+        #[ink(message, selector = "0x00000011")]
+        pub fn message_17(&self) -> u8 {
+            17
+        }
+
+        #[ink(message, selector = "0x00000010")]
+        pub fn message_16(&self) -> u8 {
+            16
+        }
+
+        #[ink(message, selector = "0x0000000F")]
+        pub fn message_15(&self) -> u8 {
+            15
+        }
+
+        #[ink(message, selector = "0x0000000E")]
+        pub fn message_14(&self) -> u8 {
+            14
+        }
+
+        #[ink(message, selector = "0x0000000D")]
+        pub fn message_13(&self) -> u8 {
+            13
+        }
+
+        #[ink(message, selector = "0x0000000C")]
+        pub fn message_12(&self) -> u8 {
+            12
+        }
+
+        #[ink(message, selector = "0x0000000B")]
+        pub fn message_11(&self) -> u8 {
+            11
+        }
+
+        #[ink(message, selector = "0x0000000A")]
+        pub fn message_10(&self) -> u8 {
+            10
+        }
+
+        #[ink(message, selector = "0x00000009")]
+        pub fn message_9(&self) -> u8 {
+            9
+        }
+
+        #[ink(message, selector = "0x00000008")]
+        pub fn message_8(&self) -> u8 {
+            8
+        }
+
+        #[ink(message, selector = "0x00000007")]
+        pub fn message_7(&self) -> u8 {
+            7
+        }
+
+        #[ink(message, selector = "0x00000006")]
+        pub fn message_6(&self) -> u8 {
+            6
+        }
+
+        #[ink(message, selector = "0x00000005")]
+        pub fn message_5(&self) -> u8 {
+            5
+        }
+
+        #[ink(message, selector = "0x00000004")]
+        pub fn message_4(&self) -> u8 {
+            4
+        }
+
+        #[ink(message, selector = "0x00000003")]
+        pub fn message_3(&self) -> u8 {
+            3
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn message_2(&self) -> u8 {
+            2
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn message_1(&self) -> u8 {
+            1
+        }
+
+        #[ink(message, selector = "0x00000000")]
+        pub fn message_0(&self) -> u8 {
+            0
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use many_messages::ManyMessages;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        test::set_raw_call_data(vec![0xFF, 0xFF, 0xFF, 0xFF])?;
+        test::catch_return(|| {
+            <ManyMessages as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        // Every selector still dispatches to its ink! message.
+        for index in 0..20u8 {
+            test::set_raw_call_data(vec![0x00, 0x00, 0x00, index])?;
+            test::catch_return(|| {
+                <ManyMessages as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Call,
+                )
+                .expect("encountered failed dispatch")
+            });
+            assert_eq!(test::last_return_value::<u8>()?, Some(index));
+        }
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 20])?;
+        assert_eq!(
+            <ManyMessages as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call),
+            Err(DispatchError::UnknownCallSelector),
+        );
+        Ok(())
+    })
+    .unwrap();
+}
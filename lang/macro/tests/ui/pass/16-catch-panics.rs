@@ -0,0 +1,61 @@
+use ink_lang as ink;
+
+#[ink::contract(catch_panics = true)]
+mod catch_panics {
+    #[ink(storage)]
+    pub struct CatchPanics {}
+
+    impl CatchPanics {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn fine(&self) -> bool {
+            true
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn boom(&self) -> bool {
+            panic!("boom")
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use catch_panics::CatchPanics;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x00])?;
+        test::catch_return(|| {
+            <CatchPanics as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        // Returning a value is not mistaken for a panic.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <CatchPanics as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call)
+                .expect("encountered failed dispatch")
+        });
+        assert_eq!(test::last_return_value::<bool>()?, Some(true));
+        // Panics are reported as dispatch error instead.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x02])?;
+        assert_eq!(
+            <CatchPanics as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call),
+            Err(DispatchError::CalleePanicked),
+        );
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,57 @@
+use ink_lang as ink;
+
+#[ink::contract(named_constructor_inputs = true)]
+mod named_constructor_inputs {
+    #[ink(storage)]
+    pub struct NamedConstructorInputs {
+        value: u64,
+    }
+
+    impl NamedConstructorInputs {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new(a: u8, b: u16, c: u32) -> Self {
+            Self {
+                value: a as u64 * 100_000 + b as u64 * 1_000 + c as u64,
+            }
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn get(&self) -> u64 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use named_constructor_inputs::NamedConstructorInputs;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // The inputs `1u8`, `2u16` and `3u32` decode into the fields in order.
+        test::set_raw_call_data(vec![
+            0x00, 0x00, 0x00, 0x00, 0x01, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00,
+        ])?;
+        test::catch_return(|| {
+            <NamedConstructorInputs as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <NamedConstructorInputs as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Call,
+            )
+            .expect("encountered failed dispatch")
+        });
+        assert_eq!(test::last_return_value::<u64>()?, Some(102_003));
+        Ok(())
+    })
+    .unwrap();
+}
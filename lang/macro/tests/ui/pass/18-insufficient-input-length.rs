@@ -0,0 +1,53 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod insufficient_input_length {
+    #[ink(storage)]
+    pub struct InsufficientInputLength {
+        value: u32,
+    }
+
+    impl InsufficientInputLength {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn add(&mut self, a: u32, b: u32) {
+            self.value += a + b;
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use insufficient_input_length::InsufficientInputLength;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x00])?;
+        test::catch_return(|| {
+            <InsufficientInputLength as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        // The selector is followed by an empty buffer instead of two `u32`.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        assert_eq!(
+            <InsufficientInputLength as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Call,
+            ),
+            Err(DispatchError::InsufficientCallInput),
+        );
+        Ok(())
+    })
+    .unwrap();
+}
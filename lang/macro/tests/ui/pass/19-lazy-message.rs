@@ -0,0 +1,59 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod lazy_message {
+    use ink_core::storage2::Lazy;
+
+    #[ink(storage)]
+    pub struct LazyMessage {
+        data: Lazy<Vec<u8>>,
+    }
+
+    impl LazyMessage {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new(len: u32) -> Self {
+            Self {
+                data: Lazy::new((0..len).map(|i| i as u8).collect()),
+            }
+        }
+
+        /// Encodes the stored bytes directly into the return buffer.
+        ///
+        /// Unlike returning `Vec<u8>` this does not clone the loaded bytes.
+        #[ink(message, selector = "0x00000001")]
+        pub fn data(&self) -> &Vec<u8> {
+            &self.data
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use lazy_message::LazyMessage;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // Instantiates the contract with 10_000 bytes of data.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x00, 0x10, 0x27, 0x00, 0x00])?;
+        test::catch_return(|| {
+            <LazyMessage as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <LazyMessage as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call)
+                .expect("encountered failed dispatch")
+        });
+        let expected = (0..10_000_u32).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(test::last_return_value::<Vec<u8>>()?, Some(expected));
+        Ok(())
+    })
+    .unwrap();
+}
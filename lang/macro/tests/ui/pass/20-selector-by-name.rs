@@ -0,0 +1,50 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod selector_by_name {
+    #[ink(storage)]
+    pub struct SelectorByName {
+        value: bool,
+    }
+
+    impl SelectorByName {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        #[ink(message, selector = "0xDEADBEEF")]
+        pub fn get_value(&self) -> bool {
+            self.value
+        }
+    }
+}
+
+use selector_by_name::InkMessage;
+
+const FLIP: [u8; 4] = InkMessage::Flip.selector();
+const GET_VALUE: [u8; 4] = InkMessage::GetValue.selector();
+
+/// Resolves selectors at compile time without hashing any message names.
+const fn message_name(selector: [u8; 4]) -> &'static str {
+    match selector {
+        FLIP => "flip",
+        GET_VALUE => "get_value",
+        _ => "unknown",
+    }
+}
+
+const FLIP_NAME: &str = message_name(InkMessage::Flip.selector());
+
+fn main() {
+    assert_eq!(FLIP_NAME, "flip");
+    assert_eq!(message_name(GET_VALUE), "get_value");
+    assert_eq!(GET_VALUE, [0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(FLIP, selector_by_name::INK_SELECTOR_FLIP);
+    assert_eq!(message_name([0x00; 4]), "unknown");
+}
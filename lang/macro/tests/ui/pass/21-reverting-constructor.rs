@@ -0,0 +1,77 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod reverting_constructor {
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    pub enum Error {
+        ZeroValue,
+    }
+
+    #[ink(storage)]
+    pub struct RevertingConstructor {
+        value: u32,
+    }
+
+    impl RevertingConstructor {
+        #[ink(constructor, selector = "0x00000000", revert_on_error)]
+        pub fn new(value: u32) -> Result<Self, Error> {
+            if value == 0 {
+                return Err(Error::ZeroValue)
+            }
+            Ok(Self { value })
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+        ReturnFlags,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+    use reverting_constructor::{
+        Error,
+        RevertingConstructor,
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // The invalid value reverts the instantiation and returns the error.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00])?;
+        let flags = test::catch_return(|| {
+            <RevertingConstructor as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        assert_eq!(flags, Some(ReturnFlags::default().set_reverted(true)));
+        assert!(flags.map(ReturnFlags::is_reverted).unwrap_or(false));
+        assert_eq!(test::last_return_value::<Error>()?, Some(Error::ZeroValue));
+        // Valid values instantiate the contract without returning.
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x00, 0x2A, 0x00, 0x00, 0x00])?;
+        let flags = test::catch_return(|| {
+            <RevertingConstructor as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Instantiate,
+            )
+            .expect("encountered failed instantiation")
+        });
+        assert_eq!(flags, None);
+        test::set_raw_call_data(vec![0x00, 0x00, 0x00, 0x01])?;
+        test::catch_return(|| {
+            <RevertingConstructor as DispatchUsingMode>::dispatch_using_mode(
+                DispatchMode::Call,
+            )
+            .expect("encountered failed dispatch")
+        });
+        assert_eq!(test::last_return_value::<u32>()?, Some(42));
+        Ok(())
+    })
+    .unwrap();
+}
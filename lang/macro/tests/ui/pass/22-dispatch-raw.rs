@@ -0,0 +1,68 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod counter {
+    #[ink(storage)]
+    pub struct Counter {
+        value: u32,
+    }
+
+    impl Counter {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new(init_value: u32) -> Self {
+            Self { value: init_value }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn inc_by(&mut self, by: u32) {
+            self.value += by;
+        }
+
+        #[ink(message, selector = "0x00000003")]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use counter::Counter;
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    use scale::Encode as _;
+    let call_data = |selector: u8, input: &[u8]| {
+        let mut call_data = vec![0x00, 0x00, 0x00, selector];
+        call_data.extend_from_slice(input);
+        call_data
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let deployed = ink_lang::dispatch_raw::<Counter>(
+            DispatchMode::Instantiate,
+            call_data(0x01, &41_u32.encode()),
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        let incremented = ink_lang::dispatch_raw::<Counter>(
+            DispatchMode::Call,
+            call_data(0x02, &1_u32.encode()),
+        );
+        assert_eq!(incremented, Ok(Vec::new()));
+        let value = ink_lang::dispatch_raw::<Counter>(
+            DispatchMode::Call,
+            call_data(0x03, &[]),
+        );
+        assert_eq!(value, Ok(42_u32.encode()));
+        let unknown = ink_lang::dispatch_raw::<Counter>(
+            DispatchMode::Call,
+            call_data(0x04, &[]),
+        );
+        assert_eq!(unknown, Err(DispatchError::UnknownCallSelector));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,57 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod router {
+    #[ink(storage)]
+    pub struct Router {
+        routed: u32,
+    }
+
+    impl Router {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self { routed: 0 }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn routed(&self) -> u32 {
+            self.routed
+        }
+
+        #[ink(message, selector = "_")]
+        pub fn route(&mut self, selector: [u8; 4], by: u32) -> [u8; 4] {
+            self.routed += by;
+            selector
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::DispatchMode;
+    use router::Router;
+    use scale::Encode as _;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let deployed = ink_lang::dispatch_raw::<Router>(
+            DispatchMode::Instantiate,
+            vec![0x00, 0x00, 0x00, 0x01],
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        // Unknown selectors are routed to the wildcard message which receives
+        // the selector followed by the remaining arguments.
+        let mut call_data = vec![0xDE, 0xAD, 0xBE, 0xEF];
+        call_data.extend_from_slice(&5_u32.encode());
+        let routed = ink_lang::dispatch_raw::<Router>(DispatchMode::Call, call_data);
+        assert_eq!(routed, Ok([0xDE, 0xAD, 0xBE, 0xEF].encode()));
+        let total = ink_lang::dispatch_raw::<Router>(
+            DispatchMode::Call,
+            vec![0x00, 0x00, 0x00, 0x02],
+        );
+        assert_eq!(total, Ok(5_u32.encode()));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,85 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod token {
+    #[ink(storage)]
+    pub struct Token {
+        total_supply: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        amount: Balance,
+    }
+
+    impl Token {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self { total_supply: 0 }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn mint(&mut self, amount: Balance) {
+            self.total_supply += amount;
+            self.env().emit_event(Transferred {
+                from: None,
+                to: Some(self.env().caller()),
+                amount,
+            });
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+        TopicFields,
+    };
+    use ink_lang::DispatchMode;
+    use scale::Encode as _;
+    use token::{
+        Token,
+        Transferred,
+    };
+    let call_data = |selector: u8, input: &[u8]| {
+        let mut call_data = vec![0x00, 0x00, 0x00, selector];
+        call_data.extend_from_slice(input);
+        call_data
+    };
+    assert_eq!(<Transferred as TopicFields>::TOPIC_FIELDS, &["from", "to"]);
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let deployed = ink_lang::dispatch_raw::<Token>(
+            DispatchMode::Instantiate,
+            call_data(0x01, &[]),
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        let minted = ink_lang::dispatch_raw::<Token>(
+            DispatchMode::Call,
+            call_data(0x02, &100_u128.encode()),
+        );
+        assert_eq!(minted, Ok(Vec::new()));
+        assert_eq!(test::recorded_events().count(), 1);
+        // The topics are the hashes of the encoded topic fields.
+        let hashed = |value: Option<AccountId>| {
+            let mut output = [0x00; 32];
+            ink_core::env::hash::blake2_256(&value.encode(), &mut output);
+            Hash::from(output)
+        };
+        let topic = |field_name| {
+            test::event_topic::<DefaultEnvTypes, Transferred>(0, field_name)
+        };
+        let caller = ink_core::env::caller::<DefaultEnvTypes>()?;
+        assert_eq!(topic("from")?, Some(hashed(None)));
+        assert_eq!(topic("to")?, Some(hashed(Some(caller))));
+        assert_eq!(topic("amount")?, None);
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,66 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod selector_aliases {
+    #[ink(storage)]
+    pub struct SelectorAliases {
+        value: u32,
+    }
+
+    impl SelectorAliases {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, selector = "0xDEADBEEF", selector = "0xC0DECAFE")]
+        pub fn inc(&mut self) {
+            self.value += 1;
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    use scale::Encode as _;
+    use selector_aliases::SelectorAliases;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let deployed = ink_lang::dispatch_raw::<SelectorAliases>(
+            DispatchMode::Instantiate,
+            vec![0x00, 0x00, 0x00, 0x01],
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        // Both the selector and its alias call `inc`.
+        let inc = |selector: [u8; 4]| {
+            ink_lang::dispatch_raw::<SelectorAliases>(
+                DispatchMode::Call,
+                selector.to_vec(),
+            )
+        };
+        assert_eq!(inc([0xDE, 0xAD, 0xBE, 0xEF]), Ok(Vec::new()));
+        assert_eq!(inc([0xC0, 0xDE, 0xCA, 0xFE]), Ok(Vec::new()));
+        assert_eq!(
+            inc([0xC0, 0xDE, 0xCA, 0xFF]),
+            Err(DispatchError::UnknownCallSelector)
+        );
+        let value = ink_lang::dispatch_raw::<SelectorAliases>(
+            DispatchMode::Call,
+            vec![0x00, 0x00, 0x00, 0x02],
+        );
+        assert_eq!(value, Ok(2_u32.encode()));
+        Ok(())
+    })
+    .unwrap();
+}
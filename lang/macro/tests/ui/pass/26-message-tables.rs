@@ -0,0 +1,71 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod message_tables {
+    #[ink(storage)]
+    pub struct MessageTables {
+        value: bool,
+    }
+
+    impl MessageTables {
+        #[ink(constructor, selector = "0xC0DECAFE")]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message, payable, selector = "0xDEADBEEF")]
+        pub fn set(&mut self, value: bool) {
+            self.value = value;
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_lang::MessageInfo;
+    use message_tables::MessageTables;
+    // Every ink! message is listed by its selector.
+    assert_eq!(
+        MessageTables::__INK_MESSAGE_SELECTORS,
+        &[[0xDE, 0xAD, 0xBE, 0xEF], [0x00, 0x00, 0x00, 0x01]]
+    );
+    // Every ink! message and constructor is listed with its name.
+    assert_eq!(
+        MessageTables::__INK_MESSAGE_NAMES,
+        &[([0xDE, 0xAD, 0xBE, 0xEF], "set"), ([0x00, 0x00, 0x00, 0x01], "get")]
+    );
+    assert_eq!(
+        MessageTables::__INK_CONSTRUCTOR_NAMES,
+        &[([0xC0, 0xDE, 0xCA, 0xFE], "new")]
+    );
+    // The payable `&mut self` message `set` takes one argument.
+    assert_eq!(
+        MessageTables::__INK_MESSAGE_INFOS,
+        &[
+            MessageInfo {
+                selector: [0xDE, 0xAD, 0xBE, 0xEF],
+                mutates: true,
+                payable: true,
+                args: 1,
+            },
+            MessageInfo {
+                selector: [0x00, 0x00, 0x00, 0x01],
+                mutates: false,
+                payable: false,
+                args: 0,
+            },
+        ]
+    );
+    // Only the `&mut self` message `set` mutates the contract storage.
+    let mutates = |selector| {
+        MessageInfo::find(MessageTables::__INK_MESSAGE_INFOS, selector)
+            .map(|info| info.mutates)
+    };
+    assert_eq!(mutates([0xDE, 0xAD, 0xBE, 0xEF]), Some(true));
+    assert_eq!(mutates([0x00, 0x00, 0x00, 0x01]), Some(false));
+    assert_eq!(mutates([0xC0, 0xDE, 0xCA, 0xFE]), None);
+}
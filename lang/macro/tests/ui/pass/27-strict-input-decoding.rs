@@ -0,0 +1,77 @@
+use ink_lang as ink;
+
+#[ink::contract(strict_input_decoding = true)]
+mod strict_input_decoding {
+    #[ink(storage)]
+    pub struct StrictInputDecoding {
+        value: u32,
+    }
+
+    impl StrictInputDecoding {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn add(&mut self, by: u32) {
+            self.value += by;
+        }
+
+        #[ink(message, selector = "0x00000003")]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    use scale::Encode as _;
+    use strict_input_decoding::StrictInputDecoding;
+    let call_data = |selector: u8, input: &[u8]| {
+        let mut call_data = vec![0x00, 0x00, 0x00, selector];
+        call_data.extend_from_slice(input);
+        call_data
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // Trailing bytes are rejected for constructors and messages alike.
+        let deployed = ink_lang::dispatch_raw::<StrictInputDecoding>(
+            DispatchMode::Instantiate,
+            call_data(0x01, &[0xFF]),
+        );
+        assert_eq!(deployed, Err(DispatchError::InvalidInstantiateParameters));
+        let deployed = ink_lang::dispatch_raw::<StrictInputDecoding>(
+            DispatchMode::Instantiate,
+            call_data(0x01, &[]),
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        let mut trailing = 1_u32.encode();
+        trailing.push(0xFF);
+        let added = ink_lang::dispatch_raw::<StrictInputDecoding>(
+            DispatchMode::Call,
+            call_data(0x02, &trailing),
+        );
+        assert_eq!(added, Err(DispatchError::InvalidCallParameters));
+        // Exhausted inputs are still accepted.
+        let added = ink_lang::dispatch_raw::<StrictInputDecoding>(
+            DispatchMode::Call,
+            call_data(0x02, &1_u32.encode()),
+        );
+        assert_eq!(added, Ok(Vec::new()));
+        let value = ink_lang::dispatch_raw::<StrictInputDecoding>(
+            DispatchMode::Call,
+            call_data(0x03, &[]),
+        );
+        assert_eq!(value, Ok(1_u32.encode()));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod fallible_constructor {
+    #[ink(storage)]
+    pub struct FallibleConstructor {
+        value: bool,
+    }
+
+    impl FallibleConstructor {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new(init_value: u8) -> Result<Self, ()> {
+            match init_value {
+                0 => Ok(Self { value: false }),
+                1 => Ok(Self { value: true }),
+                _ => Err(()),
+            }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use fallible_constructor::FallibleConstructor;
+    use ink_core::env::{
+        self,
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    use scale::Encode as _;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let instantiate = |init_value: u8| {
+            ink_lang::dispatch_raw::<FallibleConstructor>(
+                DispatchMode::Instantiate,
+                vec![0x00, 0x00, 0x00, 0x01, init_value],
+            )
+        };
+        // The returned error fails the deployment without initializing storage.
+        assert_eq!(instantiate(2), Err(DispatchError::ConstructorFailed));
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        assert!(test::storage_keys::<DefaultEnvTypes>(&contract)?.is_empty());
+        assert_eq!(instantiate(1), Ok(Vec::new()));
+        let value = ink_lang::dispatch_raw::<FallibleConstructor>(
+            DispatchMode::Call,
+            vec![0x00, 0x00, 0x00, 0x02],
+        );
+        assert_eq!(value, Ok(true.encode()));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,89 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod decode_errors {
+    #[ink(storage)]
+    pub struct DecodeErrors {
+        value: u32,
+    }
+
+    impl DecodeErrors {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new(init_value: bool) -> Self {
+            Self {
+                value: init_value as u32,
+            }
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn add(&mut self, by: u32, twice: bool) {
+            self.value += if twice { 2 * by } else { by };
+        }
+    }
+}
+
+fn main() {
+    use decode_errors::DecodeErrors;
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    let call_data = |selector: u8, input: &[u8]| {
+        let mut call_data = vec![0x00, 0x00, 0x00, selector];
+        call_data.extend_from_slice(input);
+        call_data
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // Unknown selectors are told apart by the dispatch mode.
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Instantiate,
+                call_data(0x02, &[]),
+            ),
+            Err(DispatchError::UnknownInstantiateSelector),
+        );
+        // `0x02` is no valid encoding of a `bool`.
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Instantiate,
+                call_data(0x01, &[0x02]),
+            ),
+            Err(DispatchError::InvalidInstantiateArgument(0)),
+        );
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Instantiate,
+                call_data(0x01, &[0x01]),
+            ),
+            Ok(Vec::new()),
+        );
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Call,
+                call_data(0x01, &[]),
+            ),
+            Err(DispatchError::UnknownCallSelector),
+        );
+        // Malformed arguments report their position among the inputs.
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Call,
+                call_data(0x02, &[0x01, 0x00, 0x00, 0x00, 0x02]),
+            ),
+            Err(DispatchError::InvalidCallArgument(1)),
+        );
+        assert_eq!(
+            ink_lang::dispatch_raw::<DecodeErrors>(
+                DispatchMode::Call,
+                call_data(0x02, &[0x01, 0x00, 0x00, 0x00, 0x01]),
+            ),
+            Ok(Vec::new()),
+        );
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,62 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod counter {
+    #[ink(storage)]
+    pub struct Counter {
+        value: u32,
+    }
+
+    impl Counter {
+        #[ink(constructor, selector = "0x00000001")]
+        pub fn new(init_value: u32) -> Self {
+            Self { value: init_value }
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use counter::Counter;
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DecodeDispatch,
+        DispatchError,
+        DispatchMode,
+        Execute,
+        UnifiedDispatcher,
+    };
+    use scale::Encode as _;
+    type Unified = <Counter as UnifiedDispatcher>::Type;
+    let decode = |input: Vec<u8>| {
+        <Unified as DecodeDispatch>::decode_dispatch(&mut &input[..])
+    };
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        // The leading mode byte tells the equal selectors apart.
+        let mut input = vec![0x00, 0x00, 0x00, 0x00, 0x01];
+        input.extend(42_u32.encode());
+        let constructor = decode(input).expect("encountered invalid constructor input");
+        assert!(constructor.mode() == DispatchMode::Instantiate);
+        assert_eq!(constructor.execute(), Ok(()));
+        let message = decode(vec![0x01, 0x00, 0x00, 0x00, 0x01])
+            .expect("encountered invalid message input");
+        assert!(message.mode() == DispatchMode::Call);
+        test::catch_return(|| {
+            message.execute().expect("encountered failed dispatch")
+        });
+        assert_eq!(test::last_return_value::<u32>()?, Some(42));
+        assert_eq!(
+            decode(vec![0x02, 0x00, 0x00, 0x00, 0x01]).map(|_| ()),
+            Err(DispatchError::UnknownSelector),
+        );
+        Ok(())
+    })
+    .unwrap();
+}
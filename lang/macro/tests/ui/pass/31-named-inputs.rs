@@ -0,0 +1,56 @@
+use ink_lang as ink;
+
+#[ink::contract(named_inputs = true)]
+mod named_inputs {
+    #[ink(storage)]
+    pub struct NamedInputs {
+        value: u64,
+    }
+
+    impl NamedInputs {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, selector = "0x00000001")]
+        pub fn mix(&mut self, a: u8, b: u16, c: u32, d: u64, e: bool) {
+            let value = a as u64 * 1_000_000 + b as u64 * 10_000 + c as u64 * 100 + d;
+            self.value = if e { value } else { 0 };
+        }
+
+        #[ink(message, selector = "0x00000002")]
+        pub fn get(&self) -> u64 {
+            self.value
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::DispatchMode;
+    use named_inputs::NamedInputs;
+    use scale::Encode as _;
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let deployed = ink_lang::dispatch_raw::<NamedInputs>(
+            DispatchMode::Instantiate,
+            vec![0x00, 0x00, 0x00, 0x00],
+        );
+        assert_eq!(deployed, Ok(Vec::new()));
+        // The five inputs decode into the fields of the named input struct in order.
+        let mut call_data = vec![0x00, 0x00, 0x00, 0x01];
+        call_data.extend((1_u8, 2_u16, 3_u32, 4_u64, true).encode());
+        let mixed = ink_lang::dispatch_raw::<NamedInputs>(DispatchMode::Call, call_data);
+        assert_eq!(mixed, Ok(Vec::new()));
+        let value = ink_lang::dispatch_raw::<NamedInputs>(
+            DispatchMode::Call,
+            vec![0x00, 0x00, 0x00, 0x02],
+        );
+        assert_eq!(value, Ok(1_020_304_u64.encode()));
+        Ok(())
+    })
+    .unwrap();
+}
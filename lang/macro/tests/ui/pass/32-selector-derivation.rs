@@ -0,0 +1,78 @@
+use ink_lang as ink;
+
+#[ink::contract(selector_derivation = "name_only")]
+mod name_only {
+    #[ink(storage)]
+    pub struct NameOnly {
+        value: bool,
+    }
+
+    #[ink(namespace = "my_namespace")]
+    impl NameOnly {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+    }
+}
+
+/// Only provides the selector constants of the same contract with the
+/// default `qualified` selector derivation.
+#[ink::contract(compile_as_dependency = true)]
+mod qualified {
+    #[ink(storage)]
+    pub struct Qualified {
+        value: bool,
+    }
+
+    #[ink(namespace = "my_namespace")]
+    impl Qualified {
+        #[ink(constructor, selector = "0x00000000")]
+        pub fn new() -> Self {
+            Self { value: false }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        test,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+    };
+    use name_only::NameOnly;
+    // Deriving from `flip` and `my_namespace::flip` yields different selectors.
+    assert_ne!(name_only::INK_SELECTOR_FLIP, qualified::INK_SELECTOR_FLIP);
+    test::run_test::<DefaultEnvTypes, _>(|_| {
+        let dispatch = |mode, call_data: [u8; 4]| {
+            ink_lang::dispatch_raw::<NameOnly>(mode, call_data.to_vec())
+        };
+        assert_eq!(
+            dispatch(DispatchMode::Instantiate, [0x00, 0x00, 0x00, 0x00]),
+            Ok(Vec::new())
+        );
+        assert_eq!(
+            dispatch(DispatchMode::Call, name_only::INK_SELECTOR_FLIP),
+            Ok(Vec::new())
+        );
+        assert_eq!(
+            dispatch(DispatchMode::Call, qualified::INK_SELECTOR_FLIP),
+            Err(DispatchError::UnknownCallSelector)
+        );
+        Ok(())
+    })
+    .unwrap();
+}
@@ -31,3 +31,34 @@ pub enum DispatchMode {
 pub trait DispatchUsingMode {
     fn dispatch_using_mode(mode: DispatchMode) -> Result<(), DispatchError>;
 }
+
+/// The attributes of an ink! message relevant to ABI tooling.
+///
+/// # Note
+///
+/// The ink! codegen generates an array of those for all ink! messages
+/// of a contract as its `__INK_MESSAGE_INFOS` associated constant.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MessageInfo {
+    /// The selector of the message.
+    pub selector: [u8; 4],
+    /// `true` if the message takes `&mut self`.
+    pub mutates: bool,
+    /// `true` if the message accepts payments.
+    pub payable: bool,
+    /// The number of arguments of the message.
+    pub args: usize,
+}
+
+impl MessageInfo {
+    /// Returns the attributes of the message with the given selector.
+    ///
+    /// # Note
+    ///
+    /// This allows off-chain simulators and RPC layers to e.g. look up
+    /// whether a message mutates the contract storage in order to route
+    /// calls to read-only messages through a cheaper path.
+    pub fn find(infos: &[MessageInfo], selector: [u8; 4]) -> Option<&MessageInfo> {
+        infos.iter().find(|info| info.selector == selector)
+    }
+}
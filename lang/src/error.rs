@@ -16,7 +16,7 @@
 pub type DispatchResult = core::result::Result<(), DispatchError>;
 
 /// A dispatch error.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DispatchError {
     UnknownSelector,
     UnknownInstantiateSelector,
@@ -25,9 +25,26 @@ pub enum DispatchError {
     InvalidParameters,
     InvalidInstantiateParameters,
     InvalidCallParameters,
+    /// The argument of an ink! constructor at the given position is invalid.
+    InvalidInstantiateArgument(u8),
+    /// The argument of an ink! message at the given position is invalid.
+    InvalidCallArgument(u8),
+    /// The input is too short to hold the arguments of an ink! constructor.
+    InsufficientInstantiateInput,
+    /// The input is too short to hold the arguments of an ink! message.
+    InsufficientCallInput,
 
     CouldNotReadInput,
     PaidUnpayableMessage,
+    ConstructorFailed,
+    ReentrantCall,
+    ReadOnlyViolation,
+    /// The balance of the caller is below the minimum required by the ink! message.
+    CallerBalanceTooLow,
+    /// The environment cannot query the balance of the caller.
+    CallerBalanceUnavailable,
+    /// The executed ink! message panicked.
+    CalleePanicked,
 }
 
 impl DispatchError {
@@ -38,6 +55,55 @@ impl DispatchError {
     }
 }
 
+impl From<DispatchError> for scale::Error {
+    #[inline]
+    fn from(err: DispatchError) -> Self {
+        let description = match err {
+            DispatchError::UnknownSelector => "encountered unknown selector",
+            DispatchError::UnknownInstantiateSelector => {
+                "encountered unknown ink! constructor selector"
+            }
+            DispatchError::UnknownCallSelector => {
+                "encountered unknown ink! message selector"
+            }
+            DispatchError::InvalidParameters => "encountered invalid parameters",
+            DispatchError::InvalidInstantiateParameters => {
+                "encountered invalid ink! constructor parameters"
+            }
+            DispatchError::InvalidCallParameters => {
+                "encountered invalid ink! message parameters"
+            }
+            DispatchError::InvalidInstantiateArgument(_) => {
+                "failed to decode ink! constructor argument"
+            }
+            DispatchError::InvalidCallArgument(_) => {
+                "failed to decode ink! message argument"
+            }
+            DispatchError::InsufficientInstantiateInput => {
+                "insufficient input length for ink! constructor arguments"
+            }
+            DispatchError::InsufficientCallInput => {
+                "insufficient input length for ink! message arguments"
+            }
+            DispatchError::CouldNotReadInput => "could not read input",
+            DispatchError::PaidUnpayableMessage => "paid an unpayable message",
+            DispatchError::ConstructorFailed => "ink! constructor failed",
+            DispatchError::ReentrantCall => "encountered reentrant call",
+            DispatchError::ReadOnlyViolation => {
+                "called a mutating ink! message in read-only mode"
+            }
+            DispatchError::CallerBalanceTooLow => {
+                "caller balance is below the minimum required by the ink! message"
+            }
+            DispatchError::CallerBalanceUnavailable => {
+                "cannot query the caller balance required by the ink! message"
+            }
+            DispatchError::CalleePanicked => "ink! message panicked",
+        };
+        scale::Error::from(description)
+    }
+}
+
 /// A return code indicating success or error in a compact form.
 #[derive(Copy, Clone)]
 pub struct DispatchRetCode(u32);
@@ -70,8 +136,21 @@ impl From<DispatchError> for DispatchRetCode {
             DispatchError::InvalidParameters => Self(0x04),
             DispatchError::InvalidInstantiateParameters => Self(0x05),
             DispatchError::InvalidCallParameters => Self(0x06),
+            // The argument position is only reported off-chain so that
+            // return codes stay the same as for invalid parameters.
+            DispatchError::InvalidInstantiateArgument(_) => Self(0x05),
+            DispatchError::InvalidCallArgument(_) => Self(0x06),
+            // Inputs too short for the arguments are invalid parameters too.
+            DispatchError::InsufficientInstantiateInput => Self(0x05),
+            DispatchError::InsufficientCallInput => Self(0x06),
             DispatchError::CouldNotReadInput => Self(0x07),
             DispatchError::PaidUnpayableMessage => Self(0x08),
+            DispatchError::ConstructorFailed => Self(0x09),
+            DispatchError::ReentrantCall => Self(0x0A),
+            DispatchError::ReadOnlyViolation => Self(0x0B),
+            DispatchError::CallerBalanceTooLow => Self(0x0C),
+            DispatchError::CalleePanicked => Self(0x0D),
+            DispatchError::CallerBalanceUnavailable => Self(0x0E),
         }
     }
 }
@@ -28,10 +28,16 @@ pub use ink_lang_macro::{
     trait_definition,
 };
 
+#[cfg(feature = "std")]
+pub use self::dispatcher::{
+    assert_dispatch_err,
+    dispatch_raw,
+};
 pub use self::{
     contract::{
         DispatchMode,
         DispatchUsingMode,
+        MessageInfo,
     },
     cross_calling::{
         ForwardCall,
@@ -40,15 +46,31 @@ pub use self::{
         ToAccountId,
     },
     dispatcher::{
+        catch_panic,
+        decode_cow_bytes,
+        decode_dispatch_input,
         deny_payment,
+        encode_call,
+        ensure_input_exhausted,
+        ensure_min_caller_balance,
+        ensure_min_input_len,
+        enter_reentrancy_guard,
         execute_constructor,
+        execute_fallible_constructor,
+        execute_lazy_message,
         execute_message,
         execute_message_mut,
+        execute_reverting_constructor,
+        leave_reentrancy_guard,
         AcceptsPayments,
         ConstructorDispatcher,
+        DecodeDispatch,
         EnablesDynamicStorageAllocator,
         Execute,
+        GuardsReentrancy,
         MessageDispatcher,
+        UnifiedDispatch,
+        UnifiedDispatcher,
     },
     env_access::{
         ContractEnv,
@@ -70,11 +92,13 @@ pub use self::{
     traits::{
         CheckedInkTrait,
         Constructor,
+        FallibleConstructor,
         FnInput,
         FnOutput,
         FnSelector,
         FnState,
         ImpliesReturn,
+        LazyMessage,
         MessageMut,
         MessageRef,
     },
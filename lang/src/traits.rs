@@ -94,6 +94,16 @@ pub trait Constructor: FnInput + FnSelector + FnState {
     const CALLABLE: fn(<Self as FnInput>::Input) -> <Self as FnState>::State;
 }
 
+/// A dispatchable contract constructor message that might fail.
+pub trait FallibleConstructor: FnInput + FnSelector + FnState {
+    /// The error type of the constructor.
+    type Error;
+
+    const CALLABLE: fn(
+        <Self as FnInput>::Input,
+    ) -> core::result::Result<<Self as FnState>::State, Self::Error>;
+}
+
 /// A `&self` dispatchable contract message.
 pub trait MessageRef: FnInput + FnOutput + FnSelector + FnState {
     const CALLABLE: fn(
@@ -102,6 +112,19 @@ pub trait MessageRef: FnInput + FnOutput + FnSelector + FnState {
     ) -> <Self as FnOutput>::Output;
 }
 
+/// A `&self` dispatchable contract message returning a reference into its storage.
+///
+/// # Note
+///
+/// The referenced value is encoded directly into the return buffer without
+/// materializing an owned copy of it first.
+pub trait LazyMessage: FnInput + FnOutput + FnSelector + FnState {
+    const CALLABLE: fn(
+        &<Self as FnState>::State,
+        <Self as FnInput>::Input,
+    ) -> &<Self as FnOutput>::Output;
+}
+
 /// A `&mut self` dispatchable contract message.
 pub trait MessageMut: FnInput + FnOutput + FnSelector + FnState {
     const CALLABLE: fn(